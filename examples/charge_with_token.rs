@@ -44,7 +44,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  Paid: {}", charge.paid);
     println!("  Captured: {}", charge.captured);
 
-    if let Some(card) = &charge.card {
+    if let Some(card) = charge.card.as_ref().and_then(|card| card.as_card()) {
         println!("  Card: {} ending in {}", card.brand, card.last4);
     }
 
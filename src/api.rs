@@ -0,0 +1,249 @@
+//! Object-safe facade over the PAY.JP client.
+//!
+//! The service types returned by [`PayjpClient`] borrow the client and are not
+//! usable behind trait objects. [`PayjpApi`] exposes the common operations as an
+//! object-safe trait, so the client can be stored as `Arc<dyn PayjpApi>` in
+//! dependency injection containers and replaced with a test double.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{CreateChargeParams, PayjpApi, PayjpClient};
+//! use std::sync::Arc;
+//!
+//! struct Checkout {
+//!     payjp: Arc<dyn PayjpApi>,
+//! }
+//!
+//! impl Checkout {
+//!     async fn pay(&self, token: &str) -> payjp::PayjpResult<String> {
+//!         let charge = self
+//!             .payjp
+//!             .create_charge(CreateChargeParams::new(1000, "jpy").card(token))
+//!             .await?;
+//!         Ok(charge.id)
+//!     }
+//! }
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let checkout = Checkout {
+//!     payjp: Arc::new(PayjpClient::new("sk_test_xxxxx")?),
+//! };
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::PayjpResult;
+use crate::params::ListParams;
+use crate::resources::customer::DeletedCustomer;
+use crate::resources::{
+    CaptureParams, Charge, CreateChargeParams, CreateCustomerParams, Customer, Event,
+    ListChargeParams, ListEventParams, RefundParams, UpdateChargeParams, UpdateCustomerParams,
+};
+#[cfg(feature = "subscriptions")]
+use crate::resources::{
+    CancelSubscriptionParams, CreateSubscriptionParams, Plan, Subscription,
+    UpdateSubscriptionParams,
+};
+use crate::response::ListResponse;
+use async_trait::async_trait;
+
+/// Object-safe interface to the most commonly used PAY.JP operations.
+///
+/// Implemented by [`PayjpClient`]; each method delegates to the corresponding
+/// service method.
+#[async_trait]
+pub trait PayjpApi: Send + Sync {
+    /// Create a charge. See [`ChargeService::create`](crate::ChargeService::create).
+    async fn create_charge(&self, params: CreateChargeParams) -> PayjpResult<Charge>;
+
+    /// Retrieve a charge by ID.
+    async fn retrieve_charge(&self, charge_id: &str) -> PayjpResult<Charge>;
+
+    /// Update a charge.
+    async fn update_charge(
+        &self,
+        charge_id: &str,
+        params: UpdateChargeParams,
+    ) -> PayjpResult<Charge>;
+
+    /// Capture a previously authorized charge.
+    async fn capture_charge(&self, charge_id: &str, params: CaptureParams) -> PayjpResult<Charge>;
+
+    /// Refund a charge.
+    async fn refund_charge(&self, charge_id: &str, params: RefundParams) -> PayjpResult<Charge>;
+
+    /// List charges.
+    async fn list_charges(&self, params: ListChargeParams) -> PayjpResult<ListResponse<Charge>>;
+
+    /// Create a customer.
+    async fn create_customer(&self, params: CreateCustomerParams) -> PayjpResult<Customer>;
+
+    /// Retrieve a customer by ID.
+    async fn retrieve_customer(&self, customer_id: &str) -> PayjpResult<Customer>;
+
+    /// Update a customer.
+    async fn update_customer(
+        &self,
+        customer_id: &str,
+        params: UpdateCustomerParams,
+    ) -> PayjpResult<Customer>;
+
+    /// Delete a customer.
+    async fn delete_customer(&self, customer_id: &str) -> PayjpResult<DeletedCustomer>;
+
+    /// List customers.
+    async fn list_customers(&self, params: ListParams) -> PayjpResult<ListResponse<Customer>>;
+
+    #[cfg(feature = "subscriptions")]
+    /// Retrieve a plan by ID.
+    async fn retrieve_plan(&self, plan_id: &str) -> PayjpResult<Plan>;
+
+    #[cfg(feature = "subscriptions")]
+    /// Create a subscription.
+    async fn create_subscription(
+        &self,
+        params: CreateSubscriptionParams,
+    ) -> PayjpResult<Subscription>;
+
+    #[cfg(feature = "subscriptions")]
+    /// Retrieve a subscription by ID.
+    async fn retrieve_subscription(&self, subscription_id: &str) -> PayjpResult<Subscription>;
+
+    #[cfg(feature = "subscriptions")]
+    /// Update a subscription.
+    async fn update_subscription(
+        &self,
+        subscription_id: &str,
+        params: UpdateSubscriptionParams,
+    ) -> PayjpResult<Subscription>;
+
+    #[cfg(feature = "subscriptions")]
+    /// Cancel a subscription.
+    async fn cancel_subscription(
+        &self,
+        subscription_id: &str,
+        params: CancelSubscriptionParams,
+    ) -> PayjpResult<Subscription>;
+
+    #[cfg(feature = "subscriptions")]
+    /// List subscriptions.
+    async fn list_subscriptions(
+        &self,
+        params: ListParams,
+    ) -> PayjpResult<ListResponse<Subscription>>;
+
+    /// Retrieve an event by ID.
+    async fn retrieve_event(&self, event_id: &str) -> PayjpResult<Event>;
+
+    /// List events.
+    async fn list_events(&self, params: ListEventParams) -> PayjpResult<ListResponse<Event>>;
+}
+
+#[async_trait]
+impl PayjpApi for PayjpClient {
+    async fn create_charge(&self, params: CreateChargeParams) -> PayjpResult<Charge> {
+        self.charges().create(params).await
+    }
+
+    async fn retrieve_charge(&self, charge_id: &str) -> PayjpResult<Charge> {
+        self.charges().retrieve(charge_id).await
+    }
+
+    async fn update_charge(
+        &self,
+        charge_id: &str,
+        params: UpdateChargeParams,
+    ) -> PayjpResult<Charge> {
+        self.charges().update(charge_id, params).await
+    }
+
+    async fn capture_charge(&self, charge_id: &str, params: CaptureParams) -> PayjpResult<Charge> {
+        self.charges().capture(charge_id, params).await
+    }
+
+    async fn refund_charge(&self, charge_id: &str, params: RefundParams) -> PayjpResult<Charge> {
+        self.charges().refund(charge_id, params).await
+    }
+
+    async fn list_charges(&self, params: ListChargeParams) -> PayjpResult<ListResponse<Charge>> {
+        self.charges().list(params).await
+    }
+
+    async fn create_customer(&self, params: CreateCustomerParams) -> PayjpResult<Customer> {
+        self.customers().create(params).await
+    }
+
+    async fn retrieve_customer(&self, customer_id: &str) -> PayjpResult<Customer> {
+        self.customers().retrieve(customer_id).await
+    }
+
+    async fn update_customer(
+        &self,
+        customer_id: &str,
+        params: UpdateCustomerParams,
+    ) -> PayjpResult<Customer> {
+        self.customers().update(customer_id, params).await
+    }
+
+    async fn delete_customer(&self, customer_id: &str) -> PayjpResult<DeletedCustomer> {
+        self.customers().delete(customer_id).await
+    }
+
+    async fn list_customers(&self, params: ListParams) -> PayjpResult<ListResponse<Customer>> {
+        self.customers().list(params).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn retrieve_plan(&self, plan_id: &str) -> PayjpResult<Plan> {
+        self.plans().retrieve(plan_id).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn create_subscription(
+        &self,
+        params: CreateSubscriptionParams,
+    ) -> PayjpResult<Subscription> {
+        self.subscriptions().create(params).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn retrieve_subscription(&self, subscription_id: &str) -> PayjpResult<Subscription> {
+        self.subscriptions().retrieve(subscription_id).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn update_subscription(
+        &self,
+        subscription_id: &str,
+        params: UpdateSubscriptionParams,
+    ) -> PayjpResult<Subscription> {
+        self.subscriptions().update(subscription_id, params).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn cancel_subscription(
+        &self,
+        subscription_id: &str,
+        params: CancelSubscriptionParams,
+    ) -> PayjpResult<Subscription> {
+        self.subscriptions().cancel(subscription_id, params).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn list_subscriptions(
+        &self,
+        params: ListParams,
+    ) -> PayjpResult<ListResponse<Subscription>> {
+        self.subscriptions().list(params).await
+    }
+
+    async fn retrieve_event(&self, event_id: &str) -> PayjpResult<Event> {
+        self.events().retrieve(event_id).await
+    }
+
+    async fn list_events(&self, params: ListEventParams) -> PayjpResult<ListResponse<Event>> {
+        self.events().list(params).await
+    }
+}
@@ -0,0 +1,167 @@
+//! API key providers.
+//!
+//! By default the client holds a fixed API key. Implement [`KeyProvider`] to look
+//! the key up on every request instead, e.g. from a secrets manager, so keys can
+//! be rotated without restarting or rebuilding the client. When the API rejects a
+//! key with HTTP 401, the client asks the provider for a fresh key with
+//! [`KeyProvider::refresh_key`] and retries the request once if the key changed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//! use payjp::auth::{KeyProvider, SecretString};
+//! use payjp::{ClientOptions, PayjpClient, PayjpResult};
+//! use std::sync::RwLock;
+//!
+//! /// Holds the current key; a background task refreshes it from the secrets manager.
+//! struct RotatingKey {
+//!     current: RwLock<String>,
+//! }
+//!
+//! # async fn read_from_vault() -> PayjpResult<String> { unimplemented!() }
+//! #[async_trait]
+//! impl KeyProvider for RotatingKey {
+//!     async fn api_key(&self) -> PayjpResult<SecretString> {
+//!         Ok(self.current.read().unwrap().clone().into())
+//!     }
+//!
+//!     // Called after a 401: the key may have been rotated since the last refresh
+//!     async fn refresh_key(&self) -> PayjpResult<SecretString> {
+//!         let key = read_from_vault().await?;
+//!         *self.current.write().unwrap() = key.clone();
+//!         Ok(key.into())
+//!     }
+//! }
+//!
+//! let provider = RotatingKey { current: RwLock::new("sk_live_xxxxx".to_string()) };
+//! let client = PayjpClient::with_key_provider(provider, ClientOptions::new())?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::error::PayjpResult;
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::Arc;
+
+pub use secrecy::{ExposeSecret, SecretString};
+
+/// Source of the API key, consulted before every request.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Get the API key to use for the next request.
+    ///
+    /// Leading and trailing whitespace in the returned key is ignored. Requests
+    /// fail with [`PayjpError::PublicKeyAsSecret`](crate::PayjpError::PublicKeyAsSecret)
+    /// if this or [`refresh_key`](Self::refresh_key) returns a public key.
+    async fn api_key(&self) -> PayjpResult<SecretString>;
+
+    /// Get the API key again after the API rejected the current one with HTTP 401.
+    ///
+    /// If the returned key differs from the rejected one, the request is retried
+    /// once with it. Providers that cache the key should bypass the cache here.
+    /// The default calls [`api_key`](Self::api_key).
+    async fn refresh_key(&self) -> PayjpResult<SecretString> {
+        self.api_key().await
+    }
+}
+
+/// Kind of a PAY.JP API key, read from its prefix.
+///
+/// # Example
+///
+/// ```
+/// use payjp::auth::KeyKind;
+///
+/// let kind = KeyKind::of("pk_live_xxxxx");
+/// assert!(kind.is_public());
+/// assert_eq!(kind.is_live(), Some(true));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeyKind {
+    /// Test mode secret key (`sk_test_`).
+    SecretTest,
+
+    /// Live mode secret key (`sk_live_`).
+    SecretLive,
+
+    /// Test mode public key (`pk_test_`).
+    PublicTest,
+
+    /// Live mode public key (`pk_live_`).
+    PublicLive,
+
+    /// A key without a known prefix.
+    Unknown,
+}
+
+impl KeyKind {
+    /// Get the kind of `key`, ignoring surrounding whitespace.
+    pub fn of(key: &str) -> Self {
+        let key = key.trim();
+        if key.starts_with("sk_test_") {
+            Self::SecretTest
+        } else if key.starts_with("sk_live_") {
+            Self::SecretLive
+        } else if key.starts_with("pk_test_") {
+            Self::PublicTest
+        } else if key.starts_with("pk_live_") {
+            Self::PublicLive
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether this is a secret key, for [`PayjpClient`](crate::PayjpClient).
+    pub fn is_secret(self) -> bool {
+        matches!(self, Self::SecretTest | Self::SecretLive)
+    }
+
+    /// Whether this is a public key, for [`PayjpPublicClient`](crate::PayjpPublicClient).
+    pub fn is_public(self) -> bool {
+        matches!(self, Self::PublicTest | Self::PublicLive)
+    }
+
+    /// Whether this is a live mode key, or `None` for an unknown kind.
+    pub fn is_live(self) -> Option<bool> {
+        match self {
+            Self::SecretLive | Self::PublicLive => Some(true),
+            Self::SecretTest | Self::PublicTest => Some(false),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// A fixed API key.
+#[derive(Debug, Clone)]
+pub struct StaticKey(SecretString);
+
+impl StaticKey {
+    /// Create a provider that always returns `key`, with surrounding whitespace trimmed.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into().trim().into())
+    }
+
+    /// Get the kind of the key.
+    pub fn kind(&self) -> KeyKind {
+        KeyKind::of(self.0.expose_secret())
+    }
+}
+
+#[async_trait]
+impl KeyProvider for StaticKey {
+    async fn api_key(&self) -> PayjpResult<SecretString> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Shared handle to a [`KeyProvider`] that never prints the key.
+#[derive(Clone)]
+pub(crate) struct KeySource(pub(crate) Arc<dyn KeyProvider>);
+
+impl fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeySource([REDACTED])")
+    }
+}
@@ -0,0 +1,420 @@
+//! Authorize-then-capture workflow helper.
+//!
+//! [`Authorization`] wraps an uncaptured charge and tracks its authorization
+//! deadline, exposing capture, re-authorization and void operations that check
+//! the charge's state before calling the API. [`ExpiryTracker`] finds
+//! authorizations that are about to lapse, and can extend them or report them.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{CreateChargeParams, PayjpClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let mut auth = client
+//!     .charges()
+//!     .authorize(CreateChargeParams::new(5000, "jpy").card("tok_xxxxx").expiry_days(7))
+//!     .await?;
+//! println!("authorized until {:?}", auth.expires_at());
+//!
+//! // Once the order ships, capture the final amount
+//! auth.capture(4500).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Sweep for authorizations expiring within a day, e.g. from a scheduled job:
+//!
+//! ```no_run
+//! use payjp::{ExpiryTracker, PayjpClient};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let report = ExpiryTracker::new(&client, Duration::from_secs(24 * 3600))
+//!     .on_expiring(|charge| println!("{} expires at {:?}", charge.id, charge.expired_at))
+//!     .reauth(Some(7))
+//!     .run()
+//!     .await?;
+//! for (charge_id, error) in &report.failed {
+//!     eprintln!("could not re-authorize {}: {}", charge_id, error);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::pagination::ListStream;
+use crate::resources::{
+    CaptureParams, Charge, CreateChargeParams, ListChargeParams, ReauthParams, RefundParams,
+};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use std::fmt;
+use std::time::Duration;
+
+/// Longest authorization hold PAY.JP allows, in days.
+const MAX_HOLD_DAYS: i64 = 60;
+
+/// Lifecycle state of an [`Authorization`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorizationState {
+    /// Funds are held and the charge can be captured, extended or voided.
+    Authorized,
+
+    /// The charge has been captured.
+    Captured,
+
+    /// The authorization was released without capturing.
+    Voided,
+
+    /// The authorization deadline passed without a capture.
+    Expired,
+}
+
+/// An uncaptured charge and the operations allowed on it.
+#[derive(Debug, Clone)]
+pub struct Authorization<'a> {
+    client: &'a PayjpClient,
+    charge: Charge,
+}
+
+impl<'a> Authorization<'a> {
+    /// Create an uncaptured charge.
+    ///
+    /// `capture` is forced to `false`; use `expiry_days` on the params to choose how
+    /// long the authorization is held.
+    pub async fn create(client: &'a PayjpClient, params: CreateChargeParams) -> PayjpResult<Self> {
+        let charge = client.charges().create(params.capture(false)).await?;
+        Ok(Self::from_charge(client, charge))
+    }
+
+    /// Wrap an existing charge, e.g. one retrieved after a restart.
+    pub fn from_charge(client: &'a PayjpClient, charge: Charge) -> Self {
+        Self { client, charge }
+    }
+
+    /// Get the underlying charge, as of the last operation or refresh.
+    pub fn charge(&self) -> &Charge {
+        &self.charge
+    }
+
+    /// Consume the helper and return the underlying charge.
+    pub fn into_charge(self) -> Charge {
+        self.charge
+    }
+
+    /// Get the authorization deadline, if the charge is uncaptured.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.charge
+            .expired_at
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+    }
+
+    /// Get the current state of the authorization.
+    pub fn state(&self) -> AuthorizationState {
+        self.state_at(Utc::now())
+    }
+
+    /// Get the state of the authorization at the given time.
+    pub fn state_at(&self, now: DateTime<Utc>) -> AuthorizationState {
+        if self.charge.captured {
+            AuthorizationState::Captured
+        } else if self.charge.refunded {
+            AuthorizationState::Voided
+        } else if self.expires_at().is_some_and(|deadline| deadline <= now) {
+            AuthorizationState::Expired
+        } else {
+            AuthorizationState::Authorized
+        }
+    }
+
+    /// Capture `amount` of the authorized amount.
+    ///
+    /// The amount must be positive and must not exceed the authorized amount.
+    pub async fn capture(&mut self, amount: i64) -> PayjpResult<&Charge> {
+        self.ensure_authorized("capture")?;
+        if amount <= 0 || amount > self.charge.amount {
+            return Err(PayjpError::InvalidRequest(format!(
+                "Capture amount {} must be between 1 and the authorized amount {}",
+                amount, self.charge.amount
+            )));
+        }
+
+        let params = CaptureParams::new().amount(amount);
+        self.charge = self.client.charges().capture(&self.charge.id, params).await?;
+        Ok(&self.charge)
+    }
+
+    /// Re-authorize the charge to extend its deadline.
+    ///
+    /// `expiry_days` defaults to the API default when `None`.
+    pub async fn extend(&mut self, expiry_days: Option<i64>) -> PayjpResult<&Charge> {
+        self.ensure_authorized("extend")?;
+
+        let mut params = ReauthParams::new();
+        if let Some(days) = expiry_days {
+            params = params.expiry_days(days);
+        }
+        self.charge = self.client.charges().reauth(&self.charge.id, params).await?;
+        Ok(&self.charge)
+    }
+
+    /// Release the authorization without capturing.
+    pub async fn void(&mut self) -> PayjpResult<&Charge> {
+        self.ensure_authorized("void")?;
+
+        self.charge = self
+            .client
+            .charges()
+            .refund(&self.charge.id, RefundParams::new())
+            .await?;
+        Ok(&self.charge)
+    }
+
+    /// Reload the charge from the API.
+    pub async fn refresh(&mut self) -> PayjpResult<&Charge> {
+        self.charge = self.client.charges().retrieve(&self.charge.id).await?;
+        Ok(&self.charge)
+    }
+
+    /// Fail with `InvalidRequest` unless the authorization can still be operated on.
+    fn ensure_authorized(&self, operation: &str) -> PayjpResult<()> {
+        match self.state() {
+            AuthorizationState::Authorized => Ok(()),
+            state => Err(PayjpError::InvalidRequest(format!(
+                "Cannot {} charge {}: authorization is {:?}",
+                operation, self.charge.id, state
+            ))),
+        }
+    }
+}
+
+/// Stream uncaptured charges whose authorization expires within `window` from `now`.
+///
+/// Only charges created within the longest hold period are listed, since older
+/// authorizations have already lapsed.
+pub(crate) fn expiring<'a>(
+    client: &'a PayjpClient,
+    window: Duration,
+    now: i64,
+) -> ListStream<'a, Charge> {
+    let window = i64::try_from(window.as_secs()).unwrap_or(i64::MAX);
+    let deadline = now.saturating_add(window);
+    let params = ListChargeParams {
+        since: Some(now - MAX_HOLD_DAYS * 24 * 3600),
+        ..ListChargeParams::new().max_limit()
+    };
+    client.charges().list_all(params).filter_local(move |charge| {
+        !charge.captured
+            && !charge.refunded
+            && charge
+                .expired_at
+                .is_some_and(|expired_at| expired_at > now && expired_at <= deadline)
+    })
+}
+
+/// Outcome of an [`ExpiryTracker`] run.
+#[derive(Debug, Default)]
+pub struct ExpiryReport {
+    /// Charges found expiring within the window, as listed.
+    pub expiring: Vec<Charge>,
+
+    /// Charges that were re-authorized, with their new deadlines.
+    pub reauthorized: Vec<Charge>,
+
+    /// IDs of charges whose re-authorization failed, with the error.
+    pub failed: Vec<(String, PayjpError)>,
+}
+
+type Callback<'a> = Box<dyn FnMut(&Charge) + Send + 'a>;
+
+/// Finds uncaptured charges whose authorization is about to expire.
+///
+/// By default the charges are only collected; use [`on_expiring`](Self::on_expiring)
+/// to be notified of each one and [`reauth`](Self::reauth) to extend them.
+pub struct ExpiryTracker<'a> {
+    client: &'a PayjpClient,
+    window: Duration,
+    reauth: Option<ReauthParams>,
+    on_expiring: Option<Callback<'a>>,
+}
+
+impl fmt::Debug for ExpiryTracker<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpiryTracker")
+            .field("window", &self.window)
+            .field("reauth", &self.reauth)
+            .field("on_expiring", &self.on_expiring.is_some())
+            .finish()
+    }
+}
+
+impl<'a> ExpiryTracker<'a> {
+    /// Track authorizations expiring within `window` from now.
+    pub fn new(client: &'a PayjpClient, window: Duration) -> Self {
+        Self {
+            client,
+            window,
+            reauth: None,
+            on_expiring: None,
+        }
+    }
+
+    /// Re-authorize each expiring charge.
+    ///
+    /// `expiry_days` defaults to the API default when `None`.
+    pub fn reauth(mut self, expiry_days: Option<i64>) -> Self {
+        let mut params = ReauthParams::new();
+        if let Some(days) = expiry_days {
+            params = params.expiry_days(days);
+        }
+        self.reauth = Some(params);
+        self
+    }
+
+    /// Call `callback` for each expiring charge, before it is re-authorized.
+    pub fn on_expiring<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&Charge) + Send + 'a,
+    {
+        self.on_expiring = Some(Box::new(callback));
+        self
+    }
+
+    /// Stream the expiring charges without notifying or re-authorizing them.
+    pub fn stream(&self) -> ListStream<'a, Charge> {
+        self.client.charges().expiring(self.window)
+    }
+
+    /// List the expiring charges, notifying and re-authorizing each as configured.
+    ///
+    /// A failed re-authorization is recorded in the report and does not stop the
+    /// run; an error while listing does.
+    pub async fn run(mut self) -> PayjpResult<ExpiryReport> {
+        let mut report = ExpiryReport::default();
+        let mut stream = self.stream();
+        while let Some(charge) = stream.try_next().await? {
+            if let Some(callback) = &mut self.on_expiring {
+                callback(&charge);
+            }
+            if let Some(params) = &self.reauth {
+                match self.client.charges().reauth(&charge.id, params.clone()).await {
+                    Ok(reauthorized) => report.reauthorized.push(reauthorized),
+                    Err(e) => report.failed.push((charge.id.clone(), e)),
+                }
+            }
+            report.expiring.push(charge);
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uncaptured_charge(expired_at: i64) -> Charge {
+        serde_json::from_value(serde_json::json!({
+            "id": "ch_1",
+            "object": "charge",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 5000,
+            "currency": "jpy",
+            "paid": true,
+            "captured": false,
+            "refunded": false,
+            "amount_refunded": 0,
+            "expired_at": expired_at
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_state_checks_guard_operations() {
+        let client = PayjpClient::new("sk_test_xxxxx").expect("Failed to create client");
+        let deadline = Utc::now().timestamp() + 3600;
+
+        let mut auth = Authorization::from_charge(&client, uncaptured_charge(deadline));
+        assert_eq!(auth.state(), AuthorizationState::Authorized);
+        assert!(matches!(
+            auth.capture(6000).await,
+            Err(PayjpError::InvalidRequest(_))
+        ));
+        assert_eq!(
+            auth.state_at(DateTime::from_timestamp(deadline, 0).unwrap()),
+            AuthorizationState::Expired
+        );
+
+        let mut expired = Authorization::from_charge(&client, uncaptured_charge(1700000000));
+        assert_eq!(expired.state(), AuthorizationState::Expired);
+        assert!(matches!(expired.void().await, Err(PayjpError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expiry_tracker_reauthorizes_expiring_charges() {
+        use crate::ClientOptions;
+        use std::sync::{Arc, Mutex};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = Utc::now().timestamp();
+        let charge = |id: &str, expired_at: i64, captured: bool| {
+            let mut charge = serde_json::to_value(uncaptured_charge(expired_at)).unwrap();
+            charge["id"] = id.into();
+            charge["captured"] = captured.into();
+            charge
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    charge("ch_soon", now + 3600, false),
+                    charge("ch_later", now + 7 * 86400, false),
+                    charge("ch_captured", now + 3600, true),
+                    charge("ch_lapsed", now - 3600, false),
+                ],
+                "has_more": false,
+                "url": "/v1/charges",
+                "count": 4
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/charges/ch_soon/reauth"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(charge("ch_soon", now + 7 * 86400, false)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let report = ExpiryTracker::new(&client, Duration::from_secs(86400))
+            .on_expiring({
+                let notified = notified.clone();
+                move |charge| notified.lock().unwrap().push(charge.id.clone())
+            })
+            .reauth(Some(7))
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(*notified.lock().unwrap(), ["ch_soon"]);
+        assert_eq!(report.expiring.len(), 1);
+        assert_eq!(report.reauthorized[0].expired_at, Some(now + 7 * 86400));
+        assert!(report.failed.is_empty());
+    }
+}
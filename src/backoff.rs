@@ -0,0 +1,75 @@
+//! Retry delay strategies.
+//!
+//! Rate-limited requests are retried after a delay computed by a
+//! [`BackoffStrategy`]. The default is [`ExponentialBackoff`]; implement the trait
+//! to plug in an organization-wide retry policy.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::backoff::BackoffStrategy;
+//! use payjp::{ClientOptions, PayjpClient};
+//! use std::time::Duration;
+//!
+//! /// Wait one second longer for every attempt.
+//! #[derive(Debug)]
+//! struct LinearBackoff;
+//!
+//! impl BackoffStrategy for LinearBackoff {
+//!     fn delay(&self, retry_count: u32) -> Duration {
+//!         Duration::from_secs(u64::from(retry_count) + 1)
+//!     }
+//! }
+//!
+//! let options = ClientOptions::new().backoff(LinearBackoff);
+//! let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use rand::Rng;
+use std::fmt;
+use std::time::Duration;
+
+/// Computes how long to wait before retrying a rate-limited request.
+pub trait BackoffStrategy: fmt::Debug + Send + Sync {
+    /// Get the delay before retry number `retry_count` (starting at 0).
+    fn delay(&self, retry_count: u32) -> Duration;
+}
+
+/// Exponential backoff with equal jitter.
+///
+/// The delay doubles with every retry, starting at `initial_delay` and capped at
+/// `max_delay`, and is then randomized between half and all of that value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExponentialBackoff {
+    /// Delay before the first retry, before jitter.
+    pub initial_delay: Duration,
+
+    /// Maximum delay between retries, before jitter.
+    pub max_delay: Duration,
+}
+
+impl ExponentialBackoff {
+    /// Create an exponential backoff with the given initial and maximum delays.
+    pub fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+        }
+    }
+}
+
+impl BackoffStrategy for ExponentialBackoff {
+    /// Uses saturating arithmetic to safely handle edge cases where retry_count
+    /// is very high (e.g., >= 64) which would otherwise cause overflow or panic.
+    fn delay(&self, retry_count: u32) -> Duration {
+        let base = (self.initial_delay.as_millis() as u64)
+            .saturating_mul(2u64.saturating_pow(retry_count));
+        let max = self.max_delay.as_millis() as u64;
+        let capped = base.min(max);
+
+        // Equal jitter: random between capped/2 and capped
+        let jittered = capped / 2 + rand::rng().random_range(0..=capped / 2);
+        Duration::from_millis(jittered)
+    }
+}
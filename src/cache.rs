@@ -0,0 +1,233 @@
+//! Opt-in TTL cache for GET responses.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time-to-live for cached responses (60 seconds).
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Default maximum number of cached responses.
+pub const DEFAULT_CACHE_MAX_ENTRIES: usize = 256;
+
+/// Configuration for the GET response cache.
+///
+/// Only responses for paths under one of the configured prefixes are cached.
+/// Prefixes match whole path segments: `/plans` covers `/plans` and
+/// `/plans/pln_1`, but not `/plansX`. By default these are the rarely-changing
+/// read-mostly resources: `/plans`, `/terms` and `/account`.
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    /// How long a cached response stays valid.
+    pub ttl: Duration,
+
+    /// Maximum number of cached responses.
+    pub max_entries: usize,
+
+    /// Path prefixes whose GET responses may be cached.
+    pub paths: Vec<String>,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_CACHE_TTL,
+            max_entries: DEFAULT_CACHE_MAX_ENTRIES,
+            paths: vec![
+                "/plans".to_string(),
+                "/terms".to_string(),
+                "/account".to_string(),
+            ],
+        }
+    }
+}
+
+impl CacheOptions {
+    /// Create a new `CacheOptions` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time-to-live for cached responses.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the maximum number of cached responses.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Replace the cacheable path prefixes.
+    pub fn paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add a cacheable path prefix (e.g., `"/tenants"`).
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+}
+
+/// A cached response body.
+#[derive(Debug)]
+struct CacheEntry {
+    body: Vec<u8>,
+    inserted_at: Instant,
+}
+
+/// In-memory response cache shared between clones of a client.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    options: CacheOptions,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// Create a new cache with the given options.
+    pub(crate) fn new(options: CacheOptions) -> Self {
+        Self {
+            options,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether responses for the given path may be cached.
+    pub(crate) fn is_cacheable(&self, path: &str) -> bool {
+        self.options.paths.iter().any(|prefix| is_under(path, prefix))
+    }
+
+    /// Get a cached response body if it has not expired.
+    pub(crate) fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.options.ttl => {
+                Some(entry.body.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a response body, evicting expired or the oldest entries when full.
+    pub(crate) fn insert(&self, key: String, body: Vec<u8>) {
+        if self.options.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.options.max_entries && !entries.contains_key(&key) {
+            let ttl = self.options.ttl;
+            entries.retain(|_, entry| entry.inserted_at.elapsed() < ttl);
+
+            if entries.len() >= self.options.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.inserted_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        entries.insert(
+            key,
+            CacheEntry {
+                body,
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Remove all cached responses for the resource collection a path belongs to.
+    ///
+    /// For example, a write to `/plans/pln_xxxxx` invalidates every cached
+    /// response under `/plans`.
+    pub(crate) fn invalidate(&self, path: &str) {
+        let collection = path
+            .trim_start_matches('/')
+            .split(['/', '?'])
+            .next()
+            .unwrap_or_default();
+        let prefix = format!("/{}", collection);
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|key, _| !is_under(key, &prefix));
+    }
+
+    /// Remove all cached responses.
+    pub(crate) fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Whether `path` (or a cache key, which is a path with a query) lies under
+/// `prefix`, matching whole path segments.
+fn is_under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_cacheable_matches_whole_segments() {
+        let cache = ResponseCache::new(CacheOptions::new());
+        assert!(cache.is_cacheable("/plans"));
+        assert!(cache.is_cacheable("/plans/pln_1"));
+        assert!(cache.is_cacheable("/plans?limit=10"));
+        assert!(!cache.is_cacheable("/plansX"));
+        assert!(!cache.is_cacheable("/accounts"));
+        assert!(!cache.is_cacheable("/charges"));
+    }
+
+    #[test]
+    fn test_get_drops_expired_entries() {
+        let cache = ResponseCache::new(CacheOptions::new().ttl(Duration::ZERO));
+        cache.insert("/plans?".to_string(), b"{}".to_vec());
+        assert_eq!(cache.get("/plans?"), None);
+        assert!(cache.entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_insert_evicts_the_oldest_entry_when_full() {
+        let cache = ResponseCache::new(CacheOptions::new().max_entries(2));
+        for key in ["/plans/pln_1?", "/plans/pln_2?", "/plans/pln_3?"] {
+            cache.insert(key.to_string(), key.as_bytes().to_vec());
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(cache.get("/plans/pln_1?"), None);
+        assert!(cache.get("/plans/pln_2?").is_some());
+        assert!(cache.get("/plans/pln_3?").is_some());
+    }
+
+    #[test]
+    fn test_invalidate_drops_the_collection_by_whole_segments() {
+        let cache = ResponseCache::new(CacheOptions::new());
+        for key in ["/plans?", "/plans/pln_1?", "/plansX?", "/terms?"] {
+            cache.insert(key.to_string(), b"{}".to_vec());
+        }
+        cache.invalidate("/plans/pln_1");
+        assert_eq!(cache.get("/plans?"), None);
+        assert_eq!(cache.get("/plans/pln_1?"), None);
+        assert!(cache.get("/plansX?").is_some());
+        assert!(cache.get("/terms?").is_some());
+    }
+}
@@ -0,0 +1,216 @@
+//! Fault injection for resilience testing (requires the `chaos` feature).
+//!
+//! [`ChaosOptions`] wraps the client's HTTP transport and, at the configured
+//! probabilities, delays requests or replaces their outcome with a rate limit,
+//! server error, timeout or truncated response body. Use it in CI to verify that
+//! code built on the SDK copes with PAY.JP misbehaving. Never enable it in
+//! production.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::chaos::ChaosOptions;
+//! use payjp::{ClientOptions, PayjpClient};
+//! use std::time::Duration;
+//!
+//! let chaos = ChaosOptions::new()
+//!     .latency(0.5, Duration::from_millis(300))
+//!     .rate_limit(0.1)
+//!     .server_error(0.05)
+//!     .timeout(0.01);
+//!
+//! let options = ClientOptions::new().chaos(chaos);
+//! let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::client;
+use crate::error::PayjpResult;
+use rand::Rng;
+use reqwest::{RequestBuilder, StatusCode};
+use std::time::Duration;
+
+/// Probabilities of each injected fault, in `[0.0, 1.0]`.
+///
+/// Faults are rolled independently for every HTTP request, including retries.
+/// At most one of rate limit, server error, timeout and truncated body is
+/// injected per request, checked in that order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosOptions {
+    /// Probability of delaying the request by `latency`.
+    pub latency_rate: f64,
+
+    /// Extra delay added before sending the request.
+    pub latency: Duration,
+
+    /// Probability of answering with `429 Too Many Requests` without sending the request.
+    pub rate_limit_rate: f64,
+
+    /// Probability of answering with `503 Service Unavailable` without sending the request.
+    pub server_error_rate: f64,
+
+    /// Probability of failing the request with a timeout error.
+    ///
+    /// Like a real timeout, the request may or may not have reached the server.
+    pub timeout_rate: f64,
+
+    /// Probability of cutting the response body in half.
+    pub truncate_rate: f64,
+}
+
+impl ChaosOptions {
+    /// Create options that inject no faults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay requests by `latency` with the given probability.
+    pub fn latency(mut self, rate: f64, latency: Duration) -> Self {
+        self.latency_rate = rate.clamp(0.0, 1.0);
+        self.latency = latency;
+        self
+    }
+
+    /// Answer requests with `429 Too Many Requests` with the given probability.
+    pub fn rate_limit(mut self, rate: f64) -> Self {
+        self.rate_limit_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Answer requests with `503 Service Unavailable` with the given probability.
+    pub fn server_error(mut self, rate: f64) -> Self {
+        self.server_error_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fail requests with a timeout error with the given probability.
+    pub fn timeout(mut self, rate: f64) -> Self {
+        self.timeout_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Truncate response bodies with the given probability.
+    pub fn truncated_body(mut self, rate: f64) -> Self {
+        self.truncate_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Send a request through the fault injector.
+    pub(crate) async fn execute(
+        &self,
+        request: RequestBuilder,
+        max_response_size: Option<usize>,
+    ) -> reqwest::Result<(StatusCode, PayjpResult<Vec<u8>>)> {
+        if roll(self.latency_rate) {
+            tokio::time::sleep(self.latency).await;
+        }
+
+        if roll(self.rate_limit_rate) {
+            return Ok(synthetic_error(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many requests",
+            ));
+        }
+        if roll(self.server_error_rate) {
+            return Ok(synthetic_error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service unavailable",
+            ));
+        }
+
+        let request = if roll(self.timeout_rate) {
+            request.timeout(Duration::from_nanos(1))
+        } else {
+            request
+        };
+        let response = request.send().await?;
+        let status = response.status();
+        let mut bytes = client::read_body(response, max_response_size).await;
+
+        if roll(self.truncate_rate) {
+            if let Ok(bytes) = &mut bytes {
+                bytes.truncate(bytes.len() / 2);
+            }
+        }
+        Ok((status, bytes))
+    }
+}
+
+/// Return `true` with the given probability.
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && rand::rng().random_bool(rate.min(1.0))
+}
+
+/// Build an error response in the PAY.JP error format.
+fn synthetic_error(status: StatusCode, message: &str) -> (StatusCode, PayjpResult<Vec<u8>>) {
+    let body = serde_json::json!({
+        "error": {
+            "status": status.as_u16(),
+            "type": "server_error",
+            "message": format!("{} (injected by payjp::chaos)", message)
+        }
+    });
+    (status, Ok(body.to_string().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PayjpError;
+    use crate::{ClientOptions, PayjpClient};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_injected_faults() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .mount(&server)
+            .await;
+
+        let client = |chaos: ChaosOptions| {
+            let options = ClientOptions::new()
+                .base_url(&server.uri())
+                .max_retry(0)
+                .chaos(chaos);
+            PayjpClient::with_options("sk_test_xxxxx", options).expect("Failed to create client")
+        };
+
+        let result = client(ChaosOptions::new().rate_limit(1.0))
+            .account()
+            .retrieve()
+            .await;
+        assert!(matches!(result, Err(PayjpError::RateLimit)));
+
+        let result = client(ChaosOptions::new().server_error(1.0))
+            .account()
+            .retrieve()
+            .await;
+        assert!(matches!(result, Err(PayjpError::Api(ref e)) if e.status == 503));
+
+        let result = client(ChaosOptions::new().truncated_body(1.0))
+            .account()
+            .retrieve()
+            .await;
+        assert!(matches!(result, Err(PayjpError::Decode(_))));
+
+        let result = client(ChaosOptions::new().timeout(1.0))
+            .account()
+            .retrieve()
+            .await;
+        assert!(matches!(result, Err(PayjpError::Network(ref e)) if e.is_timeout()));
+
+        let requests = server.received_requests().await.unwrap_or_default();
+        assert!(
+            requests.len() <= 2,
+            "synthetic errors must not reach the server"
+        );
+    }
+}
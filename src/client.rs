@@ -1,13 +1,30 @@
 //! PAY.JP API client implementation.
 
-use crate::error::{ErrorResponse, PayjpError, PayjpResult};
+use crate::auth::{ExposeSecret, KeyKind, KeyProvider, KeySource, StaticKey};
+use crate::backoff::{BackoffStrategy, ExponentialBackoff};
+use crate::cache::{CacheOptions, ResponseCache};
+#[cfg(feature = "chaos")]
+use crate::chaos::ChaosOptions;
+use crate::error::{DecodeError, ErrorResponse, PayjpError, PayjpResult};
+use crate::extras;
+use crate::livemode::{self, LivemodeCheck};
+use crate::metrics::{self, MetricsSink, RateLimitEvent, RateLimitSource, RequestMetrics};
+use crate::pagination::PageParams;
+use crate::redact;
+use crate::request_id::{
+    self, IdempotencyLog, CORRELATION_ID_HEADER, IDEMPOTENCY_KEY_HEADER, REQUEST_ID_HEADER,
+};
+use crate::response::ListResponse;
+use crate::shutdown::Lifecycle;
 use base64::{engine::general_purpose, Engine as _};
-use rand::Rng;
-use reqwest::header::HeaderValue;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Method, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::time::Duration;
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Default base URL for PAY.JP API.
 pub const DEFAULT_BASE_URL: &str = "https://api.pay.jp/v1";
@@ -30,6 +47,9 @@ pub struct ClientOptions {
     /// Base URL for the API (default: https://api.pay.jp/v1).
     pub base_url: String,
 
+    /// Fallback base URLs, tried in order when the base URL cannot be connected to.
+    pub failover_urls: Vec<String>,
+
     /// Maximum number of retry attempts for rate-limited requests.
     pub max_retry: u32,
 
@@ -39,18 +59,93 @@ pub struct ClientOptions {
     /// Maximum delay between retries.
     pub retry_max_delay: Duration,
 
+    /// Custom retry delay strategy; overrides `retry_initial_delay` and `retry_max_delay`.
+    pub backoff: Option<Arc<dyn BackoffStrategy>>,
+
     /// HTTP client timeout.
     pub timeout: Duration,
+
+    /// Maximum size of a response body in bytes (optional, unlimited by default).
+    pub max_response_size: Option<usize>,
+
+    /// Whether to check that card tokens are unused before creating resources from them.
+    pub verify_tokens: bool,
+
+    /// Maximum age of a card token, checked when `verify_tokens` is enabled (optional).
+    pub max_token_age: Option<Duration>,
+
+    /// Whether to check that the API key's mode matches the base URL and responses.
+    pub livemode_check: LivemodeCheck,
+
+    /// Opt-in cache for GET responses (disabled by default).
+    pub cache: Option<CacheOptions>,
+
+    /// DNS resolution overrides, as `(domain, address)` pairs.
+    pub resolve: Vec<(String, SocketAddr)>,
+
+    /// Proxy URL for all requests (optional).
+    pub proxy: Option<String>,
+
+    /// Headers sent with every request, as `(name, value)` pairs.
+    pub default_headers: Vec<(String, String)>,
+
+    /// Whether to keep a sanitized copy of the most recent request/response.
+    pub capture_last_exchange: bool,
+
+    /// How request and response bodies are recorded.
+    pub body_logging: BodyLogging,
+
+    /// Receiver for per-request timing data (optional).
+    pub metrics: Option<Arc<dyn MetricsSink>>,
+
+    /// Pre-built HTTP client to use instead of constructing one (optional).
+    pub http_client: Option<reqwest::Client>,
+
+    /// Application identification appended to the User-Agent (optional).
+    pub app_info: Option<AppInfo>,
+
+    /// Fault injection for resilience testing (requires the `chaos` feature).
+    #[cfg(feature = "chaos")]
+    pub chaos: Option<ChaosOptions>,
+
+    /// Whether to accept gzip-compressed responses (requires the `gzip` feature).
+    #[cfg(feature = "gzip")]
+    pub gzip: bool,
+
+    /// Whether to accept brotli-compressed responses (requires the `brotli` feature).
+    #[cfg(feature = "brotli")]
+    pub brotli: bool,
 }
 
 impl Default for ClientOptions {
     fn default() -> Self {
         Self {
             base_url: DEFAULT_BASE_URL.to_string(),
+            failover_urls: Vec::new(),
             max_retry: DEFAULT_MAX_RETRY,
             retry_initial_delay: DEFAULT_RETRY_INITIAL_DELAY,
             retry_max_delay: DEFAULT_RETRY_MAX_DELAY,
+            backoff: None,
             timeout: Duration::from_secs(30),
+            max_response_size: None,
+            verify_tokens: false,
+            max_token_age: None,
+            livemode_check: LivemodeCheck::Off,
+            cache: None,
+            resolve: Vec::new(),
+            proxy: None,
+            default_headers: Vec::new(),
+            capture_last_exchange: false,
+            body_logging: BodyLogging::Redacted,
+            metrics: None,
+            http_client: None,
+            app_info: None,
+            #[cfg(feature = "chaos")]
+            chaos: None,
+            #[cfg(feature = "gzip")]
+            gzip: true,
+            #[cfg(feature = "brotli")]
+            brotli: true,
         }
     }
 }
@@ -67,6 +162,29 @@ impl ClientOptions {
         self
     }
 
+    /// Add a fallback base URL (e.g., an internal mirror or egress proxy).
+    ///
+    /// When a connection to the base URL fails, the request is retried against each
+    /// fallback URL in the order they were added. Only connection errors trigger
+    /// failover; API errors and timeouts are returned as-is, so a request that may
+    /// have reached PAY.JP is never sent twice.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpClient};
+    ///
+    /// let options = ClientOptions::new()
+    ///     .failover_url("https://payjp-proxy.internal.example.com/v1");
+    ///
+    /// let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn failover_url(mut self, url: &str) -> Self {
+        self.failover_urls.push(url.to_string());
+        self
+    }
+
     /// Set the maximum number of retry attempts.
     pub fn max_retry(mut self, max_retry: u32) -> Self {
         self.max_retry = max_retry;
@@ -85,22 +203,432 @@ impl ClientOptions {
         self
     }
 
+    /// Set a custom retry delay strategy.
+    ///
+    /// Replaces the default [`ExponentialBackoff`] built from `retry_initial_delay`
+    /// and `retry_max_delay`. See the [`backoff`](crate::backoff) module for an example.
+    pub fn backoff(mut self, strategy: impl BackoffStrategy + 'static) -> Self {
+        self.backoff = Some(Arc::new(strategy));
+        self
+    }
+
     /// Set the HTTP client timeout.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
     }
+
+    /// Fail requests whose response body is larger than `bytes`.
+    ///
+    /// Protects memory in constrained workers from unexpectedly large list or
+    /// statement responses, or from a misbehaving proxy. Bodies are read in
+    /// chunks and abandoned as soon as they exceed the limit, with
+    /// [`PayjpError::ResponseTooLarge`]. Note that the request itself may still
+    /// have been processed by PAY.JP.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpClient};
+    ///
+    /// let options = ClientOptions::new().max_response_size(4 * 1024 * 1024);
+    ///
+    /// let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Check that a card token is unused before creating a charge, customer or
+    /// card from it (default: false).
+    ///
+    /// Each create call with a `tok_` card sends one extra request to retrieve
+    /// the token, and fails with [`PayjpError::TokenAlreadyUsed`] instead of an
+    /// API card error when the token was used before, e.g. by a double-submitted
+    /// checkout form.
+    pub fn verify_tokens(mut self, enabled: bool) -> Self {
+        self.verify_tokens = enabled;
+        self
+    }
+
+    /// Reject card tokens created more than `max_age` ago with
+    /// [`PayjpError::TokenExpired`]. Enables [`verify_tokens`](Self::verify_tokens).
+    pub fn max_token_age(mut self, max_age: Duration) -> Self {
+        self.verify_tokens = true;
+        self.max_token_age = Some(max_age);
+        self
+    }
+
+    /// Check that the API key's mode (live or test) matches the base URL and the
+    /// `livemode` of responses. See the [`livemode`](crate::livemode) module.
+    pub fn livemode_check(mut self, check: LivemodeCheck) -> Self {
+        self.livemode_check = check;
+        self
+    }
+
+    /// Enable caching of GET responses for read-mostly resources.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{CacheOptions, ClientOptions, PayjpClient};
+    /// use std::time::Duration;
+    ///
+    /// let options = ClientOptions::new()
+    ///     .cache(CacheOptions::new().ttl(Duration::from_secs(300)));
+    ///
+    /// let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cache(mut self, options: CacheOptions) -> Self {
+        self.cache = Some(options);
+        self
+    }
+
+    /// Override DNS resolution for a domain.
+    ///
+    /// Requests to `domain` are sent to `addr` instead of the address returned by DNS.
+    /// The port in `addr` is used unless the URL specifies one explicitly (use port 0
+    /// for the scheme's default port). This is useful for hermetic tests that point
+    /// `api.pay.jp` at a local server, or for split-horizon DNS environments.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpClient};
+    ///
+    /// let options = ClientOptions::new()
+    ///     .resolve("api.pay.jp", "127.0.0.1:8443".parse()?);
+    ///
+    /// let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve.push((domain.into(), addr));
+        self
+    }
+
+    /// Send all requests through the proxy at `url` (e.g., `http://proxy:3128`).
+    ///
+    /// An invalid URL is reported as [`PayjpError::InvalidRequest`] when the
+    /// client is created.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Send the header `name: value` with every request, e.g. for an egress
+    /// gateway that requires its own authentication.
+    ///
+    /// Headers set by the SDK, such as `Authorization`, take precedence. An
+    /// invalid name or value is reported as [`PayjpError::InvalidRequest`] when
+    /// the client is created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpPublicClient};
+    ///
+    /// let options = ClientOptions::new()
+    ///     .proxy("http://proxy.internal.example.com:3128")
+    ///     .default_header("X-Gateway-Token", "xxxxx");
+    ///
+    /// let client = PayjpPublicClient::with_options("pk_test_xxxxx", "password", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Keep a sanitized copy of the most recent request/response for debugging.
+    ///
+    /// When enabled, [`PayjpClient::last_exchange`] returns the method, path, status
+    /// and bodies of the latest API call, with card numbers and CVCs redacted.
+    pub fn capture_last_exchange(mut self, enabled: bool) -> Self {
+        self.capture_last_exchange = enabled;
+        self
+    }
+
+    /// Set how request and response bodies are recorded.
+    ///
+    /// [`BodyLogging::Hashed`] keeps a SHA-256 digest and size of each body
+    /// instead of its contents, so request uniqueness and replays can be audited
+    /// without storing card or personal data.
+    pub fn body_logging(mut self, body_logging: BodyLogging) -> Self {
+        self.body_logging = body_logging;
+        self
+    }
+
+    /// Report the method, path template, status and duration of every request.
+    ///
+    /// See the [`metrics`](crate::metrics) module for an example.
+    pub fn metrics(mut self, sink: impl MetricsSink + 'static) -> Self {
+        self.metrics = Some(Arc::new(sink));
+        self
+    }
+
+    /// Use an existing HTTP client, sharing its connection pool, proxy and TLS settings.
+    ///
+    /// The client is used as-is: `timeout`, `resolve`, `proxy`, `default_header`
+    /// and the compression options are not applied to it, so configure them on
+    /// the client itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpClient};
+    ///
+    /// let http = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::https("http://proxy.internal.example.com:3128")?)
+    ///     .build()?;
+    ///
+    /// let client = PayjpClient::with_options(
+    ///     "sk_test_xxxxx",
+    ///     ClientOptions::new().http_client(http),
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Identify the application or plugin built on this SDK in the User-Agent.
+    ///
+    /// Helps PAY.JP support attribute traffic to a platform or plugin when
+    /// diagnosing problems.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use payjp::{ClientOptions, PayjpClient};
+    ///
+    /// let options = ClientOptions::new()
+    ///     .app_info("MyShopPlugin", Some("1.2.0"), Some("https://example.com/plugin"));
+    ///
+    /// // User-Agent: payjp-rust/x.y.z MyShopPlugin/1.2.0 (https://example.com/plugin)
+    /// let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn app_info(
+        mut self,
+        name: impl Into<String>,
+        version: Option<&str>,
+        url: Option<&str>,
+    ) -> Self {
+        self.app_info = Some(AppInfo {
+            name: name.into(),
+            version: version.map(str::to_string),
+            url: url.map(str::to_string),
+        });
+        self
+    }
+
+    /// Inject faults into requests at the given probabilities (for testing only).
+    ///
+    /// See the [`chaos`](crate::chaos) module for an example.
+    #[cfg(feature = "chaos")]
+    pub fn chaos(mut self, options: ChaosOptions) -> Self {
+        self.chaos = Some(options);
+        self
+    }
+
+    /// Set whether to accept gzip-compressed responses (default: true).
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Set whether to accept brotli-compressed responses (default: true).
+    #[cfg(feature = "brotli")]
+    pub fn brotli(mut self, enabled: bool) -> Self {
+        self.brotli = enabled;
+        self
+    }
+
+    /// Get the configured retry delay strategy.
+    pub(crate) fn build_backoff(&self) -> Arc<dyn BackoffStrategy> {
+        self.backoff.clone().unwrap_or_else(|| {
+            Arc::new(ExponentialBackoff::new(
+                self.retry_initial_delay,
+                self.retry_max_delay,
+            ))
+        })
+    }
+
+    /// Build the User-Agent header value, including the application info if set.
+    pub(crate) fn build_user_agent(&self) -> PayjpResult<HeaderValue> {
+        let Some(app_info) = &self.app_info else {
+            return Ok(HeaderValue::from_static(USER_AGENT));
+        };
+
+        let mut user_agent = format!("{} {}", USER_AGENT, app_info.name);
+        if let Some(version) = &app_info.version {
+            user_agent.push('/');
+            user_agent.push_str(version);
+        }
+        if let Some(url) = &app_info.url {
+            user_agent.push_str(&format!(" ({})", url));
+        }
+        HeaderValue::from_str(&user_agent)
+            .map_err(|e| PayjpError::InvalidRequest(format!("Invalid app info: {}", e)))
+    }
+
+    /// Build the underlying HTTP client from these options.
+    pub(crate) fn build_http_client(&self) -> PayjpResult<reqwest::Client> {
+        if let Some(client) = &self.http_client {
+            return Ok(client.clone());
+        }
+
+        let mut builder = reqwest::Client::builder().timeout(self.timeout);
+
+        for (domain, addr) in &self.resolve {
+            builder = builder.resolve(domain, *addr);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str()).map_err(|e| {
+                PayjpError::InvalidRequest(format!("Invalid proxy URL {}: {}", proxy, e))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        if !self.default_headers.is_empty() {
+            let mut headers = HeaderMap::new();
+            for (name, value) in &self.default_headers {
+                let invalid = |e: String| {
+                    PayjpError::InvalidRequest(format!("Invalid header {}: {}", name, e))
+                };
+                let header_name =
+                    HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+                let header_value =
+                    HeaderValue::from_str(value).map_err(|e| invalid(e.to_string()))?;
+                headers.append(header_name, header_value);
+            }
+            builder = builder.default_headers(headers);
+        }
+
+        #[cfg(feature = "gzip")]
+        let builder = builder.gzip(self.gzip);
+
+        #[cfg(feature = "brotli")]
+        let builder = builder.brotli(self.brotli);
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Identification of an application or plugin built on this SDK.
+///
+/// Set with [`ClientOptions::app_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppInfo {
+    /// Application name.
+    pub name: String,
+
+    /// Application version (optional).
+    pub version: Option<String>,
+
+    /// Application URL (optional).
+    pub url: Option<String>,
+}
+
+/// How request and response bodies are recorded in captured exchanges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyLogging {
+    /// Record bodies, with card numbers and CVCs in request parameters redacted (default).
+    #[default]
+    Redacted,
+
+    /// Record only a SHA-256 digest and the size of each body.
+    ///
+    /// Request digests are taken over the redacted parameters, so they cannot be
+    /// used to recover card numbers. With the `tracing` feature, every request is
+    /// also logged with its digests at target `payjp::audit`.
+    Hashed,
+}
+
+/// SHA-256 digest and size of a request or response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BodyDigest {
+    /// Lowercase hex SHA-256 digest.
+    pub sha256: String,
+
+    /// Size in bytes.
+    pub size: usize,
+}
+
+impl BodyDigest {
+    /// Digest `body`.
+    pub fn of(body: &[u8]) -> Self {
+        let sha256 = Sha256::digest(body)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Self {
+            sha256,
+            size: body.len(),
+        }
+    }
+}
+
+/// A sanitized record of a single API request and its response.
+///
+/// Card numbers and CVCs in the request parameters are redacted. With
+/// [`BodyLogging::Hashed`], only digests of the bodies are kept.
+#[derive(Debug, Clone, Serialize)]
+pub struct Exchange {
+    /// HTTP method (e.g., "POST").
+    pub method: String,
+
+    /// Request path relative to the base URL (e.g., "/charges").
+    pub path: String,
+
+    /// Client-generated request ID sent in the `X-Request-Id` header.
+    pub request_id: String,
+
+    /// Form-encoded request parameters or query string, redacted (optional).
+    pub request_body: Option<String>,
+
+    /// Digest of the redacted request parameters (with [`BodyLogging::Hashed`], optional).
+    pub request_body_digest: Option<BodyDigest>,
+
+    /// HTTP status code (absent if no response was received).
+    pub status: Option<u16>,
+
+    /// Response body (absent if no response was received).
+    pub response_body: Option<String>,
+
+    /// Digest of the response body (with [`BodyLogging::Hashed`], optional).
+    pub response_body_digest: Option<BodyDigest>,
 }
 
 /// The main PAY.JP API client.
 #[derive(Debug, Clone)]
 pub struct PayjpClient {
-    api_key: String,
+    key: KeySource,
     http_client: reqwest::Client,
+    user_agent: HeaderValue,
     base_url: String,
+    failover_urls: Vec<String>,
     max_retry: u32,
-    retry_initial_delay: Duration,
-    retry_max_delay: Duration,
+    backoff: Arc<dyn BackoffStrategy>,
+    max_response_size: Option<usize>,
+    verify_tokens: bool,
+    max_token_age: Option<Duration>,
+    livemode_check: LivemodeCheck,
+    cache: Option<Arc<ResponseCache>>,
+    last_exchange: Option<Arc<Mutex<Option<Exchange>>>>,
+    body_logging: BodyLogging,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    lifecycle: Arc<Lifecycle>,
+    idempotency_log: Arc<IdempotencyLog>,
+    #[cfg(feature = "chaos")]
+    chaos: Option<ChaosOptions>,
 }
 
 impl PayjpClient {
@@ -108,7 +636,8 @@ impl PayjpClient {
     ///
     /// Leading and trailing whitespace in the API key will be automatically trimmed.
     /// This is useful when reading API keys from environment variables or shell commands,
-    /// which often include trailing newlines.
+    /// which often include trailing newlines. Public keys (`pk_`) are rejected with
+    /// [`PayjpError::PublicKeyAsSecret`].
     ///
     /// # Example
     ///
@@ -142,29 +671,134 @@ impl PayjpClient {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn with_options(api_key: impl Into<String>, options: ClientOptions) -> PayjpResult<Self> {
-        let http_client = reqwest::Client::builder()
-            .timeout(options.timeout)
-            .build()?;
+        let key = StaticKey::new(api_key);
+        if key.kind().is_public() {
+            return Err(PayjpError::PublicKeyAsSecret);
+        }
+        Self::with_key_provider(key, options)
+    }
+
+    /// Create a new PAY.JP client that looks up the API key before every request.
+    ///
+    /// See [`KeyProvider`] for integrating secrets managers and key rotation.
+    pub fn with_key_provider(
+        provider: impl KeyProvider + 'static,
+        options: ClientOptions,
+    ) -> PayjpResult<Self> {
+        let http_client = options.build_http_client()?;
+        let backoff = options.build_backoff();
+        let user_agent = options.build_user_agent()?;
 
         Ok(Self {
-            api_key: api_key.into().trim().to_string(),
+            key: KeySource(Arc::new(provider)),
             http_client,
+            user_agent,
             base_url: options.base_url,
+            failover_urls: options.failover_urls,
             max_retry: options.max_retry,
-            retry_initial_delay: options.retry_initial_delay,
-            retry_max_delay: options.retry_max_delay,
+            backoff,
+            max_response_size: options.max_response_size,
+            verify_tokens: options.verify_tokens,
+            max_token_age: options.max_token_age,
+            livemode_check: options.livemode_check,
+            cache: options.cache.map(|cache| Arc::new(ResponseCache::new(cache))),
+            last_exchange: options
+                .capture_last_exchange
+                .then(|| Arc::new(Mutex::new(None))),
+            body_logging: options.body_logging,
+            metrics: options.metrics,
+            lifecycle: Arc::default(),
+            idempotency_log: Arc::default(),
+            #[cfg(feature = "chaos")]
+            chaos: options.chaos,
         })
     }
 
+    /// Check the card token `card` before it is used, if token verification is
+    /// enabled. Card IDs and other non-token values are not checked.
+    pub(crate) async fn verify_token(&self, card: Option<&str>) -> PayjpResult<()> {
+        if !self.verify_tokens {
+            return Ok(());
+        }
+        let Some(token_id) = card.filter(|card| card.starts_with("tok_")) else {
+            return Ok(());
+        };
+        let token = self.tokens().retrieve(token_id).await?;
+        if token.used {
+            return Err(PayjpError::TokenAlreadyUsed { token: token.id });
+        }
+        if let Some(max_age) = self.max_token_age {
+            let age = chrono::Utc::now().timestamp() - token.created;
+            if age > i64::try_from(max_age.as_secs()).unwrap_or(i64::MAX) {
+                return Err(PayjpError::TokenExpired {
+                    token: token.id,
+                    age: Duration::from_secs(age.max(0) as u64),
+                    max_age,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the in-flight request tracking shared by this client's clones.
+    pub(crate) fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
+    }
+
     /// Get the base URL for the API.
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// Get a sanitized copy of the most recent request and response.
+    ///
+    /// Returns `None` unless [`ClientOptions::capture_last_exchange`] is enabled
+    /// and at least one request has been sent.
+    pub fn last_exchange(&self) -> Option<Exchange> {
+        self.last_exchange
+            .as_ref()
+            .and_then(|last| last.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+
+    /// Open a connection to the API ahead of the first request.
+    ///
+    /// Sends an unauthenticated `HEAD` request to the base URL, which resolves
+    /// DNS and completes the TCP and TLS handshakes. The connection stays in the
+    /// pool (shared with clones), so the first real call after a cold start,
+    /// often a user-facing charge, does not pay for it. Any HTTP response counts
+    /// as success; only connection failures are returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// client.warm_up().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn warm_up(&self) -> PayjpResult<()> {
+        self.http_client
+            .head(&self.base_url)
+            .header("User-Agent", self.user_agent.clone())
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Remove all responses from the GET response cache, if enabled.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
     /// Get the API key (for testing purposes).
     #[cfg(test)]
-    pub(crate) fn api_key(&self) -> &str {
-        &self.api_key
+    pub(crate) fn api_key(&self) -> String {
+        let key = futures::executor::block_on(self.key.0.api_key()).expect("Failed to get API key");
+        key.expose_secret().to_string()
     }
 
     /// Send a GET request.
@@ -183,6 +817,16 @@ impl PayjpClient {
             .await
     }
 
+    /// Send a GET request to a list endpoint, validating the list parameters first.
+    pub(crate) async fn get_list<T: DeserializeOwned, P: PageParams>(
+        &self,
+        path: &str,
+        params: &P,
+    ) -> PayjpResult<ListResponse<T>> {
+        params.validate()?;
+        self.get_with_params(path, params).await
+    }
+
     /// Send a POST request.
     pub(crate) async fn post<T: DeserializeOwned, P: Serialize>(
         &self,
@@ -199,117 +843,536 @@ impl PayjpClient {
             .await
     }
 
-    /// Send a request with retry logic for rate limiting.
+    /// Send a request with retry logic for rate limiting, tracked for shutdown.
     async fn request_with_retry<T: DeserializeOwned>(
         &self,
         method: Method,
         path: &str,
         body: Option<&impl Serialize>,
     ) -> PayjpResult<T> {
+        let request_id = request_id::generate_request_id();
+        let operation = self.lifecycle.begin(method.as_str(), path, &request_id)?;
+        operation
+            .run(self.send_with_retry(method, path, body, &request_id))
+            .await
+    }
+
+    /// Send a request, serving it from the cache and retrying on rate limits.
+    async fn send_with_retry<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+        request_id: &str,
+    ) -> PayjpResult<T> {
+        // Serve cacheable GET requests from the response cache when possible
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.is_cacheable(path) && extras::current().is_none());
+        let cache_entry = match cache {
+            Some(cache) if method == Method::GET => Some((cache, Self::cache_key(path, body)?)),
+            _ => None,
+        };
+        if let Some((cache, key)) = &cache_entry {
+            if let Some(cached) = cache.get(key) {
+                let data = DecodeError::decode(&cached)
+                    .map_err(|e| e.with_request(method.as_str(), path, None))?;
+                return Ok(data);
+            }
+        }
+
         let mut retry_count = 0;
 
-        loop {
-            match self.send_request(method.clone(), path, body).await {
-                Ok(response) => return Ok(response),
-                Err(PayjpError::RateLimit) if retry_count < self.max_retry => {
-                    let delay = self.calculate_retry_delay(retry_count);
-                    tokio::time::sleep(delay).await;
+        let (status, response) = loop {
+            match self.send_with_failover(method.clone(), path, body, request_id).await {
+                Ok(response) => break response,
+                Err(e @ PayjpError::RateLimit) => {
+                    let retry = retry_count < self.max_retry && is_retry_safe(&method);
+                    let delay = if retry {
+                        self.calculate_retry_delay(retry_count)
+                    } else {
+                        Duration::ZERO
+                    };
+                    self.record_rate_limit(&method, path, RateLimitSource::Server, delay);
+                    if !retry {
+                        return Err(e);
+                    }
+                    log_retry(&method, path, request_id, retry_count + 1, delay, &e);
+                    // A rate-limited request was not processed, so stop retrying
+                    // it once shutdown starts
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = self.lifecycle.draining() => return Err(e),
+                    }
                     retry_count += 1;
                 }
                 Err(e) => return Err(e),
             }
+        };
+
+        // Only responses that are cached need to outlive decoding; the rest are
+        // handed over so they can be parsed in place.
+        let context = |e: DecodeError| e.with_request(method.as_str(), path, Some(status.as_u16()));
+        let data = match cache_entry {
+            Some((cache, key)) => {
+                let data = DecodeError::decode(&response).map_err(context)?;
+                cache.insert(key, response);
+                data
+            }
+            None => {
+                let data = DecodeError::decode_owned(response).map_err(context)?;
+                if let Some(cache) = self.cache.as_ref().filter(|_| method != Method::GET) {
+                    cache.invalidate(path);
+                }
+                data
+            }
+        };
+        Ok(data)
+    }
+
+    /// Build the cache key for a GET request from its path and query parameters.
+    fn cache_key(path: &str, params: Option<&impl Serialize>) -> PayjpResult<String> {
+        let query = match params {
+            Some(params) => serde_urlencoded::to_string(params)
+                .map_err(|e| PayjpError::InvalidRequest(format!("Failed to encode query: {}", e)))?,
+            None => String::new(),
+        };
+        Ok(format!("{}?{}", path, query))
+    }
+
+    /// Calculate retry delay using the configured backoff strategy.
+    fn calculate_retry_delay(&self, retry_count: u32) -> Duration {
+        self.backoff.delay(retry_count)
+    }
+
+    /// Send a request to the base URL, falling back to the failover URLs on connect errors.
+    async fn send_with_failover(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+        request_id: &str,
+    ) -> PayjpResult<(StatusCode, Vec<u8>)> {
+        let mut result = self
+            .send_request(&self.base_url, method.clone(), path, body, request_id)
+            .await;
+
+        for base_url in &self.failover_urls {
+            match &result {
+                Err(PayjpError::Network(e)) if e.is_connect() => {
+                    result = self
+                        .send_request(base_url, method.clone(), path, body, request_id)
+                        .await;
+                }
+                _ => break,
+            }
         }
+
+        result
     }
 
-    /// Calculate retry delay with exponential backoff and jitter.
+    /// Send an HTTP request to the PAY.JP API, returning the status and raw
+    /// body of a successful response.
     ///
-    /// Uses saturating arithmetic to safely handle edge cases where retry_count
-    /// is very high (e.g., >= 64) which would otherwise cause overflow or panic.
-    fn calculate_retry_delay(&self, retry_count: u32) -> Duration {
-        // Use saturating_pow to handle retry_count >= 64 safely
-        // Use saturating_mul to prevent overflow in the multiplication
-        let base = (self.retry_initial_delay.as_millis() as u64)
-            .saturating_mul(2u64.saturating_pow(retry_count));
-        let max = self.retry_max_delay.as_millis() as u64;
-        let capped = base.min(max);
+    /// If the API key is rejected, the key provider is asked for a fresh key and
+    /// the request is sent once more if the key changed. Public keys returned by
+    /// the provider fail with [`PayjpError::PublicKeyAsSecret`] without being sent.
+    async fn send_request(
+        &self,
+        base_url: &str,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+        request_id: &str,
+    ) -> PayjpResult<(StatusCode, Vec<u8>)> {
+        let api_key = self.key.0.api_key().await?;
+        let api_key = check_secret_key(api_key.expose_secret())?;
+        let result = self
+            .send_with_key(api_key, base_url, method.clone(), path, body, request_id)
+            .await;
+        if !matches!(result, Err(PayjpError::Auth(_))) {
+            return result;
+        }
 
-        // Equal jitter: random between capped/2 and capped
-        let jittered = capped / 2 + rand::rng().random_range(0..=capped / 2);
-        Duration::from_millis(jittered)
+        let refreshed = self.key.0.refresh_key().await?;
+        let refreshed = check_secret_key(refreshed.expose_secret())?;
+        if refreshed == api_key {
+            return result;
+        }
+        self.send_with_key(refreshed, base_url, method, path, body, request_id)
+            .await
     }
 
-    /// Send an HTTP request to the PAY.JP API.
-    async fn send_request<T: DeserializeOwned>(
+    /// Send an HTTP request authenticated with `api_key`.
+    async fn send_with_key(
         &self,
+        api_key: &str,
+        base_url: &str,
         method: Method,
         path: &str,
         body: Option<&impl Serialize>,
-    ) -> PayjpResult<T> {
-        let url = format!("{}{}", self.base_url, path);
+        request_id: &str,
+    ) -> PayjpResult<(StatusCode, Vec<u8>)> {
+        let url = format!("{}{}", base_url, path);
+
+        // Create basic auth header
+        livemode::check_base_url(self.livemode_check, api_key, base_url)?;
+        let auth = format!("{}:", api_key);
+        let encoded = general_purpose::STANDARD.encode(auth.as_bytes());
+        let auth_header_str = format!("Basic {}", encoded);
+
+        // Convert header values explicitly
+        let auth_header = HeaderValue::from_str(&auth_header_str).map_err(|e| {
+            PayjpError::InvalidRequest(format!("Invalid authorization header: {}", e))
+        })?;
+
+        // Encode parameters once: as the query string for GET, as the form body otherwise
+        let encoded_params = body
+            .map(|params| {
+                serde_urlencoded::to_string(params).map_err(|e| {
+                    PayjpError::InvalidRequest(format!("Failed to encode form data: {}", e))
+                })
+            })
+            .transpose()?;
+
+        let mut url = match &encoded_params {
+            Some(query) if method == Method::GET && !query.is_empty() => {
+                format!("{}?{}", url, query)
+            }
+            _ => url,
+        };
+        let extras = extras::current();
+        if let Some(extras) = &extras {
+            extras.append_query(&mut url)?;
+        }
+
+        let mut request = self
+            .http_client
+            .request(method.clone(), &url)
+            .header("Authorization", auth_header)
+            .header("User-Agent", self.user_agent.clone())
+            .header(REQUEST_ID_HEADER, request_id);
+        if let Some(correlation_id) = request_id::correlation_id() {
+            request = request.header(CORRELATION_ID_HEADER, correlation_id);
+        }
+        #[cfg(feature = "otel")]
+        for (name, value) in crate::otel::current_headers() {
+            request = request.header(name, value);
+        }
+        if let Some(extras) = &extras {
+            request = extras.apply_headers(request)?;
+        }
+        let idempotency_key = request_id::idempotency_key().filter(|_| method == Method::POST);
+        if let Some(key) = &idempotency_key {
+            self.idempotency_log.record(key, request_id);
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key.as_str());
+        }
+
+        if method != Method::GET {
+            if let Some(encoded) = &encoded_params {
+                let content_type = HeaderValue::from_static("application/x-www-form-urlencoded");
+                request = request.header("Content-Type", content_type).body(encoded.clone());
+            }
+        }
+
+        let started = Instant::now();
+        let (status, bytes) = match self.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_metrics(&method, path, None, started);
+                self.record_exchange(&method, path, request_id, encoded_params.as_deref(), None, None);
+                return Err(e.into());
+            }
+        };
+        self.record_metrics(&method, path, Some(status), started);
+        let bytes = bytes?;
+        self.record_exchange(
+            &method,
+            path,
+            request_id,
+            encoded_params.as_deref(),
+            Some(status),
+            Some(&bytes),
+        );
+
+        // Handle different status codes
+        match status {
+            StatusCode::OK | StatusCode::CREATED => {
+                livemode::check_response(self.livemode_check, api_key, &bytes)?;
+                Ok((status, bytes))
+            }
+            StatusCode::TOO_MANY_REQUESTS => Err(PayjpError::RateLimit),
+            StatusCode::UNAUTHORIZED => {
+                Err(PayjpError::Auth("Invalid API key".to_string()))
+            }
+            _ => {
+                // Try to parse error response
+                let mut error = match serde_json::from_slice::<ErrorResponse>(&bytes) {
+                    Ok(error_response) => error_response.error,
+                    Err(_) => crate::error::ApiError {
+                        status: status.as_u16(),
+                        error_type: "unknown_error".to_string(),
+                        message: format!("HTTP error: {}", status),
+                        code: None,
+                        param: None,
+                        request_id: None,
+                    },
+                };
+                error.request_id = Some(request_id.to_string());
+                match idempotency_key.filter(|_| is_idempotency_conflict(&error)) {
+                    Some(key) => Err(PayjpError::IdempotencyConflict {
+                        original_request_id: self.idempotency_log.original(&key),
+                        key,
+                        error: Box::new(error),
+                    }),
+                    None => Err(error.into()),
+                }
+            }
+        }
+    }
+
+    /// Send the request and read the response status and body.
+    async fn execute(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<(StatusCode, PayjpResult<Vec<u8>>)> {
+        #[cfg(feature = "chaos")]
+        if let Some(chaos) = &self.chaos {
+            return chaos.execute(request, self.max_response_size).await;
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        Ok((status, read_body(response, self.max_response_size).await))
+    }
+
+    /// Report request timing to the metrics sink, if one is configured.
+    fn record_metrics(
+        &self,
+        method: &Method,
+        path: &str,
+        status: Option<StatusCode>,
+        started: Instant,
+    ) {
+        record_metrics(self.metrics.as_deref(), method, path, status, started);
+    }
+
+    /// Report a rate limit to the metrics sink, if one is configured.
+    pub(crate) fn record_rate_limit(
+        &self,
+        method: &Method,
+        path: &str,
+        source: RateLimitSource,
+        wait: Duration,
+    ) {
+        record_rate_limit(self.metrics.as_deref(), method, path, source, wait);
+    }
+
+    /// Record a sanitized copy of the latest exchange when capture is enabled,
+    /// and log body digests when bodies are hashed.
+    fn record_exchange(
+        &self,
+        method: &Method,
+        path: &str,
+        request_id: &str,
+        params: Option<&str>,
+        status: Option<StatusCode>,
+        response_body: Option<&[u8]>,
+    ) {
+        let request_body = params.map(redact::redact_form);
+        let (request_body, request_body_digest, response_body, response_body_digest) =
+            match self.body_logging {
+                BodyLogging::Redacted => (
+                    request_body,
+                    None,
+                    response_body.map(redact::redact_body),
+                    None,
+                ),
+                BodyLogging::Hashed => {
+                    let request = request_body.map(|body| BodyDigest::of(body.as_bytes()));
+                    let response = response_body.map(BodyDigest::of);
+                    let (request_digest, response_digest) = (request.as_ref(), response.as_ref());
+                    log_audit(method, path, request_id, status, request_digest, response_digest);
+                    (None, request, None, response)
+                }
+            };
+        if let Some(last_exchange) = &self.last_exchange {
+            let exchange = Exchange {
+                method: method.to_string(),
+                path: path.to_string(),
+                request_id: request_id.to_string(),
+                request_body,
+                request_body_digest,
+                status: status.map(|status| status.as_u16()),
+                response_body,
+                response_body_digest,
+            };
+            *last_exchange.lock().unwrap_or_else(|e| e.into_inner()) = Some(exchange);
+        }
+    }
+}
+
+/// Read a response body, failing once it grows past `limit` bytes.
+pub(crate) async fn read_body(
+    mut response: reqwest::Response,
+    limit: Option<usize>,
+) -> PayjpResult<Vec<u8>> {
+    let Some(limit) = limit else {
+        return Ok(response.bytes().await?.into());
+    };
+    if response.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(PayjpError::ResponseTooLarge { limit });
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(PayjpError::ResponseTooLarge { limit });
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Report request timing to `sink`, if one is configured.
+fn record_metrics(
+    sink: Option<&dyn MetricsSink>,
+    method: &Method,
+    path: &str,
+    status: Option<StatusCode>,
+    started: Instant,
+) {
+    if let Some(sink) = sink {
+        sink.record(&RequestMetrics {
+            method: method.to_string(),
+            path_template: metrics::path_template(path),
+            status: status.map(|status| status.as_u16()),
+            duration: started.elapsed(),
+        });
+    }
+}
+
+/// Report a rate limit to `sink`, if one is configured.
+fn record_rate_limit(
+    sink: Option<&dyn MetricsSink>,
+    method: &Method,
+    path: &str,
+    source: RateLimitSource,
+    wait: Duration,
+) {
+    if let Some(sink) = sink {
+        sink.rate_limited(&RateLimitEvent {
+            method: method.to_string(),
+            path_template: metrics::path_template(path),
+            source,
+            wait,
+        });
+    }
+}
+
+/// Whether a rate-limited request may be sent again.
+///
+/// GET and DELETE requests are always retried. POST requests are only retried
+/// with an idempotency key, so a retry cannot create a duplicate; other methods
+/// are never retried.
+fn is_retry_safe(method: &Method) -> bool {
+    if method == Method::GET || method == Method::DELETE {
+        true
+    } else if method == Method::POST {
+        request_id::idempotency_key().is_some()
+    } else {
+        false
+    }
+}
+
+/// Trim a key returned by a [`KeyProvider`] and reject public keys, which the
+/// API would only answer with a generic authentication error.
+fn check_secret_key(api_key: &str) -> PayjpResult<&str> {
+    let api_key = api_key.trim();
+    if KeyKind::of(api_key).is_public() {
+        return Err(PayjpError::PublicKeyAsSecret);
+    }
+    Ok(api_key)
+}
 
-        // Create basic auth header
-        let auth = format!("{}:", self.api_key);
-        let encoded = general_purpose::STANDARD.encode(auth.as_bytes());
-        let auth_header_str = format!("Basic {}", encoded);
+/// Error code of the API's response to an idempotency key reused with
+/// different parameters.
+const IDEMPOTENCY_CONFLICT_CODE: &str = "idempotency_key_conflict";
 
-        // Convert header values explicitly
-        let auth_header = HeaderValue::from_str(&auth_header_str).map_err(|e| {
-            PayjpError::InvalidRequest(format!("Invalid authorization header: {}", e))
-        })?;
-        let user_agent = HeaderValue::from_static(USER_AGENT);
+/// Whether an error response rejects an idempotency key reused with different
+/// parameters. Other conflicts are left as API errors.
+fn is_idempotency_conflict(error: &crate::error::ApiError) -> bool {
+    error.code.as_deref() == Some(IDEMPOTENCY_CONFLICT_CODE)
+}
 
-        let mut request = self
-            .http_client
-            .request(method.clone(), &url)
-            .header("Authorization", auth_header)
-            .header("User-Agent", user_agent);
-
-        // Add body based on method
-        request = if method == Method::GET {
-            if let Some(params) = body {
-                request.query(params)
-            } else {
-                request
-            }
-        } else if let Some(params) = body {
-            // Manually encode form data with proper card[field] format
-            let encoded = serde_urlencoded::to_string(params)
-                .map_err(|e| PayjpError::InvalidRequest(format!("Failed to encode form data: {}", e)))?;
-            let content_type = HeaderValue::from_static("application/x-www-form-urlencoded");
-            request.header("Content-Type", content_type).body(encoded)
-        } else {
-            request
-        };
+/// Emit a request audit event with body digests at target `payjp::audit`
+/// (requires the `tracing` feature).
+#[cfg(feature = "tracing")]
+fn log_audit(
+    method: &Method,
+    path: &str,
+    request_id: &str,
+    status: Option<StatusCode>,
+    request: Option<&BodyDigest>,
+    response: Option<&BodyDigest>,
+) {
+    tracing::info!(
+        target: "payjp::audit",
+        method = %method,
+        endpoint = %metrics::path_template(path),
+        request_id,
+        status = status.map(|status| status.as_u16()),
+        request_sha256 = request.map(|digest| digest.sha256.as_str()),
+        request_size = request.map(|digest| digest.size),
+        response_sha256 = response.map(|digest| digest.sha256.as_str()),
+        response_size = response.map(|digest| digest.size),
+        "PAY.JP request"
+    );
+}
 
-        let response = request.send().await?;
-        let status = response.status();
+#[cfg(not(feature = "tracing"))]
+fn log_audit(
+    _method: &Method,
+    _path: &str,
+    _request_id: &str,
+    _status: Option<StatusCode>,
+    _request: Option<&BodyDigest>,
+    _response: Option<&BodyDigest>,
+) {
+}
 
-        // Handle different status codes
-        match status {
-            StatusCode::OK | StatusCode::CREATED => {
-                let data = response.json::<T>().await?;
-                Ok(data)
-            }
-            StatusCode::TOO_MANY_REQUESTS => Err(PayjpError::RateLimit),
-            StatusCode::UNAUTHORIZED => {
-                Err(PayjpError::Auth("Invalid API key".to_string()))
-            }
-            _ => {
-                // Try to parse error response
-                if let Ok(error_response) = response.json::<ErrorResponse>().await {
-                    Err(PayjpError::Api(error_response.error))
-                } else {
-                    Err(PayjpError::Api(crate::error::ApiError {
-                        status: status.as_u16(),
-                        error_type: "unknown_error".to_string(),
-                        message: format!("HTTP error: {}", status),
-                        code: None,
-                        param: None,
-                    }))
-                }
-            }
-        }
-    }
+/// Emit a structured retry event with target `payjp::retry` (requires the `tracing` feature).
+#[cfg(feature = "tracing")]
+fn log_retry(
+    method: &Method,
+    path: &str,
+    request_id: &str,
+    attempt: u32,
+    delay: Duration,
+    error: &PayjpError,
+) {
+    tracing::warn!(
+        target: "payjp::retry",
+        method = %method,
+        endpoint = %metrics::path_template(path),
+        request_id,
+        attempt,
+        delay_ms = delay.as_millis() as u64,
+        error_class = %error.details().error_type,
+        "retrying PAY.JP request"
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+fn log_retry(
+    _method: &Method,
+    _path: &str,
+    _request_id: &str,
+    _attempt: u32,
+    _delay: Duration,
+    _error: &PayjpError,
+) {
 }
 
 /// The PAY.JP client for public key operations (token creation only).
@@ -321,10 +1384,13 @@ pub struct PayjpPublicClient {
     public_key: String,
     password: String,
     http_client: reqwest::Client,
+    user_agent: HeaderValue,
     base_url: String,
+    failover_urls: Vec<String>,
     max_retry: u32,
-    retry_initial_delay: Duration,
-    retry_max_delay: Duration,
+    backoff: Arc<dyn BackoffStrategy>,
+    max_response_size: Option<usize>,
+    metrics: Option<Arc<dyn MetricsSink>>,
 }
 
 impl PayjpPublicClient {
@@ -332,6 +1398,7 @@ impl PayjpPublicClient {
     ///
     /// Public keys start with `pk_test_` (for test mode) or `pk_live_` (for live mode).
     /// Leading and trailing whitespace in both the public key and password will be automatically trimmed.
+    /// Secret keys (`sk_`) are rejected with [`PayjpError::SecretKeyAsPublic`].
     ///
     /// # Example
     ///
@@ -347,6 +1414,12 @@ impl PayjpPublicClient {
 
     /// Create a new PAY.JP public client with custom options.
     ///
+    /// The connection options (base and failover URLs, timeout, retries and
+    /// backoff, DNS overrides, proxy, default headers, HTTP client, compression,
+    /// response size limit, app info and metrics) behave as for [`PayjpClient`].
+    /// Options for secret key operations, such as the response cache or token
+    /// verification, are ignored.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -365,18 +1438,25 @@ impl PayjpPublicClient {
         password: impl Into<String>,
         options: ClientOptions,
     ) -> PayjpResult<Self> {
-        let http_client = reqwest::Client::builder()
-            .timeout(options.timeout)
-            .build()?;
+        let public_key = public_key.into().trim().to_string();
+        if KeyKind::of(&public_key).is_secret() {
+            return Err(PayjpError::SecretKeyAsPublic);
+        }
+        let http_client = options.build_http_client()?;
+        let backoff = options.build_backoff();
+        let user_agent = options.build_user_agent()?;
 
         Ok(Self {
-            public_key: public_key.into().trim().to_string(),
+            public_key,
             password: password.into().trim().to_string(),
             http_client,
+            user_agent,
             base_url: options.base_url,
+            failover_urls: options.failover_urls,
             max_retry: options.max_retry,
-            retry_initial_delay: options.retry_initial_delay,
-            retry_max_delay: options.retry_max_delay,
+            backoff,
+            max_response_size: options.max_response_size,
+            metrics: options.metrics,
         })
     }
 
@@ -408,13 +1488,25 @@ impl PayjpPublicClient {
         path: &str,
         body: Option<&impl Serialize>,
     ) -> PayjpResult<T> {
+        let request_id = request_id::generate_request_id();
         let mut retry_count = 0;
 
         loop {
-            match self.send_request(method.clone(), path, body).await {
+            match self.send_with_failover(method.clone(), path, body, &request_id).await {
                 Ok(response) => return Ok(response),
-                Err(PayjpError::RateLimit) if retry_count < self.max_retry => {
-                    let delay = self.calculate_retry_delay(retry_count);
+                Err(e @ PayjpError::RateLimit) => {
+                    let retry = retry_count < self.max_retry && is_retry_safe(&method);
+                    let delay = if retry {
+                        self.calculate_retry_delay(retry_count)
+                    } else {
+                        Duration::ZERO
+                    };
+                    let source = RateLimitSource::Server;
+                    record_rate_limit(self.metrics.as_deref(), &method, path, source, delay);
+                    if !retry {
+                        return Err(e);
+                    }
+                    log_retry(&method, path, &request_id, retry_count + 1, delay, &e);
                     tokio::time::sleep(delay).await;
                     retry_count += 1;
                 }
@@ -423,26 +1515,50 @@ impl PayjpPublicClient {
         }
     }
 
-    /// Calculate retry delay with exponential backoff and jitter.
+    /// Calculate retry delay using the configured backoff strategy.
     fn calculate_retry_delay(&self, retry_count: u32) -> Duration {
-        let base = (self.retry_initial_delay.as_millis() as u64)
-            .saturating_mul(2u64.saturating_pow(retry_count));
-        let max = self.retry_max_delay.as_millis() as u64;
-        let capped = base.min(max);
+        self.backoff.delay(retry_count)
+    }
 
-        // Equal jitter: random between capped/2 and capped
-        let jittered = capped / 2 + rand::rng().random_range(0..=capped / 2);
-        Duration::from_millis(jittered)
+    /// Send a request to the base URL, falling back to the failover URLs on connect errors.
+    async fn send_with_failover<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+        request_id: &str,
+    ) -> PayjpResult<T> {
+        let mut result = self
+            .send_request(&self.base_url, method.clone(), path, body, request_id)
+            .await;
+
+        for base_url in &self.failover_urls {
+            match &result {
+                Err(PayjpError::Network(e)) if e.is_connect() => {
+                    result = self
+                        .send_request(base_url, method.clone(), path, body, request_id)
+                        .await;
+                }
+                _ => break,
+            }
+        }
+        result
     }
 
     /// Send an HTTP request to the PAY.JP API.
     async fn send_request<T: DeserializeOwned>(
         &self,
+        base_url: &str,
         method: Method,
         path: &str,
         body: Option<&impl Serialize>,
+        request_id: &str,
     ) -> PayjpResult<T> {
-        let url = format!("{}{}", self.base_url, path);
+        let mut url = format!("{}{}", base_url, path);
+        let extras = extras::current();
+        if let Some(extras) = &extras {
+            extras.append_query(&mut url)?;
+        }
 
         // Create basic auth header with public key and password
         let auth = format!("{}:{}", self.public_key, self.password);
@@ -453,13 +1569,26 @@ impl PayjpPublicClient {
         let auth_header = HeaderValue::from_str(&auth_header_str).map_err(|e| {
             PayjpError::InvalidRequest(format!("Invalid authorization header: {}", e))
         })?;
-        let user_agent = HeaderValue::from_static(USER_AGENT);
 
         let mut request = self
             .http_client
             .request(method.clone(), &url)
             .header("Authorization", auth_header)
-            .header("User-Agent", user_agent);
+            .header("User-Agent", self.user_agent.clone())
+            .header(REQUEST_ID_HEADER, request_id);
+        if let Some(correlation_id) = request_id::correlation_id() {
+            request = request.header(CORRELATION_ID_HEADER, correlation_id);
+        }
+        #[cfg(feature = "otel")]
+        for (name, value) in crate::otel::current_headers() {
+            request = request.header(name, value);
+        }
+        if let Some(extras) = &extras {
+            request = extras.apply_headers(request)?;
+        }
+        if let Some(key) = request_id::idempotency_key().filter(|_| method == Method::POST) {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
 
         // Add body (public client only supports POST for token creation)
         request = if let Some(params) = body {
@@ -472,13 +1601,23 @@ impl PayjpPublicClient {
             request
         };
 
-        let response = request.send().await?;
+        let started = Instant::now();
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                record_metrics(self.metrics.as_deref(), &method, path, None, started);
+                return Err(e.into());
+            }
+        };
         let status = response.status();
+        record_metrics(self.metrics.as_deref(), &method, path, Some(status), started);
+        let bytes = read_body(response, self.max_response_size).await?;
 
         // Handle different status codes
         match status {
             StatusCode::OK | StatusCode::CREATED => {
-                let data = response.json::<T>().await?;
+                let data = DecodeError::decode(&bytes)
+                    .map_err(|e| e.with_request(method.as_str(), path, Some(status.as_u16())))?;
                 Ok(data)
             }
             StatusCode::TOO_MANY_REQUESTS => Err(PayjpError::RateLimit),
@@ -487,17 +1626,19 @@ impl PayjpPublicClient {
             }
             _ => {
                 // Try to parse error response
-                if let Ok(error_response) = response.json::<ErrorResponse>().await {
-                    Err(PayjpError::Api(error_response.error))
-                } else {
-                    Err(PayjpError::Api(crate::error::ApiError {
+                let mut error = match serde_json::from_slice::<ErrorResponse>(&bytes) {
+                    Ok(error_response) => error_response.error,
+                    Err(_) => crate::error::ApiError {
                         status: status.as_u16(),
                         error_type: "unknown_error".to_string(),
                         message: format!("HTTP error: {}", status),
                         code: None,
                         param: None,
-                    }))
-                }
+                        request_id: None,
+                    },
+                };
+                error.request_id = Some(request_id.to_string());
+                Err(error.into())
             }
         }
     }
@@ -568,6 +1709,27 @@ mod tests {
         assert!(delay.as_millis() as u64 <= 30_000);
     }
 
+    #[test]
+    fn test_custom_backoff_strategy() {
+        #[derive(Debug)]
+        struct Fixed;
+
+        impl BackoffStrategy for Fixed {
+            fn delay(&self, retry_count: u32) -> Duration {
+                Duration::from_millis(u64::from(retry_count) * 10)
+            }
+        }
+
+        let options = ClientOptions::new()
+            .retry_initial_delay(Duration::from_secs(1))
+            .backoff(Fixed);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client with custom backoff");
+
+        assert_eq!(client.calculate_retry_delay(0), Duration::ZERO);
+        assert_eq!(client.calculate_retry_delay(3), Duration::from_millis(30));
+    }
+
     #[test]
     fn test_user_agent_format() {
         // Verify USER_AGENT is correctly formatted with package version
@@ -579,6 +1741,25 @@ mod tests {
         assert_eq!(USER_AGENT, format!("payjp-rust/{}", version));
     }
 
+    #[test]
+    fn test_app_info_user_agent() {
+        let options =
+            ClientOptions::new().app_info("MyPlugin", Some("1.2.0"), Some("https://example.com"));
+        assert_eq!(
+            options.build_user_agent().unwrap(),
+            format!("{} MyPlugin/1.2.0 (https://example.com)", USER_AGENT).as_str()
+        );
+
+        let options = ClientOptions::new().app_info("MyPlugin", None, None);
+        assert_eq!(
+            options.build_user_agent().unwrap(),
+            format!("{} MyPlugin", USER_AGENT).as_str()
+        );
+
+        let options = ClientOptions::new().app_info("Bad\nName", None, None);
+        assert!(PayjpClient::with_options("sk_test_xxxxx", options).is_err());
+    }
+
     #[test]
     fn test_api_key_whitespace_trimming() {
         // Test with trailing newline (common case from environment variables)
@@ -611,6 +1792,1012 @@ mod tests {
         assert_eq!(client.api_key(), "sk_test_options");
     }
 
+    #[test]
+    fn test_clients_reject_the_other_key_type() {
+        assert!(matches!(
+            PayjpClient::new("pk_live_xxxxx"),
+            Err(PayjpError::PublicKeyAsSecret)
+        ));
+        assert!(matches!(
+            PayjpPublicClient::new(" sk_test_xxxxx", "password"),
+            Err(PayjpError::SecretKeyAsPublic)
+        ));
+        let public = PayjpPublicClient::new(" pk_test_xxxxx\n", "password")
+            .expect("Failed to create public client");
+        assert_eq!(public.public_key(), "pk_test_xxxxx");
+        assert_eq!(StaticKey::new("sk_live_xxxxx\n").kind(), KeyKind::SecretLive);
+        assert_eq!(KeyKind::of("rk_test_xxxxx"), KeyKind::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_public_client_honors_connection_options() {
+        use crate::resources::token::{CardDetails, CreateTokenParams};
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tokens"))
+            .and(header("X-Gateway-Token", "gw"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "tok_1",
+                "object": "token",
+                "livemode": false,
+                "created": 1700000000,
+                "used": false,
+                "card": {
+                    "id": "car_1",
+                    "object": "card",
+                    "livemode": false,
+                    "created": 1700000000,
+                    "brand": "Visa",
+                    "last4": "4242",
+                    "exp_month": 12,
+                    "exp_year": 2030
+                }
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url("http://127.0.0.1:1")
+            .failover_url(&server.uri())
+            .default_header("X-Gateway-Token", "gw");
+        let client = PayjpPublicClient::with_options("pk_test_xxxxx", "password", options)
+            .expect("Failed to create public client");
+        let card = CardDetails::new("4242424242424242", 12, 2030, "123");
+        let token = client.tokens().create(CreateTokenParams::from_card(card.clone())).await;
+        assert_eq!(token.expect("Token should be created via failover").id, "tok_1");
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .default_header("X-Gateway-Token", "gw")
+            .max_response_size(16);
+        let client = PayjpPublicClient::with_options("pk_test_xxxxx", "password", options)
+            .expect("Failed to create public client");
+        let result = client.tokens().create(CreateTokenParams::from_card(card)).await;
+        assert!(matches!(result, Err(PayjpError::ResponseTooLarge { limit: 16 })));
+
+        let invalid = ClientOptions::new().proxy("not a url");
+        assert!(matches!(
+            PayjpPublicClient::with_options("pk_test_xxxxx", "password", invalid),
+            Err(PayjpError::InvalidRequest(_))
+        ));
+        let invalid = ClientOptions::new().default_header("Bad Header", "x");
+        assert!(matches!(
+            PayjpClient::with_options("sk_test_xxxxx", invalid),
+            Err(PayjpError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cached_get_requests() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .cache(CacheOptions::new());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        for _ in 0..3 {
+            let account = client.account().retrieve().await.expect("Failed to retrieve");
+            assert_eq!(account.id, "acct_xxxxx");
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[tokio::test]
+    async fn test_writes_invalidate_cached_responses() {
+        use crate::UpdatePlanParams;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let plan = serde_json::json!({
+            "id": "pln_1",
+            "object": "plan",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "interval": "month"
+        });
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plans/pln_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(plan.clone()))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/plans/pln_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(plan))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .cache(CacheOptions::new());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        client.plans().retrieve("pln_1").await.expect("Failed to retrieve");
+        client.plans().retrieve("pln_1").await.expect("Failed to retrieve");
+        client
+            .plans()
+            .update("pln_1", UpdatePlanParams::new().name("Gold"))
+            .await
+            .expect("Failed to update");
+        client.plans().retrieve("pln_1").await.expect("Failed to retrieve");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_override() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url("http://api.payjp.invalid/v1")
+            .resolve("api.payjp.invalid", *server.address());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let account = client.account().retrieve().await.expect("Failed to retrieve");
+        assert_eq!(account.id, "acct_xxxxx");
+    }
+
+    #[tokio::test]
+    async fn test_last_exchange_is_redacted() {
+        use crate::resources::token::{CardDetails, CreateTokenParams};
+        use wiremock::matchers::{header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tokens"))
+            .and(header_exists(REQUEST_ID_HEADER))
+            .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 402,
+                    "type": "card_error",
+                    "code": "card_declined",
+                    "message": "Card declined"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .capture_last_exchange(true);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        assert!(client.last_exchange().is_none());
+
+        let card = CardDetails::new("4242424242424242", 12, 2030, "123");
+        let result = client.tokens().create(CreateTokenParams::from_card(card)).await;
+        let request_id = match result {
+            Err(PayjpError::Api(err)) => err.request_id.expect("Error should carry the request ID"),
+            other => panic!("Expected API error, got {:?}", other),
+        };
+
+        let exchange = client.last_exchange().expect("Exchange should be captured");
+        assert_eq!(exchange.request_id, request_id);
+        assert_eq!(exchange.method, "POST");
+        assert_eq!(exchange.path, "/tokens");
+        assert_eq!(exchange.status, Some(402));
+        let request_body = exchange.request_body.expect("Request body should be captured");
+        assert!(request_body.contains("************4242"));
+        assert!(!request_body.contains("4242424242424242"));
+        assert!(!request_body.contains("=123"));
+        assert!(exchange.response_body.unwrap().contains("card_declined"));
+    }
+
+    #[tokio::test]
+    async fn test_last_exchange_redacts_response_body() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tokens"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 400,
+                    "type": "client_error",
+                    "code": "invalid_number",
+                    "message": "Invalid card number 4242424242424242",
+                    "card": { "number": "4242424242424242", "cvc": "123" }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(
+                ResponseTemplate::new(502).set_body_string("bad gateway 4242424242424242"),
+            )
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .max_retry(0)
+            .capture_last_exchange(true);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let params = crate::resources::CreateTokenParams::from_card(
+            crate::resources::token::CardDetails::new("4242424242424242", 12, 2030, "123"),
+        );
+        assert!(client.tokens().create(params).await.is_err());
+        let body = client.last_exchange().unwrap().response_body.unwrap();
+        assert!(body.contains("invalid_number"));
+        assert!(body.contains("************4242"));
+        assert!(!body.contains("4242424242424242"));
+        assert!(!body.contains("\"123\""));
+
+        assert!(client.account().retrieve().await.is_err());
+        let body = client.last_exchange().unwrap().response_body.unwrap();
+        assert_eq!(body, "bad gateway ************4242");
+    }
+
+    #[tokio::test]
+    async fn test_hashed_body_logging_keeps_only_digests() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = r#"{"id":"cus_1","object":"customer","livemode":false,"created":1704067200}"#;
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .capture_last_exchange(true)
+            .body_logging(BodyLogging::Hashed);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let params = crate::resources::CreateCustomerParams::new().email("a@example.com");
+        client.customers().create(params).await.unwrap();
+
+        let exchange = client.last_exchange().expect("Exchange should be captured");
+        assert!(exchange.request_body.is_none());
+        assert!(exchange.response_body.is_none());
+        let request = exchange.request_body_digest.expect("Request digest should be kept");
+        assert_eq!(request.size, "email=a%40example.com".len());
+        assert_eq!(request, BodyDigest::of(b"email=a%40example.com"));
+        let response = exchange.response_body_digest.expect("Response digest should be kept");
+        assert_eq!(response, BodyDigest::of(body.as_bytes()));
+        assert_eq!(
+            BodyDigest::of(b"").sha256,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_error_masks_echoed_card_data() {
+        use crate::resources::token::{CardDetails, CreateTokenParams};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tokens"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 400,
+                    "type": "client_error",
+                    "code": "invalid_number",
+                    "message": "Invalid card number: 4242 4242 4242 4241",
+                    "param": "card[number]=4242424242424241 card[cvc]=123"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let card = CardDetails::new("4242424242424241", 12, 2030, "123");
+        let params = CreateTokenParams::from_card(card);
+        let err = match client.tokens().create(params).await {
+            Err(PayjpError::Api(err)) => err,
+            other => panic!("Expected API error, got {:?}", other),
+        };
+        assert_eq!(err.message, "Invalid card number: ************4241");
+        assert_eq!(err.param.as_deref(), Some("card[number]=************4241 card[cvc]=***"));
+        let display = err.to_string();
+        assert!(!display.contains("4242424242424241") && !display.contains("4242 4242"));
+        assert!(!display.contains("=123"));
+    }
+
+    #[tokio::test]
+    async fn test_livemode_check_rejects_mismatched_response() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": true,
+                "created": 1433127983
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .livemode_check(LivemodeCheck::Error);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options.clone())
+            .expect("Failed to create client");
+        let result = client.account().retrieve().await;
+        assert!(matches!(result, Err(PayjpError::LivemodeMismatch(_))), "{:?}", result);
+
+        // A live key is not sent to a non-default base URL at all
+        let client = PayjpClient::with_options("sk_live_xxxxx", options)
+            .expect("Failed to create client");
+        let result = client.account().retrieve().await;
+        assert!(matches!(result, Err(PayjpError::LivemodeMismatch(_))), "{:?}", result);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_reported_per_request() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Debug, Default)]
+        struct Recorder(Mutex<Vec<RequestMetrics>>);
+
+        impl MetricsSink for Arc<Recorder> {
+            fn record(&self, metrics: &RequestMetrics) {
+                self.0.lock().unwrap().push(metrics.clone());
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges/ch_missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 404,
+                    "type": "client_error",
+                    "message": "No such charge"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(Recorder::default());
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .metrics(recorder.clone());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        assert!(client.charges().retrieve("ch_missing").await.is_err());
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].method, "GET");
+        assert_eq!(recorded[0].path_template, "/charges/{id}");
+        assert_eq!(recorded[0].status, Some(404));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_are_reported_to_metrics() {
+        use crate::resources::token::{CardDetails, CreateTokenParams};
+        use crate::CreateCustomerParams;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Debug, Default)]
+        struct Recorder(Mutex<Vec<RateLimitEvent>>);
+
+        #[derive(Debug)]
+        struct Fixed;
+
+        impl BackoffStrategy for Fixed {
+            fn delay(&self, _retry_count: u32) -> Duration {
+                Duration::from_millis(5)
+            }
+        }
+
+        impl MetricsSink for Arc<Recorder> {
+            fn record(&self, _metrics: &RequestMetrics) {}
+
+            fn rate_limited(&self, event: &RateLimitEvent) {
+                self.0.lock().unwrap().push(event.clone());
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_1",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(Recorder::default());
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .backoff(Fixed)
+            .metrics(recorder.clone());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        assert!(client.account().retrieve().await.is_ok());
+        assert!(client.customers().create(CreateCustomerParams::new()).await.is_err());
+
+        Mock::given(method("POST"))
+            .and(path("/tokens"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .backoff(Fixed)
+            .metrics(recorder.clone());
+        let public = PayjpPublicClient::with_options("pk_test_xxxxx", "password", options)
+            .expect("Failed to create public client");
+        let card = CardDetails::new("4242424242424242", 12, 2030, "123");
+        assert!(public.tokens().create(CreateTokenParams::from_card(card)).await.is_err());
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(
+            *recorded,
+            [
+                RateLimitEvent {
+                    method: "GET".to_string(),
+                    path_template: "/account".to_string(),
+                    source: RateLimitSource::Server,
+                    wait: Duration::from_millis(5),
+                },
+                RateLimitEvent {
+                    method: "POST".to_string(),
+                    path_template: "/customers".to_string(),
+                    source: RateLimitSource::Server,
+                    wait: Duration::ZERO,
+                },
+                RateLimitEvent {
+                    method: "POST".to_string(),
+                    path_template: "/tokens".to_string(),
+                    source: RateLimitSource::Server,
+                    wait: Duration::ZERO,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_tokens_rejects_used_and_old_tokens() {
+        use crate::CreateChargeParams;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let token = |id: &str, created: i64, used: bool| {
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": id,
+                "object": "token",
+                "livemode": false,
+                "created": created,
+                "used": used,
+                "card": {
+                    "id": "car_1",
+                    "object": "card",
+                    "livemode": false,
+                    "created": created,
+                    "brand": "Visa",
+                    "exp_month": 12,
+                    "exp_year": 2030,
+                    "last4": "4242"
+                }
+            }))
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tokens/tok_used"))
+            .respond_with(token("tok_used", now, true))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tokens/tok_old"))
+            .respond_with(token("tok_old", now - 3600, false))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/charges"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .max_token_age(Duration::from_secs(600));
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let result = client
+            .charges()
+            .create(CreateChargeParams::new(1000, "jpy").card("tok_used"))
+            .await;
+        assert!(matches!(result, Err(PayjpError::TokenAlreadyUsed { token }) if token == "tok_used"));
+
+        let result = client
+            .charges()
+            .create(CreateChargeParams::new(1000, "jpy").card("tok_old"))
+            .await;
+        assert!(
+            matches!(result, Err(PayjpError::TokenExpired { token, .. }) if token == "tok_old")
+        );
+
+        // The generic create path checks tokens too
+        let result = client
+            .create::<crate::Charge>(CreateChargeParams::new(1000, "jpy").card("tok_used"))
+            .await;
+        assert!(matches!(result, Err(PayjpError::TokenAlreadyUsed { .. })));
+        let params = crate::CreateCustomerParams::new().card("tok_old");
+        let result = client.create::<crate::Customer>(params).await;
+        assert!(matches!(result, Err(PayjpError::TokenExpired { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_accepts_any_response() {
+        use wiremock::matchers::{header_exists, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(header_exists("Authorization"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        client.warm_up().await.expect("Warm-up should succeed");
+
+        let options = ClientOptions::new().base_url("http://127.0.0.1:1");
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        assert!(matches!(client.warm_up().await, Err(PayjpError::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_key_is_refreshed_and_retried_once() {
+        use crate::auth::SecretString;
+        use async_trait::async_trait;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        struct RotatedKey {
+            current: Mutex<&'static str>,
+        }
+
+        #[async_trait]
+        impl KeyProvider for RotatedKey {
+            async fn api_key(&self) -> PayjpResult<SecretString> {
+                Ok((*self.current.lock().unwrap()).into())
+            }
+
+            async fn refresh_key(&self) -> PayjpResult<SecretString> {
+                *self.current.lock().unwrap() = "sk_test_new";
+                self.api_key().await
+            }
+        }
+
+        let basic = |key: &str| {
+            format!("Basic {}", general_purpose::STANDARD.encode(format!("{}:", key)))
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .and(header("Authorization", basic("sk_test_old").as_str()))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .and(header("Authorization", basic("sk_test_new").as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000,
+                "cards": { "object": "list", "data": [], "has_more": false, "url": "", "count": 0 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = RotatedKey { current: Mutex::new("sk_test_old") };
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_key_provider(provider, options)
+            .expect("Failed to create client");
+
+        let customer = client
+            .customers()
+            .retrieve("cus_1")
+            .await
+            .expect("Retry should succeed");
+        assert_eq!(customer.id, "cus_1");
+
+        // A rejected key that does not change is not retried
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_old", options)
+            .expect("Failed to create client");
+        let result = client.customers().retrieve("cus_1").await;
+        assert!(matches!(result, Err(PayjpError::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_public_key_from_provider_is_rejected() {
+        use crate::auth::SecretString;
+        use async_trait::async_trait;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        struct Keys {
+            current: &'static str,
+            refreshed: &'static str,
+        }
+
+        #[async_trait]
+        impl KeyProvider for Keys {
+            async fn api_key(&self) -> PayjpResult<SecretString> {
+                Ok(self.current.into())
+            }
+
+            async fn refresh_key(&self) -> PayjpResult<SecretString> {
+                Ok(self.refreshed.into())
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = |current, refreshed| {
+            let options = ClientOptions::new().base_url(&server.uri());
+            PayjpClient::with_key_provider(Keys { current, refreshed }, options)
+                .expect("Failed to create client")
+        };
+
+        // Never sent
+        let result = client("pk_test_xxxxx", "sk_test_xxxxx").account().retrieve().await;
+        assert!(matches!(result, Err(PayjpError::PublicKeyAsSecret)), "{:?}", result);
+
+        // Sent once, then the refreshed public key is rejected instead of retried
+        let result = client("sk_test_old", " pk_test_xxxxx\n").account().retrieve().await;
+        assert!(matches!(result, Err(PayjpError::PublicKeyAsSecret)), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_failover_on_connect_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Port 1 is reserved and refuses connections
+        let options = ClientOptions::new()
+            .base_url("http://127.0.0.1:1")
+            .failover_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let account = client.account().retrieve().await.expect("Failover should succeed");
+        assert_eq!(account.id, "acct_xxxxx");
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_names_path() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": "2015-06-01",
+                "padding": "x".repeat(500)
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        match client.account().retrieve().await {
+            Err(PayjpError::Decode(err)) => {
+                assert_eq!(err.path, "created");
+                #[cfg(not(feature = "simd"))]
+                assert!(err.inner().to_string().contains("expected i64"));
+                #[cfg(feature = "simd")]
+                assert!(err.inner().to_string().contains("ExpectedSigned"));
+                assert_eq!(err.method.as_deref(), Some("GET"));
+                assert_eq!(err.endpoint.as_deref(), Some("/account"));
+                assert_eq!(err.status, Some(200));
+                assert!(err
+                    .to_string()
+                    .contains("at `created` in response to GET /account (status 200)"));
+                assert!(err.snippet.contains("acct_xxxxx"));
+                assert!(err.snippet.chars().count() <= crate::DECODE_SNIPPET_LEN + 3);
+            }
+            other => panic!("Expected Decode error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_size_limit() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/account"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "acct_xxxxx",
+                "object": "account",
+                "livemode": false,
+                "created": 1433127983,
+                "padding": "x".repeat(2048)
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .max_response_size(1024);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let result = client.account().retrieve().await;
+        assert!(matches!(result, Err(PayjpError::ResponseTooLarge { limit: 1024 })));
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .max_response_size(4096);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        assert!(client.account().retrieve().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_post_retried_only_with_idempotency_key() {
+        use crate::request_id::with_idempotency_key;
+        use crate::CreateCustomerParams;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .and(header(IDEMPOTENCY_KEY_HEADER, "signup-1"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(1)
+            .expect(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .and(header(IDEMPOTENCY_KEY_HEADER, "signup-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1433127983,
+                "cards": { "object": "list", "data": [], "has_more": false, "url": "", "count": 0 }
+            })))
+            .expect(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(1)
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .retry_initial_delay(Duration::from_millis(1));
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let result = client.customers().create(CreateCustomerParams::new()).await;
+        assert!(matches!(result, Err(PayjpError::RateLimit)));
+
+        let customer = with_idempotency_key("signup-1", async {
+            client.customers().create(CreateCustomerParams::new()).await
+        })
+        .await
+        .expect("Keyed POST should be retried");
+        assert_eq!(customer.id, "cus_1");
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_conflict_carries_original_request_id() {
+        use crate::request_id::with_idempotency_key;
+        use crate::CreateCustomerParams;
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .and(body_string_contains("b%40example.com"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 409,
+                    "type": "client_error",
+                    "code": IDEMPOTENCY_CONFLICT_CODE,
+                    "message": "Idempotency key was used with different parameters"
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1433127983
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new()
+            .base_url(&server.uri())
+            .capture_last_exchange(true);
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let create = |email: &'static str| {
+            let client = &client;
+            with_idempotency_key("signup-1", async move {
+                client.customers().create(CreateCustomerParams::new().email(email)).await
+            })
+        };
+        create("a@example.com").await.expect("First request should succeed");
+        let original = client.last_exchange().unwrap().request_id;
+
+        match create("b@example.com").await {
+            Err(PayjpError::IdempotencyConflict {
+                key,
+                original_request_id,
+                error,
+            }) => {
+                assert_eq!(key, "signup-1");
+                assert_eq!(original_request_id, Some(original.clone()));
+                assert_ne!(error.request_id, Some(original));
+            }
+            other => panic!("Expected idempotency conflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_other_conflicts_with_idempotency_key_stay_api_errors() {
+        use crate::request_id::with_idempotency_key;
+        use crate::CreateCustomerParams;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 409,
+                    "type": "client_error",
+                    "code": "already_exist_id",
+                    "message": "Customer ID already exists"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let params = CreateCustomerParams::new().email("a@example.com");
+        let result = with_idempotency_key("signup-1", client.customers().create(params)).await;
+        match result {
+            Err(PayjpError::Api(err)) => {
+                assert_eq!(err.status, 409);
+                assert_eq!(err.code.as_deref(), Some("already_exist_id"));
+            }
+            other => panic!("Expected API error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_form_encoding_with_nested_structures() {
         use crate::resources::token::{CardDetails, CreateTokenParams};
@@ -0,0 +1,443 @@
+//! Generic access to resource endpoints.
+//!
+//! [`Retrievable`], [`Listable`] and [`Creatable`] connect resource types to
+//! their API endpoints, so code can be written once over many resources instead
+//! of going through each service by hand:
+//!
+//! ```no_run
+//! use payjp::endpoint::Listable;
+//! use payjp::{Charge, Customer, ListParams, PayjpClient, PayjpResult};
+//! use futures::TryStreamExt;
+//!
+//! async fn count<T: Listable>(client: &PayjpClient) -> PayjpResult<usize> {
+//!     let items: Vec<T> = client.list_all::<T>(ListParams::new().max_limit()).try_collect().await?;
+//!     Ok(items.len())
+//! }
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let charge = client.retrieve::<Charge>("ch_xxxxx").await?;
+//! println!("{} customers", count::<Customer>(&client).await?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Resources nested under another object, such as cards, are only available
+//! through their services.
+//! Tenants and tenant transfers are only available through
+//! [`PlatformClient`](crate::PlatformClient).
+
+use crate::client::PayjpClient;
+use crate::error::PayjpResult;
+use crate::pagination::{HasId, ListStream, PageParams};
+use crate::params::ListParams;
+#[cfg(feature = "platform")]
+use crate::resources::platform::{Tenant, TenantTransfer};
+use crate::resources::{
+    Charge, CreateChargeParams, CreateCustomerParams, CreateThreeDSecureRequestParams,
+    CreateTokenParams, Customer, Event, ThreeDSecureRequest, Token, Transfer,
+};
+#[cfg(feature = "subscriptions")]
+use crate::resources::{CreatePlanParams, CreateSubscriptionParams, Plan, Subscription};
+#[cfg(feature = "statements")]
+use crate::resources::{Balance, Statement, Term};
+use crate::response::{Deleted, ListResponse};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// A resource that can be retrieved by ID from `{PATH}/{id}`.
+pub trait Retrievable: DeserializeOwned + HasId + Send + 'static {
+    /// Path of the resource collection (e.g., `"/charges"`).
+    const PATH: &'static str;
+}
+
+/// A resource whose collection can be listed from [`Retrievable::PATH`].
+pub trait Listable: Retrievable {}
+
+/// A resource that can be created by posting to [`Retrievable::PATH`].
+pub trait Creatable: Retrievable {
+    /// Parameters accepted by the create endpoint.
+    type CreateParams: Serialize + Send + Sync;
+
+    /// Check the parameters before sending, as the service's `create` does.
+    fn validate_create(_params: &Self::CreateParams) -> PayjpResult<()> {
+        Ok(())
+    }
+
+    /// The card in the parameters, checked before sending when
+    /// [`ClientOptions::verify_tokens`](crate::ClientOptions::verify_tokens) is enabled.
+    fn card_token(_params: &Self::CreateParams) -> Option<&str> {
+        None
+    }
+}
+
+/// Path of a resource collection, used by the services' own requests.
+///
+/// Every [`Retrievable`] resource has one. Platform resources implement only
+/// this, so they are reachable through [`PlatformClient`](crate::PlatformClient)
+/// but not through the generic methods on [`PayjpClient`].
+pub(crate) trait ResourcePath: DeserializeOwned + HasId + Send + 'static {
+    const PATH: &'static str;
+}
+
+impl<T: Retrievable> ResourcePath for T {
+    const PATH: &'static str = <T as Retrievable>::PATH;
+}
+
+macro_rules! impl_retrievable {
+    ($($ty:ty => $path:literal),* $(,)?) => {
+        $(impl Retrievable for $ty {
+            const PATH: &'static str = $path;
+        })*
+    };
+}
+
+macro_rules! impl_listable {
+    ($($ty:ty),* $(,)?) => {
+        $(impl Listable for $ty {})*
+    };
+}
+
+macro_rules! impl_creatable {
+    ($($ty:ty => $params:ty),* $(,)?) => {
+        $(impl Creatable for $ty {
+            type CreateParams = $params;
+        })*
+    };
+}
+
+impl_retrievable!(
+    Charge => "/charges",
+    Customer => "/customers",
+    Event => "/events",
+    ThreeDSecureRequest => "/three_d_secure_requests",
+    Token => "/tokens",
+    Transfer => "/transfers",
+);
+
+impl_listable!(Charge, Customer, Event, ThreeDSecureRequest, Transfer);
+
+impl Creatable for Charge {
+    type CreateParams = CreateChargeParams;
+
+    fn card_token(params: &CreateChargeParams) -> Option<&str> {
+        params.card.as_deref()
+    }
+}
+
+impl Creatable for Customer {
+    type CreateParams = CreateCustomerParams;
+
+    fn card_token(params: &CreateCustomerParams) -> Option<&str> {
+        params.card.as_deref()
+    }
+}
+
+impl_creatable!(
+    ThreeDSecureRequest => CreateThreeDSecureRequestParams,
+    Token => CreateTokenParams,
+);
+
+#[cfg(feature = "subscriptions")]
+impl_retrievable!(
+    Plan => "/plans",
+    Subscription => "/subscriptions",
+);
+
+#[cfg(feature = "subscriptions")]
+impl_listable!(Plan, Subscription);
+
+#[cfg(feature = "subscriptions")]
+impl_creatable!(
+    Plan => CreatePlanParams,
+    Subscription => CreateSubscriptionParams,
+);
+
+#[cfg(feature = "statements")]
+impl_retrievable!(
+    Balance => "/balances",
+    Statement => "/statements",
+    Term => "/terms",
+);
+
+#[cfg(feature = "statements")]
+impl_listable!(Balance, Statement, Term);
+
+#[cfg(feature = "platform")]
+impl ResourcePath for Tenant {
+    const PATH: &'static str = "/tenants";
+}
+
+#[cfg(feature = "platform")]
+impl ResourcePath for TenantTransfer {
+    const PATH: &'static str = "/tenant_transfers";
+}
+
+impl PayjpClient {
+    /// Retrieve any [`Retrievable`] resource by ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{Charge, PayjpClient};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charge = client.retrieve::<Charge>("ch_xxxxx").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve<T: Retrievable>(&self, id: &str) -> PayjpResult<T> {
+        self.retrieve_resource(id).await
+    }
+
+    /// Retrieve many resources by ID, with at most `concurrency` requests in flight.
+    ///
+    /// Repeated IDs are retrieved once. Every ID gets its own result, so one
+    /// missing resource does not fail the others. A `concurrency` of 0 is
+    /// treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{Charge, PayjpClient};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charges = client.retrieve_many::<Charge, _>(["ch_1", "ch_2", "ch_1"], 4).await;
+    /// for (id, result) in &charges {
+    ///     match result {
+    ///         Ok(charge) => println!("{}: ¥{}", id, charge.amount),
+    ///         Err(e) => eprintln!("{}: {}", id, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve_many<T, I>(
+        &self,
+        ids: I,
+        concurrency: usize,
+    ) -> HashMap<String, PayjpResult<T>>
+    where
+        T: Retrievable,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut seen = HashSet::new();
+        let ids: Vec<String> = ids
+            .into_iter()
+            .map(|id| id.as_ref().to_string())
+            .filter(|id| seen.insert(id.clone()))
+            .collect();
+
+        futures::stream::iter(ids)
+            .map(|id| async move {
+                let result = self.retrieve::<T>(&id).await;
+                (id, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// List one page of any [`Listable`] resource.
+    pub async fn list<T: Listable>(&self, params: ListParams) -> PayjpResult<ListResponse<T>> {
+        self.list_resource(&params).await
+    }
+
+    /// List all items of any [`Listable`] resource, fetching further pages as the
+    /// stream is consumed.
+    pub fn list_all<T: Listable>(&self, params: ListParams) -> ListStream<'_, T> {
+        self.stream_resource(params)
+    }
+
+    /// Create any [`Creatable`] resource.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{CreateCustomerParams, Customer, PayjpClient};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let customer = client
+    ///     .create::<Customer>(CreateCustomerParams::new().email("taro@example.com"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create<T: Creatable>(&self, params: T::CreateParams) -> PayjpResult<T> {
+        T::validate_create(&params)?;
+        self.verify_token(T::card_token(&params)).await?;
+        self.post(T::PATH, &params).await
+    }
+}
+
+// Shared plumbing for the service types, so endpoint paths are defined once in
+// `impl_retrievable!`.
+impl PayjpClient {
+    /// Retrieve the resource at `{PATH}/{id}`.
+    pub(crate) async fn retrieve_resource<T: ResourcePath>(&self, id: &str) -> PayjpResult<T> {
+        let path = format!("{}/{}", T::PATH, id);
+        self.get(&path).await
+    }
+
+    /// Update the resource at `{PATH}/{id}`.
+    pub(crate) async fn update_resource<T: ResourcePath, P: Serialize>(
+        &self,
+        id: &str,
+        params: &P,
+    ) -> PayjpResult<T> {
+        let path = format!("{}/{}", T::PATH, id);
+        self.post(&path, params).await
+    }
+
+    /// Delete the resource at `{PATH}/{id}`.
+    pub(crate) async fn delete_resource<T: ResourcePath>(&self, id: &str) -> PayjpResult<Deleted<T>> {
+        let path = format!("{}/{}", T::PATH, id);
+        self.delete(&path).await
+    }
+
+    /// Post to an action sub-endpoint `{PATH}/{id}/{action}` returning the resource.
+    pub(crate) async fn resource_action<T: ResourcePath, P: Serialize>(
+        &self,
+        id: &str,
+        action: &str,
+        params: &P,
+    ) -> PayjpResult<T> {
+        let path = format!("{}/{}/{}", T::PATH, id, action);
+        self.post(&path, params).await
+    }
+
+    /// List one page of the collection at `PATH` with resource-specific parameters.
+    pub(crate) async fn list_resource<T: ResourcePath, P: PageParams>(
+        &self,
+        params: &P,
+    ) -> PayjpResult<ListResponse<T>> {
+        self.get_list(T::PATH, params).await
+    }
+
+    /// Stream the collection at `PATH` with resource-specific parameters.
+    pub(crate) fn stream_resource<'a, T: ResourcePath, P: PageParams + 'a>(
+        &'a self,
+        params: P,
+    ) -> ListStream<'a, T> {
+        ListStream::new(self, T::PATH.to_string(), params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_generic_retrieve_and_list() {
+        let server = MockServer::start().await;
+        let customer = serde_json::json!({
+            "id": "cus_1",
+            "object": "customer",
+            "livemode": false,
+            "created": 1700000000
+        });
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(customer.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [customer],
+                "has_more": false,
+                "url": "/v1/customers",
+                "count": 1
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let customer = client.retrieve::<Customer>("cus_1").await.unwrap();
+        assert_eq!(customer.id, "cus_1");
+
+        let customers = client.list::<Customer>(ListParams::new()).await.unwrap();
+        assert_eq!(customers.data.len(), 1);
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Widget {
+        id: String,
+    }
+
+    impl HasId for Widget {
+        fn id(&self) -> &str {
+            &self.id
+        }
+    }
+
+    impl Retrievable for Widget {
+        const PATH: &'static str = "/widgets";
+    }
+
+    impl Creatable for Widget {
+        type CreateParams = ListParams;
+
+        fn validate_create(_params: &ListParams) -> PayjpResult<()> {
+            Err(crate::PayjpError::InvalidRequest("rejected".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generic_create_validates_params() {
+        let client = PayjpClient::new("sk_test_xxxxx").expect("Failed to create client");
+        let result = client.create::<Widget>(ListParams::new()).await;
+        assert!(matches!(result, Err(crate::PayjpError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_many_deduplicates_ids() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 404,
+                    "type": "client_error",
+                    "code": "invalid_id",
+                    "message": "No such customer"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let customers = client
+            .customers()
+            .retrieve_many(["cus_1", "cus_missing", "cus_1"], 0)
+            .await;
+        assert_eq!(customers.len(), 2);
+        assert_eq!(customers["cus_1"].as_ref().unwrap().id, "cus_1");
+        assert!(matches!(customers["cus_missing"], Err(crate::PayjpError::Api(_))));
+    }
+}
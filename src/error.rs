@@ -1,14 +1,17 @@
 //! Error types for PAY.JP API interactions.
 
+use crate::params::ParamField;
+use crate::redact;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// The main error type for PAY.JP operations.
 #[derive(Debug, thiserror::Error)]
 pub enum PayjpError {
-    /// API error returned by PAY.JP.
+    /// API error returned by PAY.JP (boxed to keep `PayjpResult` small).
     #[error("API error: {0}")]
-    Api(#[from] ApiError),
+    Api(Box<ApiError>),
 
     /// Card-related error.
     #[error("Card error: {0}")]
@@ -30,17 +33,133 @@ pub enum PayjpError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    /// A response body that could not be decoded into the expected type.
+    #[error("Decode error: {0}")]
+    Decode(#[from] DecodeError),
+
     /// Invalid request (missing required parameters, etc.).
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// I/O error while writing to a caller-provided sink (e.g., an export).
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// URL parsing error.
     #[error("URL error: {0}")]
     Url(#[from] url::ParseError),
+
+    /// The response body was larger than [`ClientOptions::max_response_size`].
+    ///
+    /// [`ClientOptions::max_response_size`]: crate::ClientOptions::max_response_size
+    #[error("Response body exceeds the limit of {limit} bytes")]
+    ResponseTooLarge {
+        /// The configured limit in bytes.
+        limit: usize,
+    },
+
+    /// The client was shut down before or while the request was sent.
+    #[error("Client is shut down")]
+    Shutdown,
+
+    /// A card token was already used, found before sending the request it was
+    /// passed to (see [`ClientOptions::verify_tokens`]).
+    ///
+    /// [`ClientOptions::verify_tokens`]: crate::ClientOptions::verify_tokens
+    #[error("Token {token} has already been used")]
+    TokenAlreadyUsed {
+        /// The token ID.
+        token: String,
+    },
+
+    /// A card token was older than [`ClientOptions::max_token_age`], found
+    /// before sending the request it was passed to.
+    ///
+    /// [`ClientOptions::max_token_age`]: crate::ClientOptions::max_token_age
+    #[error(
+        "Token {token} was created {}s ago, more than the maximum of {}s",
+        .age.as_secs(),
+        .max_age.as_secs()
+    )]
+    TokenExpired {
+        /// The token ID.
+        token: String,
+
+        /// Age of the token, based on its `created` timestamp.
+        age: std::time::Duration,
+
+        /// The maximum age allowed.
+        max_age: std::time::Duration,
+    },
+
+    /// The API key's mode (live or test) does not match the base URL or a
+    /// response (see [`ClientOptions::livemode_check`]).
+    ///
+    /// [`ClientOptions::livemode_check`]: crate::ClientOptions::livemode_check
+    #[error("Livemode mismatch: {0}")]
+    LivemodeMismatch(String),
+
+    /// A public key (`pk_`) was given to [`PayjpClient`](crate::PayjpClient),
+    /// which needs a secret key (`sk_`).
+    #[error("PayjpClient requires a secret key (sk_), but a public key (pk_) was given")]
+    PublicKeyAsSecret,
+
+    /// A secret key (`sk_`) was given to [`PayjpPublicClient`](crate::PayjpPublicClient),
+    /// which needs a public key (`pk_`).
+    #[error("PayjpPublicClient requires a public key (pk_), but a secret key (sk_) was given")]
+    SecretKeyAsPublic,
+
+    /// An idempotency key was reused with different parameters (see
+    /// [`with_idempotency_key`](crate::request_id::with_idempotency_key)).
+    #[error("Idempotency key {key} was already used with different parameters: {error}")]
+    IdempotencyConflict {
+        /// The reused idempotency key.
+        key: String,
+
+        /// ID of the first request this client sent with the key (optional).
+        ///
+        /// The API does not return it: it comes from the client's in-process log of
+        /// the most recent keys it sent, shared only between clones of the client.
+        /// It is absent if the key was first used by another process, before a
+        /// restart, or long enough ago to have left the log.
+        original_request_id: Option<String>,
+
+        /// The API's conflict response, carrying the ID of the rejected request.
+        error: Box<ApiError>,
+    },
+
+    /// A webhook event was older than the maximum age allowed by a
+    /// [`WebhookValidator`](crate::webhook::WebhookValidator).
+    #[error(
+        "Webhook event {event_id} is {}s old, exceeding the limit of {}s",
+        .age.as_secs(),
+        .max_age.as_secs()
+    )]
+    StaleWebhook {
+        /// The event ID.
+        event_id: String,
+
+        /// Age of the event, based on its `created` timestamp.
+        age: std::time::Duration,
+
+        /// The maximum age allowed.
+        max_age: std::time::Duration,
+    },
+
+    /// A webhook event was delivered again within the replay window of a
+    /// [`WebhookValidator`](crate::webhook::WebhookValidator).
+    #[error("Webhook event {event_id} was already received")]
+    ReplayedWebhook {
+        /// The event ID.
+        event_id: String,
+    },
 }
 
 /// API error details returned by PAY.JP.
+///
+/// Marked `#[non_exhaustive]` so fields can be added without breaking callers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ApiError {
     /// HTTP status code.
     pub status: u16,
@@ -49,16 +168,31 @@ pub struct ApiError {
     #[serde(rename = "type")]
     pub error_type: String,
 
-    /// Human-readable error message.
+    /// Human-readable error message, with any echoed card data masked.
+    #[serde(deserialize_with = "deserialize_redacted")]
     pub message: String,
 
     /// Specific error code (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
 
-    /// Parameter that caused the error (optional).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Parameter that caused the error, with any echoed card data masked (optional).
+    #[serde(
+        default,
+        deserialize_with = "deserialize_redacted_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub param: Option<String>,
+
+    /// Client-generated ID of the request that failed (set by the client, not PAY.JP).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl From<ApiError> for PayjpError {
+    fn from(err: ApiError) -> Self {
+        PayjpError::Api(Box::new(err))
+    }
 }
 
 impl fmt::Display for ApiError {
@@ -66,13 +200,18 @@ impl fmt::Display for ApiError {
         write!(
             f,
             "[{}] {}: {}",
-            self.status, self.error_type, self.message
+            self.status,
+            self.error_type,
+            redact::redact_text(&self.message)
         )?;
         if let Some(code) = &self.code {
             write!(f, " (code: {})", code)?;
         }
         if let Some(param) = &self.param {
-            write!(f, " (param: {})", param)?;
+            write!(f, " (param: {})", redact::redact_text(param))?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request_id: {})", request_id)?;
         }
         Ok(())
     }
@@ -80,25 +219,168 @@ impl fmt::Display for ApiError {
 
 impl std::error::Error for ApiError {}
 
+impl ApiError {
+    /// The field of the params struct `F` that [`param`](Self::param) refers to.
+    pub fn param_field<F: ParamField>(&self) -> Option<F> {
+        F::from_param(self.param.as_deref()?)
+    }
+}
+
+/// Maximum number of characters of the response body kept in a [`DecodeError`].
+pub const DECODE_SNIPPET_LEN: usize = 200;
+
+/// Details of a response body that could not be decoded.
+#[derive(Debug)]
+pub struct DecodeError {
+    /// JSON path of the value that failed to decode (e.g., `data[0].card.exp_month`),
+    /// or `.` for the top-level value.
+    pub path: String,
+
+    /// Start of the response body, with card data redacted and truncated to
+    /// [`DECODE_SNIPPET_LEN`] characters.
+    pub snippet: String,
+
+    /// HTTP method of the request the body was returned for (e.g., "GET", optional).
+    pub method: Option<String>,
+
+    /// Path of the request relative to the base URL (e.g., "/charges/ch_xxxxx", optional).
+    pub endpoint: Option<String>,
+
+    /// HTTP status of the response (optional; absent for cached responses).
+    pub status: Option<u16>,
+
+    source: serde_json::Error,
+}
+
+impl DecodeError {
+    /// Decode a JSON response body, recording the failing path and a body snippet on error.
+    pub(crate) fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, DecodeError> {
+        let deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+        serde_path_to_error::deserialize(deserializer).map_err(|err| DecodeError {
+            path: err.path().to_string(),
+            snippet: snippet(bytes),
+            method: None,
+            endpoint: None,
+            status: None,
+            source: err.into_inner(),
+        })
+    }
+
+    /// Decode a response body the client owns.
+    ///
+    /// With the `simd` feature the body is parsed in place with simd-json instead
+    /// of being copied; otherwise this is the same as [`DecodeError::decode`].
+    pub(crate) fn decode_owned<T: DeserializeOwned>(bytes: Vec<u8>) -> Result<T, DecodeError> {
+        #[cfg(feature = "simd")]
+        {
+            let mut bytes = bytes;
+            let result = match simd_json::Deserializer::from_slice(&mut bytes) {
+                Ok(mut deserializer) => serde_path_to_error::deserialize(&mut deserializer)
+                    .map_err(|err| (err.path().to_string(), err.into_inner())),
+                Err(err) => Err((".".to_string(), err)),
+            };
+            // simd-json unescapes strings in place, so the snippet may differ from
+            // the body as received.
+            result.map_err(|(path, err)| DecodeError {
+                path,
+                snippet: snippet(&bytes),
+                method: None,
+                endpoint: None,
+                status: None,
+                source: serde::de::Error::custom(err),
+            })
+        }
+
+        #[cfg(not(feature = "simd"))]
+        Self::decode(&bytes)
+    }
+
+    /// Record the request the body was returned for.
+    pub(crate) fn with_request(mut self, method: &str, endpoint: &str, status: Option<u16>) -> Self {
+        self.method = Some(method.to_string());
+        self.endpoint = Some(endpoint.to_string());
+        self.status = status;
+        self
+    }
+
+    /// The underlying `serde_json` error, including the expected type and position.
+    pub fn inner(&self) -> &serde_json::Error {
+        &self.source
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at `{}`", self.source, self.path)?;
+        if let (Some(method), Some(endpoint)) = (&self.method, &self.endpoint) {
+            write!(f, " in response to {} {}", method, endpoint)?;
+            if let Some(status) = self.status {
+                write!(f, " (status {})", status)?;
+            }
+        }
+        write!(f, " (body: {})", self.snippet)
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Redact and truncate a response body for inclusion in an error.
+fn snippet(bytes: &[u8]) -> String {
+    let body = match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut value) => {
+            redact::redact_json(&mut value);
+            value.to_string()
+        }
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    };
+    match body.char_indices().nth(DECODE_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body,
+    }
+}
+
+/// Deserialize an error string, masking card data echoed by the API.
+fn deserialize_redacted<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    String::deserialize(deserializer).map(|text| redact::redact_text(&text))
+}
+
+/// Deserialize an optional error string, masking card data echoed by the API.
+fn deserialize_redacted_opt<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.map(|text| redact::redact_text(&text)))
+}
+
 /// Card-specific error details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardError {
     /// Error code.
     pub code: String,
 
-    /// Error message.
+    /// Error message, with any echoed card data masked.
+    #[serde(deserialize_with = "deserialize_redacted")]
     pub message: String,
 
-    /// Parameter that caused the error (optional).
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Parameter that caused the error, with any echoed card data masked (optional).
+    #[serde(
+        default,
+        deserialize_with = "deserialize_redacted_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub param: Option<String>,
 }
 
 impl fmt::Display for CardError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.code, self.message)?;
+        write!(f, "{}: {}", self.code, redact::redact_text(&self.message))?;
         if let Some(param) = &self.param {
-            write!(f, " (param: {})", param)?;
+            write!(f, " (param: {})", redact::redact_text(param))?;
         }
         Ok(())
     }
@@ -106,6 +388,146 @@ impl fmt::Display for CardError {
 
 impl std::error::Error for CardError {}
 
+impl CardError {
+    /// The field of the params struct `F` that [`param`](Self::param) refers to.
+    pub fn param_field<F: ParamField>(&self) -> Option<F> {
+        F::from_param(self.param.as_deref()?)
+    }
+}
+
+impl PayjpError {
+    /// Whether the failed request may succeed if retried later.
+    ///
+    /// Rate limits, network failures and server-side (5xx) errors are retryable;
+    /// card errors and invalid requests are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PayjpError::RateLimit => true,
+            PayjpError::Network(err) => err.is_timeout() || err.is_connect(),
+            PayjpError::Api(err) => err.status >= 500,
+            _ => false,
+        }
+    }
+
+    /// The field of the params struct `F` that caused this error, if the API
+    /// reported one.
+    ///
+    /// See [`params::ParamField`](crate::params::ParamField).
+    pub fn param_field<F: ParamField>(&self) -> Option<F> {
+        match self {
+            PayjpError::Api(err) => err.param_field(),
+            PayjpError::Card(err) => err.param_field(),
+            PayjpError::IdempotencyConflict { error, .. } => error.param_field(),
+            _ => None,
+        }
+    }
+
+    /// Convert this error into a stable, serializable [`ErrorDetails`].
+    pub fn details(&self) -> ErrorDetails {
+        ErrorDetails::from(self)
+    }
+}
+
+/// A stable, serializable summary of a PAY.JP error.
+///
+/// Useful for passing PAY.JP failures through an application's own JSON error
+/// envelope without exposing SDK internals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorDetails {
+    /// Error type (e.g., "card_error", "invalid_request_error", "network_error").
+    #[serde(rename = "type")]
+    pub error_type: String,
+
+    /// Specific error code (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Human-readable error message.
+    pub message: String,
+
+    /// Parameter that caused the error (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<String>,
+
+    /// HTTP status code, if the error came from an API response (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+
+    /// Request ID associated with the failed request, if available (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+
+    /// Whether the request may succeed if retried later.
+    pub retryable: bool,
+}
+
+impl From<&ApiError> for ErrorDetails {
+    fn from(err: &ApiError) -> Self {
+        Self {
+            error_type: err.error_type.clone(),
+            code: err.code.clone(),
+            message: err.message.clone(),
+            param: err.param.clone(),
+            status: Some(err.status),
+            request_id: err.request_id.clone(),
+            retryable: err.status >= 500,
+        }
+    }
+}
+
+impl From<&PayjpError> for ErrorDetails {
+    fn from(err: &PayjpError) -> Self {
+        let (error_type, status) = match err {
+            PayjpError::Api(api_err) => return api_err.as_ref().into(),
+            PayjpError::IdempotencyConflict { error, .. } => {
+                return Self {
+                    error_type: "idempotency_conflict".to_string(),
+                    message: err.to_string(),
+                    ..error.as_ref().into()
+                };
+            }
+            PayjpError::Card(card_err) => {
+                return Self {
+                    error_type: "card_error".to_string(),
+                    code: Some(card_err.code.clone()),
+                    message: card_err.message.clone(),
+                    param: card_err.param.clone(),
+                    status: None,
+                    request_id: None,
+                    retryable: false,
+                };
+            }
+            PayjpError::Auth(_) => ("auth_error", Some(401)),
+            PayjpError::RateLimit => ("rate_limit_error", Some(429)),
+            PayjpError::Network(_) => ("network_error", None),
+            PayjpError::Serialization(_) | PayjpError::Decode(_) => ("serialization_error", None),
+            PayjpError::InvalidRequest(_) => ("invalid_request_error", None),
+            PayjpError::Io(_) => ("io_error", None),
+            PayjpError::Url(_) => ("url_error", None),
+            PayjpError::ResponseTooLarge { .. } => ("response_too_large", None),
+            PayjpError::Shutdown => ("shutdown_error", None),
+            PayjpError::TokenAlreadyUsed { .. } => ("token_already_used", None),
+            PayjpError::TokenExpired { .. } => ("token_expired", None),
+            PayjpError::LivemodeMismatch(_) => ("livemode_mismatch", None),
+            PayjpError::PublicKeyAsSecret | PayjpError::SecretKeyAsPublic => {
+                ("invalid_key_type", None)
+            }
+            PayjpError::StaleWebhook { .. } => ("stale_webhook", None),
+            PayjpError::ReplayedWebhook { .. } => ("replayed_webhook", None),
+        };
+
+        Self {
+            error_type: error_type.to_string(),
+            code: None,
+            message: err.to_string(),
+            param: None,
+            status,
+            request_id: None,
+            retryable: err.is_retryable(),
+        }
+    }
+}
+
 /// Error response wrapper from PAY.JP API.
 #[derive(Debug, Deserialize)]
 pub(crate) struct ErrorResponse {
@@ -114,3 +536,139 @@ pub(crate) struct ErrorResponse {
 
 /// Result type alias for PAY.JP operations.
 pub type PayjpResult<T> = Result<T, PayjpError>;
+
+#[cfg(feature = "miette")]
+mod diagnostic {
+    use super::{ApiError, PayjpError};
+    use miette::Diagnostic;
+    use std::fmt::Display;
+
+    /// Base URL of the PAY.JP error reference.
+    const ERROR_DOCS_URL: &str = "https://pay.jp/docs/api/#error";
+
+    impl ApiError {
+        /// Get a human-readable hint for resolving this error, based on its code.
+        fn help_text(&self) -> Option<&'static str> {
+            let help = match self.code.as_deref()? {
+                "invalid_number" | "incorrect_number" => "Check the card number and try again.",
+                "invalid_cvc" | "incorrect_cvc" => "Check the card security code (CVC) and try again.",
+                "invalid_expiry_month" | "invalid_expiry_year" | "expired_card" => {
+                    "Check the card expiration date, or ask the customer for a different card."
+                }
+                "card_declined" => "The card was declined. Ask the customer to use a different card.",
+                "processing_error" => "A temporary error occurred. Retry the request later.",
+                "missing_card" => "Provide a `card` token or a `customer` with a default card.",
+                "unsafe_credit_card_param" => {
+                    "Raw card data was sent from a server. Create tokens with PAY.JP.js instead."
+                }
+                "already_captured" => "The charge has already been captured.",
+                "already_refunded" => "The charge has already been fully refunded.",
+                "over_capture_limit" => "The capture amount exceeds the authorized amount.",
+                "invalid_param_key" => "Remove the unsupported parameter from the request.",
+                "test_card_on_livemode" => "Test cards cannot be used with a live key.",
+                "token_already_used" => "Tokens can only be used once. Create a new token.",
+                _ => return None,
+            };
+            Some(help)
+        }
+    }
+
+    impl Diagnostic for ApiError {
+        fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            let code = self.code.as_deref().unwrap_or(&self.error_type);
+            Some(Box::new(format!("payjp::{}", code)))
+        }
+
+        fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            self.help_text().map(|help| Box::new(help) as Box<dyn Display>)
+        }
+
+        fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            Some(Box::new(ERROR_DOCS_URL))
+        }
+    }
+
+    impl Diagnostic for PayjpError {
+        fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            let code = match self {
+                PayjpError::Api(err) => return err.code(),
+                PayjpError::Card(err) => return Some(Box::new(format!("payjp::{}", err.code))),
+                PayjpError::Auth(_) => "payjp::auth",
+                PayjpError::RateLimit => "payjp::rate_limit",
+                PayjpError::Network(_) => "payjp::network",
+                PayjpError::Serialization(_) => "payjp::serialization",
+                PayjpError::Decode(_) => "payjp::decode",
+                PayjpError::InvalidRequest(_) => "payjp::invalid_request",
+                PayjpError::Io(_) => "payjp::io",
+                PayjpError::Url(_) => "payjp::url",
+                PayjpError::ResponseTooLarge { .. } => "payjp::response_too_large",
+                PayjpError::Shutdown => "payjp::shutdown",
+                PayjpError::TokenAlreadyUsed { .. } => "payjp::token_already_used",
+                PayjpError::TokenExpired { .. } => "payjp::token_expired",
+                PayjpError::LivemodeMismatch(_) => "payjp::livemode_mismatch",
+                PayjpError::PublicKeyAsSecret => "payjp::public_key_as_secret",
+                PayjpError::SecretKeyAsPublic => "payjp::secret_key_as_public",
+                PayjpError::IdempotencyConflict { .. } => "payjp::idempotency_conflict",
+                PayjpError::StaleWebhook { .. } => "payjp::stale_webhook",
+                PayjpError::ReplayedWebhook { .. } => "payjp::replayed_webhook",
+            };
+            Some(Box::new(code))
+        }
+
+        fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            let help = match self {
+                PayjpError::Api(err) => return err.help(),
+                PayjpError::Card(_) => "Ask the customer to check their card details or use a different card.",
+                PayjpError::Auth(_) => "Check that the API key is correct and matches the mode (test/live).",
+                PayjpError::RateLimit => {
+                    "Too many requests were sent. Reduce request concurrency or increase `max_retry`."
+                }
+                PayjpError::Network(_) => "Check network connectivity to the PAY.JP API and retry.",
+                PayjpError::Serialization(_) | PayjpError::Decode(_) => {
+                    "The response could not be decoded. Check `base_url` points at the PAY.JP API."
+                }
+                PayjpError::InvalidRequest(_) => "Check the request parameters.",
+                PayjpError::Io(_) => "Check that the output destination is writable.",
+                PayjpError::Url(_) => "Check the configured `base_url`.",
+                PayjpError::ResponseTooLarge { .. } => {
+                    "Request smaller pages with a lower `limit`, or raise `max_response_size`."
+                }
+                PayjpError::Shutdown => {
+                    "The client was shut down. Requests cancelled during shutdown may have been processed."
+                }
+                PayjpError::TokenAlreadyUsed { .. } => {
+                    "Tokens can only be used once. Ask the customer to enter their card again."
+                }
+                PayjpError::TokenExpired { .. } => {
+                    "Create a new token from the customer's card before using it."
+                }
+                PayjpError::LivemodeMismatch(_) => {
+                    "Check that the API key and `base_url` belong to the same environment."
+                }
+                PayjpError::PublicKeyAsSecret => {
+                    "Use the secret key from the PAY.JP dashboard, or PayjpPublicClient for tokens."
+                }
+                PayjpError::SecretKeyAsPublic => {
+                    "Use the public key; secret keys must never be shipped to clients."
+                }
+                PayjpError::IdempotencyConflict { .. } => {
+                    "Fetch the original request's result, or use a new key for a new operation."
+                }
+                PayjpError::StaleWebhook { .. } => {
+                    "Acknowledge the delivery without processing it; retrieve the event if needed."
+                }
+                PayjpError::ReplayedWebhook { .. } => {
+                    "The event was already received. Acknowledge it without processing it again."
+                }
+            };
+            Some(Box::new(help))
+        }
+
+        fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            match self {
+                PayjpError::Api(err) => err.url(),
+                _ => None,
+            }
+        }
+    }
+}
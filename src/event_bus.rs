@@ -0,0 +1,162 @@
+//! In-process fan-out of PAY.JP events.
+//!
+//! The webhook handler or an event poller publishes each received [`Event`] into
+//! an [`EventBus`]; independent consumers such as analytics, email and the ledger
+//! each hold their own [`EventSubscriber`] and receive every event, optionally
+//! filtered by [`EventType`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::event_bus::EventBus;
+//! use payjp::{Event, EventType};
+//!
+//! # async fn example(event: Event) {
+//! let bus = EventBus::default();
+//!
+//! let mut ledger = bus.subscribe_to([EventType::ChargeSucceeded, EventType::ChargeRefunded]);
+//! tokio::spawn(async move {
+//!     while let Some(event) = ledger.recv().await {
+//!         println!("ledger: {} {}", event.event_type, event.id);
+//!     }
+//! });
+//!
+//! // In the webhook handler
+//! bus.publish(event);
+//! # }
+//! ```
+
+use crate::resources::{Event, EventType};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Default number of events buffered for slow subscribers.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// Broadcasts events to any number of subscribers within one process.
+///
+/// Cloning the bus creates another handle to the same channel.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Create a bus buffering up to `capacity` events per subscriber.
+    ///
+    /// Subscribers that fall further behind skip the oldest events; see
+    /// [`EventSubscriber::missed`].
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Publish an event to all current subscribers.
+    ///
+    /// Returns the number of subscribers the event was delivered to.
+    pub fn publish(&self, event: Event) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// Subscribe to all events published from now on.
+    pub fn subscribe(&self) -> EventSubscriber {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+            event_types: None,
+            missed: 0,
+        }
+    }
+
+    /// Subscribe to events of the given types published from now on.
+    pub fn subscribe_to(
+        &self,
+        event_types: impl IntoIterator<Item = EventType>,
+    ) -> EventSubscriber {
+        EventSubscriber {
+            event_types: Some(event_types.into_iter().collect()),
+            ..self.subscribe()
+        }
+    }
+
+    /// Get the number of active subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+/// Receiving end of an [`EventBus`] subscription.
+#[derive(Debug)]
+pub struct EventSubscriber {
+    receiver: broadcast::Receiver<Event>,
+    event_types: Option<Vec<EventType>>,
+    missed: u64,
+}
+
+impl EventSubscriber {
+    /// Wait for the next matching event.
+    ///
+    /// Returns `None` once every handle to the bus has been dropped and all
+    /// buffered events have been received.
+    pub async fn recv(&mut self) -> Option<Event> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.matches(&event) => return Some(event),
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => self.missed += skipped,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Get the number of events dropped because this subscriber fell behind.
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    fn matches(&self, event: &Event) -> bool {
+        self.event_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&event.event_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, event_type: &str) -> Event {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "object": "event",
+            "livemode": false,
+            "created": 1700000000,
+            "type": event_type,
+            "data": { "object": { "id": "ch_1", "object": "charge" } }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_with_filters() {
+        let bus = EventBus::new(1);
+        let mut all = bus.subscribe();
+        let mut refunds = bus.subscribe_to([EventType::ChargeRefunded]);
+        assert_eq!(bus.subscriber_count(), 2);
+
+        assert_eq!(bus.publish(event("evnt_1", "charge.succeeded")), 2);
+        assert_eq!(all.recv().await.unwrap().id, "evnt_1");
+
+        bus.publish(event("evnt_2", "charge.refunded"));
+        assert_eq!(all.recv().await.unwrap().id, "evnt_2");
+        assert_eq!(refunds.recv().await.unwrap().id, "evnt_2");
+        assert_eq!(refunds.missed(), 1);
+
+        drop(bus);
+        assert!(all.recv().await.is_none());
+    }
+}
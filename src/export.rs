@@ -0,0 +1,343 @@
+//! Streaming export of list endpoints to a writer.
+//!
+//! Exports write each page to the sink as soon as it is fetched, so memory use
+//! stays at one page regardless of how many records are exported.
+//!
+//! Timestamps are written as Unix seconds, as PAY.JP sends them. Use
+//! [`ListStream::export_with_timestamps`] to write ISO-8601 strings in UTC or JST
+//! instead, or wrap a single resource in [`Timestamped`] when logging it or passing
+//! it to another API.
+//!
+//! ```no_run
+//! use payjp::{ExportFormat, PayjpClient};
+//! use tokio::fs::File;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let mut file = File::create("charges-2024.jsonl").await?;
+//!
+//! // Charges created in 2024 (JST), one JSON object per line
+//! let written = client
+//!     .charges()
+//!     .export(1_704_034_800..1_735_657_200, &mut file, ExportFormat::JsonLines)
+//!     .await?;
+//! println!("exported {} charges", written);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::PayjpResult;
+use crate::pagination::ListStream;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use futures::TryStreamExt;
+use serde::{Serialize, Serializer};
+use std::ops::{Bound, RangeBounds};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Output format for exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExportFormat {
+    /// One JSON object per line (JSON Lines / NDJSON).
+    JsonLines,
+    /// A single JSON array.
+    Json,
+}
+
+/// How resource timestamps are written when SDK types are serialized for output.
+///
+/// Only affects [`Timestamped`] and exports; requests to PAY.JP always use Unix
+/// timestamps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimestampFormat {
+    /// Unix timestamps in seconds, as sent by PAY.JP (e.g., `1704067200`).
+    #[default]
+    Unix,
+    /// ISO-8601 strings in UTC (e.g., `2024-01-01T00:00:00Z`).
+    Utc,
+    /// ISO-8601 strings in JST (e.g., `2024-01-01T09:00:00+09:00`).
+    Jst,
+}
+
+/// Names of the resource fields holding Unix timestamps.
+const TIMESTAMP_FIELDS: &[&str] = &[
+    "canceled_at",
+    "captured_at",
+    "closed_at",
+    "created",
+    "current_period_end",
+    "current_period_start",
+    "due_date",
+    "end_at",
+    "expired_at",
+    "expires",
+    "paused_at",
+    "resumed_at",
+    "scheduled_date",
+    "start",
+    "start_at",
+    "trial_end",
+    "updated",
+];
+
+impl TimestampFormat {
+    /// Format a Unix timestamp, or `None` for [`TimestampFormat::Unix`] and
+    /// out-of-range values.
+    fn format(self, timestamp: i64) -> Option<String> {
+        let time = DateTime::from_timestamp(timestamp, 0)?;
+        match self {
+            Self::Unix => None,
+            Self::Utc => Some(time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            Self::Jst => {
+                let jst = FixedOffset::east_opt(9 * 3600).expect("valid offset");
+                Some(time.with_timezone(&jst).to_rfc3339_opts(SecondsFormat::Secs, true))
+            }
+        }
+    }
+
+    /// Rewrite the timestamp fields of `value` and its nested objects.
+    fn apply(self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, field) in map.iter_mut() {
+                    let formatted = field
+                        .as_i64()
+                        .filter(|_| TIMESTAMP_FIELDS.contains(&key.as_str()))
+                        .and_then(|timestamp| self.format(timestamp));
+                    match formatted {
+                        Some(formatted) => *field = formatted.into(),
+                        None => self.apply(field),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            _ => {}
+        }
+    }
+}
+
+/// Serializes a resource with its timestamps in a [`TimestampFormat`].
+///
+/// # Example
+///
+/// ```
+/// use payjp::{Timestamped, TimestampFormat};
+///
+/// let event = serde_json::json!({ "id": "evnt_1", "created": 1704067200 });
+/// let json = serde_json::to_string(&Timestamped::new(&event, TimestampFormat::Jst)).unwrap();
+/// assert_eq!(json, r#"{"created":"2024-01-01T09:00:00+09:00","id":"evnt_1"}"#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Timestamped<'a, T: ?Sized> {
+    value: &'a T,
+    format: TimestampFormat,
+}
+
+impl<'a, T: ?Sized> Timestamped<'a, T> {
+    /// Wrap `value` to serialize its timestamps in `format`.
+    pub fn new(value: &'a T, format: TimestampFormat) -> Self {
+        Self { value, format }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Timestamped<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.format == TimestampFormat::Unix {
+            return self.value.serialize(serializer);
+        }
+        let mut value = serde_json::to_value(self.value).map_err(serde::ser::Error::custom)?;
+        self.format.apply(&mut value);
+        value.serialize(serializer)
+    }
+}
+
+impl<'a, T: Serialize + Send + 'a> ListStream<'a, T> {
+    /// Write every item of the stream to `writer`, page by page.
+    ///
+    /// Returns the number of items written. The writer is flushed but not shut
+    /// down. If a page request or a write fails, the error is returned and the
+    /// output ends after the last complete item.
+    pub async fn export<W>(self, writer: &mut W, format: ExportFormat) -> PayjpResult<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        self.export_with_timestamps(writer, format, TimestampFormat::Unix)
+            .await
+    }
+
+    /// Write every item of the stream to `writer` like [`export`](Self::export),
+    /// with timestamps in `timestamps`.
+    pub async fn export_with_timestamps<W>(
+        mut self,
+        writer: &mut W,
+        format: ExportFormat,
+        timestamps: TimestampFormat,
+    ) -> PayjpResult<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut line = Vec::new();
+        let mut written = 0;
+
+        if format == ExportFormat::Json {
+            writer.write_all(b"[").await?;
+        }
+        while let Some(item) = self.try_next().await? {
+            line.clear();
+            if format == ExportFormat::Json && written > 0 {
+                line.push(b',');
+            }
+            serde_json::to_writer(&mut line, &Timestamped::new(&item, timestamps))?;
+            if format == ExportFormat::JsonLines {
+                line.push(b'\n');
+            }
+            writer.write_all(&line).await?;
+            written += 1;
+        }
+        if format == ExportFormat::Json {
+            writer.write_all(b"]").await?;
+        }
+        writer.flush().await?;
+
+        Ok(written)
+    }
+}
+
+/// Convert a range of creation timestamps into inclusive `since`/`until` params.
+pub(crate) fn created_bounds(range: impl RangeBounds<i64>) -> (Option<i64>, Option<i64>) {
+    let since = match range.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => Some(start.saturating_add(1)),
+        Bound::Unbounded => None,
+    };
+    let until = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => Some(end.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+    (since, until)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientOptions, PayjpClient};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn page(ids: &[&str], has_more: bool) -> ResponseTemplate {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "object": "charge",
+                    "livemode": false,
+                    "created": 1704067200,
+                    "amount": 1000,
+                    "currency": "jpy",
+                    "paid": true,
+                    "captured": true,
+                    "refunded": false,
+                    "amount_refunded": 0
+                })
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": has_more,
+            "url": "/v1/charges",
+            "count": 3
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_export_charges_as_json_lines() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("since", "1704067200"))
+            .and(query_param("until", "1704153599"))
+            .and(query_param("offset", "2"))
+            .respond_with(page(&["ch_3"], false))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("since", "1704067200"))
+            .and(query_param("until", "1704153599"))
+            .respond_with(page(&["ch_1", "ch_2"], true))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let mut output = Vec::new();
+        let written = client
+            .charges()
+            .export(1704067200..1704153600, &mut output, ExportFormat::JsonLines)
+            .await
+            .unwrap();
+        assert_eq!(written, 3);
+
+        let ids: Vec<String> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["id"].to_string())
+            .collect();
+        assert_eq!(ids, ["\"ch_1\"", "\"ch_2\"", "\"ch_3\""]);
+    }
+
+    #[tokio::test]
+    async fn test_export_as_json_array() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(page(&["ch_1", "ch_2"], false))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let mut output = Vec::new();
+        client
+            .charges()
+            .export(.., &mut output, ExportFormat::Json)
+            .await
+            .unwrap();
+
+        let charges: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+        assert_eq!(charges.len(), 2);
+        assert_eq!(charges[1]["id"], "ch_2");
+    }
+
+    #[test]
+    fn test_timestamped_formats_nested_timestamp_fields() {
+        let charge = serde_json::json!({
+            "id": "ch_1",
+            "created": 1704067200,
+            "amount": 1000,
+            "card": { "id": "car_1", "created": 1704067200, "exp_year": 2030 },
+            "metadata": { "created": "kept as is" }
+        });
+
+        let utc = serde_json::to_value(Timestamped::new(&charge, TimestampFormat::Utc)).unwrap();
+        assert_eq!(utc["created"], "2024-01-01T00:00:00Z");
+        assert_eq!(utc["card"]["created"], "2024-01-01T00:00:00Z");
+        assert_eq!(utc["card"]["exp_year"], 2030);
+        assert_eq!(utc["amount"], 1000);
+        assert_eq!(utc["metadata"]["created"], "kept as is");
+
+        let jst = serde_json::to_value(Timestamped::new(&charge, TimestampFormat::Jst)).unwrap();
+        assert_eq!(jst["created"], "2024-01-01T09:00:00+09:00");
+
+        let unix = serde_json::to_value(Timestamped::new(&charge, TimestampFormat::Unix)).unwrap();
+        assert_eq!(unix, charge);
+    }
+}
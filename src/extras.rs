@@ -0,0 +1,162 @@
+//! Extra query parameters and headers for individual API calls.
+//!
+//! Run calls inside [`with_request_extras`] to send parameters or headers the
+//! typed methods do not know about yet, such as beta feature flags enabled for
+//! an account, while keeping the typed params and responses.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::extras::{with_request_extras, RequestExtras};
+//! use payjp::PayjpClient;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let extras = RequestExtras::new()
+//!     .query("expand", "customer")
+//!     .header("X-Payjp-Beta", "new-feature");
+//! let charge = with_request_extras(extras, client.charges().retrieve("ch_xxxxx")).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{PayjpError, PayjpResult};
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use std::future::Future;
+
+tokio::task_local! {
+    static EXTRAS: RequestExtras;
+}
+
+/// Query parameters and headers added to every request in a
+/// [`with_request_extras`] scope.
+#[derive(Debug, Clone, Default)]
+pub struct RequestExtras {
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+}
+
+impl RequestExtras {
+    /// Create an empty set of extras.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a query parameter, sent with requests of every method.
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Add a header.
+    ///
+    /// The `Authorization` header is set by the client and cannot be added.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Append the extra query parameters to `url`.
+    pub(crate) fn append_query(&self, url: &mut String) -> PayjpResult<()> {
+        if self.query.is_empty() {
+            return Ok(());
+        }
+        let query = serde_urlencoded::to_string(&self.query)
+            .map_err(|e| PayjpError::InvalidRequest(format!("Failed to encode query: {}", e)))?;
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&query);
+        Ok(())
+    }
+
+    /// Add the extra headers to `request`.
+    pub(crate) fn apply_headers(
+        &self,
+        mut request: reqwest::RequestBuilder,
+    ) -> PayjpResult<reqwest::RequestBuilder> {
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                PayjpError::InvalidRequest(format!("Invalid header name {:?}: {}", name, e))
+            })?;
+            if name == AUTHORIZATION {
+                return Err(PayjpError::InvalidRequest(
+                    "The Authorization header cannot be set as an extra".to_string(),
+                ));
+            }
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                PayjpError::InvalidRequest(format!("Invalid value for header {}: {}", name, e))
+            })?;
+            request = request.header(name, value);
+        }
+        Ok(request)
+    }
+}
+
+/// Run `future` with `extras` added to every API request it makes.
+///
+/// Nested scopes add to the extras of the enclosing scope. GET responses are
+/// never served from or stored in the response cache within a scope, since the
+/// extras may change the response.
+pub async fn with_request_extras<F: Future>(extras: RequestExtras, future: F) -> F::Output {
+    let extras = match current() {
+        Some(mut outer) => {
+            outer.query.extend(extras.query);
+            outer.headers.extend(extras.headers);
+            outer
+        }
+        None => extras,
+    };
+    EXTRAS.scope(extras, future).await
+}
+
+/// Get the extras of the current [`with_request_extras`] scope, if any.
+pub fn current() -> Option<RequestExtras> {
+    EXTRAS.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientOptions, PayjpClient};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_extras_are_sent_with_requests_in_scope() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .and(query_param("beta", "1"))
+            .and(header("X-Payjp-Beta", "feature"))
+            .and(header("X-Team", "billing"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000,
+                "cards": { "object": "list", "data": [], "has_more": false, "url": "", "count": 0 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let outer = RequestExtras::new().header("X-Team", "billing");
+        let inner = RequestExtras::new()
+            .query("beta", "1")
+            .header("X-Payjp-Beta", "feature");
+        let customer = with_request_extras(outer, async {
+            with_request_extras(inner, client.customers().retrieve("cus_1")).await
+        })
+        .await
+        .expect("Failed to retrieve customer");
+        assert_eq!(customer.id, "cus_1");
+
+        let extras = RequestExtras::new().header("Authorization", "Basic xxxxx");
+        let result = with_request_extras(extras, client.customers().retrieve("cus_1")).await;
+        assert!(matches!(result, Err(PayjpError::InvalidRequest(_))));
+    }
+}
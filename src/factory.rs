@@ -0,0 +1,476 @@
+//! Test data factories for SDK resource types.
+//!
+//! These builders produce fully-populated resource values with sensible defaults,
+//! for unit testing business logic that consumes SDK types without calling the API.
+//!
+//! # Example
+//!
+//! ```
+//! use payjp::factory::{ChargeFactory, SubscriptionFactory};
+//! use payjp::SubscriptionStatus;
+//!
+//! let charge = ChargeFactory::new()
+//!     .amount(5000)
+//!     .customer("cus_xxxxx")
+//!     .metadata("order_id", "1234")
+//!     .build();
+//! assert!(charge.paid);
+//!
+//! let subscription = SubscriptionFactory::new()
+//!     .status(SubscriptionStatus::Paused)
+//!     .with(|sub| sub.paused_at = Some(1_700_000_000))
+//!     .build();
+//! assert_eq!(subscription.status, SubscriptionStatus::Paused);
+//! ```
+
+use crate::resources::{Card, Charge, Customer};
+#[cfg(feature = "subscriptions")]
+use crate::resources::{Plan, PlanInterval, Subscription, SubscriptionStatus};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default creation timestamp used by the factories (2024-01-01 00:00:00 UTC).
+pub const DEFAULT_CREATED: i64 = 1_704_067_200;
+
+/// Generate a unique ID with the given prefix (e.g., `ch_factory_1`).
+fn next_id(prefix: &str) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(1);
+    format!("{}_factory_{}", prefix, COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Builder for test [`Card`] values.
+#[derive(Debug, Clone)]
+pub struct CardFactory {
+    card: Card,
+}
+
+impl Default for CardFactory {
+    fn default() -> Self {
+        Self {
+            card: Card {
+                id: next_id("car"),
+                object: "card".to_string(),
+                livemode: false,
+                created: DEFAULT_CREATED,
+                customer: None,
+                brand: "Visa".to_string(),
+                cvc_check: Some("passed".to_string()),
+                exp_month: 12,
+                exp_year: 2030,
+                fingerprint: Some("e1d8225886e3a7211127df751c86787f".to_string()),
+                last4: "4242".to_string(),
+                name: None,
+                address_line1: None,
+                address_line2: None,
+                address_city: None,
+                address_state: None,
+                address_zip: None,
+                address_zip_check: Some("unchecked".to_string()),
+                country: None,
+                three_d_secure_status: None,
+                email: None,
+                phone: None,
+                metadata: None,
+            },
+        }
+    }
+}
+
+impl CardFactory {
+    /// Create a new card factory with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the card ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.card.id = id.into();
+        self
+    }
+
+    /// Set the owning customer ID.
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.card.customer = Some(customer.into());
+        self
+    }
+
+    /// Set the card brand.
+    pub fn brand(mut self, brand: impl Into<String>) -> Self {
+        self.card.brand = brand.into();
+        self
+    }
+
+    /// Set the last 4 digits of the card number.
+    pub fn last4(mut self, last4: impl Into<String>) -> Self {
+        self.card.last4 = last4.into();
+        self
+    }
+
+    /// Set the expiration date.
+    pub fn expiration(mut self, month: i32, year: i32) -> Self {
+        self.card.exp_month = month;
+        self.card.exp_year = year;
+        self
+    }
+
+    /// Apply an arbitrary modification to the card.
+    pub fn with(mut self, f: impl FnOnce(&mut Card)) -> Self {
+        f(&mut self.card);
+        self
+    }
+
+    /// Build the card.
+    pub fn build(self) -> Card {
+        self.card
+    }
+}
+
+/// Builder for test [`Charge`] values.
+///
+/// By default the charge is a paid, captured ¥1,000 card charge.
+#[derive(Debug, Clone)]
+pub struct ChargeFactory {
+    charge: Charge,
+}
+
+impl Default for ChargeFactory {
+    fn default() -> Self {
+        Self {
+            charge: Charge {
+                id: next_id("ch"),
+                object: "charge".to_string(),
+                livemode: false,
+                created: DEFAULT_CREATED,
+                amount: 1000,
+                currency: "jpy".to_string(),
+                paid: true,
+                captured: true,
+                captured_at: Some(DEFAULT_CREATED),
+                card: Some(CardFactory::new().build().into()),
+                customer: None,
+                description: None,
+                failure_code: None,
+                failure_message: None,
+                fee_rate: Some("3.00".to_string()),
+                refunded: false,
+                amount_refunded: 0,
+                refund_reason: None,
+                subscription: None,
+                metadata: None,
+                expired_at: None,
+                three_d_secure_status: None,
+                tenant: None,
+                platform_fee: None,
+                platform_fee_rate: None,
+                total_platform_fee: None,
+            },
+        }
+    }
+}
+
+impl ChargeFactory {
+    /// Create a new charge factory with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the charge ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.charge.id = id.into();
+        self
+    }
+
+    /// Set the amount.
+    pub fn amount(mut self, amount: i64) -> Self {
+        self.charge.amount = amount;
+        self
+    }
+
+    /// Set the customer ID.
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.charge.customer = Some(customer.into());
+        self
+    }
+
+    /// Set the card.
+    pub fn card(mut self, card: Card) -> Self {
+        self.charge.card = Some(card.into());
+        self
+    }
+
+    /// Set the subscription ID.
+    pub fn subscription(mut self, subscription: impl Into<String>) -> Self {
+        self.charge.subscription = Some(subscription.into());
+        self
+    }
+
+    /// Make the charge an uncaptured authorization expiring at the given timestamp.
+    pub fn uncaptured(mut self, expired_at: i64) -> Self {
+        self.charge.captured = false;
+        self.charge.captured_at = None;
+        self.charge.expired_at = Some(expired_at);
+        self
+    }
+
+    /// Make the charge a failed charge with the given failure code and message.
+    pub fn failed(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        self.charge.paid = false;
+        self.charge.captured = false;
+        self.charge.captured_at = None;
+        self.charge.failure_code = Some(code.into());
+        self.charge.failure_message = Some(message.into());
+        self
+    }
+
+    /// Mark the given amount as refunded.
+    pub fn refunded(mut self, amount: i64) -> Self {
+        self.charge.amount_refunded = amount;
+        self.charge.refunded = true;
+        self
+    }
+
+    /// Add metadata to the charge.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.charge
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Apply an arbitrary modification to the charge.
+    pub fn with(mut self, f: impl FnOnce(&mut Charge)) -> Self {
+        f(&mut self.charge);
+        self
+    }
+
+    /// Build the charge.
+    pub fn build(self) -> Charge {
+        self.charge
+    }
+}
+
+/// Builder for test [`Customer`] values.
+#[derive(Debug, Clone)]
+pub struct CustomerFactory {
+    customer: Customer,
+}
+
+impl Default for CustomerFactory {
+    fn default() -> Self {
+        Self {
+            customer: Customer {
+                id: next_id("cus"),
+                object: "customer".to_string(),
+                livemode: false,
+                created: DEFAULT_CREATED,
+                default_card: None,
+                email: Some("customer@example.com".to_string()),
+                description: None,
+                metadata: None,
+                #[cfg(feature = "subscriptions")]
+                subscriptions: None,
+                cards: None,
+            },
+        }
+    }
+}
+
+impl CustomerFactory {
+    /// Create a new customer factory with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the customer ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.customer.id = id.into();
+        self
+    }
+
+    /// Set the email address.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.customer.email = Some(email.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.customer.description = Some(description.into());
+        self
+    }
+
+    /// Add metadata to the customer.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.customer
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Apply an arbitrary modification to the customer.
+    pub fn with(mut self, f: impl FnOnce(&mut Customer)) -> Self {
+        f(&mut self.customer);
+        self
+    }
+
+    /// Build the customer.
+    pub fn build(self) -> Customer {
+        self.customer
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+/// Builder for test [`Plan`] values.
+///
+/// By default the plan is a ¥1,000 monthly plan.
+#[derive(Debug, Clone)]
+pub struct PlanFactory {
+    plan: Plan,
+}
+
+#[cfg(feature = "subscriptions")]
+impl Default for PlanFactory {
+    fn default() -> Self {
+        Self {
+            plan: Plan {
+                id: next_id("pln"),
+                object: "plan".to_string(),
+                livemode: false,
+                created: DEFAULT_CREATED,
+                amount: 1000,
+                currency: "jpy".to_string(),
+                interval: PlanInterval::Month,
+                name: None,
+                trial_days: None,
+                billing_day: None,
+                metadata: None,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+impl PlanFactory {
+    /// Create a new plan factory with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the plan ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.plan.id = id.into();
+        self
+    }
+
+    /// Set the amount.
+    pub fn amount(mut self, amount: i64) -> Self {
+        self.plan.amount = amount;
+        self
+    }
+
+    /// Set the billing interval.
+    pub fn interval(mut self, interval: PlanInterval) -> Self {
+        self.plan.interval = interval;
+        self
+    }
+
+    /// Apply an arbitrary modification to the plan.
+    pub fn with(mut self, f: impl FnOnce(&mut Plan)) -> Self {
+        f(&mut self.plan);
+        self
+    }
+
+    /// Build the plan.
+    pub fn build(self) -> Plan {
+        self.plan
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+/// Builder for test [`Subscription`] values.
+///
+/// By default the subscription is an active subscription to a monthly plan.
+#[derive(Debug, Clone)]
+pub struct SubscriptionFactory {
+    subscription: Subscription,
+}
+
+#[cfg(feature = "subscriptions")]
+impl Default for SubscriptionFactory {
+    fn default() -> Self {
+        Self {
+            subscription: Subscription {
+                id: next_id("sub"),
+                object: "subscription".to_string(),
+                livemode: false,
+                created: DEFAULT_CREATED,
+                customer: next_id("cus"),
+                plan: PlanFactory::new().build(),
+                status: SubscriptionStatus::Active,
+                start: DEFAULT_CREATED,
+                trial_end: None,
+                paused_at: None,
+                canceled_at: None,
+                current_period_end: Some(DEFAULT_CREATED + 31 * 24 * 60 * 60),
+                current_period_start: Some(DEFAULT_CREATED),
+                resumed_at: None,
+                prorate: Some(false),
+                tenant: None,
+                metadata: None,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+impl SubscriptionFactory {
+    /// Create a new subscription factory with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the subscription ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.subscription.id = id.into();
+        self
+    }
+
+    /// Set the customer ID.
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.subscription.customer = customer.into();
+        self
+    }
+
+    /// Set the plan.
+    pub fn plan(mut self, plan: Plan) -> Self {
+        self.subscription.plan = plan;
+        self
+    }
+
+    /// Set the status.
+    pub fn status(mut self, status: SubscriptionStatus) -> Self {
+        self.subscription.status = status;
+        self
+    }
+
+    /// Add metadata to the subscription.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.subscription
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Apply an arbitrary modification to the subscription.
+    pub fn with(mut self, f: impl FnOnce(&mut Subscription)) -> Self {
+        f(&mut self.subscription);
+        self
+    }
+
+    /// Build the subscription.
+    pub fn build(self) -> Subscription {
+        self.subscription
+    }
+}
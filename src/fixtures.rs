@@ -0,0 +1,59 @@
+//! JSON responses shared by the unit tests.
+//!
+//! Each fixture has only the fields PAY.JP always sends; tests set the fields
+//! they care about with [`with`].
+
+use serde_json::Value;
+
+/// `value` with every field of the `fields` object set on top.
+pub(crate) fn with(mut value: Value, fields: Value) -> Value {
+    if let (Some(value), Value::Object(fields)) = (value.as_object_mut(), fields) {
+        value.extend(fields);
+    }
+    value
+}
+
+/// A paid and captured charge of 1000 JPY.
+pub(crate) fn charge(id: &str) -> Value {
+    serde_json::json!({
+        "id": id,
+        "object": "charge",
+        "livemode": false,
+        "created": 1704067200,
+        "amount": 1000,
+        "currency": "jpy",
+        "paid": true,
+        "captured": true,
+        "refunded": false,
+        "amount_refunded": 0
+    })
+}
+
+/// A monthly plan of 1000 JPY.
+#[cfg(feature = "subscriptions")]
+pub(crate) fn plan(id: &str) -> Value {
+    serde_json::json!({
+        "id": id,
+        "object": "plan",
+        "livemode": false,
+        "created": 1704067200,
+        "amount": 1000,
+        "currency": "jpy",
+        "interval": "month"
+    })
+}
+
+/// A subscription of customer `cus_1` to [`plan`] `pln_1`.
+#[cfg(feature = "subscriptions")]
+pub(crate) fn subscription(id: &str, status: &str) -> Value {
+    serde_json::json!({
+        "id": id,
+        "object": "subscription",
+        "livemode": false,
+        "created": 1704067200,
+        "customer": "cus_1",
+        "plan": plan("pln_1"),
+        "status": status,
+        "start": 1704067200
+    })
+}
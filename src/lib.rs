@@ -54,36 +54,132 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod api;
+pub mod auth;
+pub mod authorization;
+pub mod backoff;
+pub mod cache;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod client;
+pub mod endpoint;
 pub mod error;
+pub mod event_bus;
+pub mod export;
+pub mod extras;
+#[cfg(feature = "factory")]
+pub mod factory;
+#[cfg(test)]
+mod fixtures;
+pub mod livemode;
+pub mod merge;
+pub mod metrics;
+#[cfg(feature = "subscriptions")]
+pub mod migration;
+pub mod notification;
+#[cfg(feature = "otel")]
+mod otel;
+pub mod pagination;
 pub mod params;
+#[cfg(feature = "subscriptions")]
+pub mod pause;
+#[cfg(feature = "platform")]
+pub mod platform;
+pub mod query;
+pub mod receipt;
+mod redact;
+pub mod refund;
+pub mod request_id;
 pub mod resources;
 pub mod response;
+#[cfg(feature = "subscriptions")]
+pub mod revenue;
+pub mod shutdown;
+pub mod sync;
+pub mod tds;
+#[cfg(all(feature = "platform", feature = "statements"))]
+pub mod tenant_report;
+#[cfg(feature = "subscriptions")]
+pub mod watch;
+pub mod webhook;
 
 // Re-export main types
-pub use client::{ClientOptions, PayjpClient, PayjpPublicClient, DEFAULT_BASE_URL};
-pub use error::{ApiError, CardError, PayjpError, PayjpResult};
-pub use params::{ListParams, Metadata};
-pub use response::ListResponse;
+pub use api::PayjpApi;
+pub use authorization::{Authorization, AuthorizationState, ExpiryReport, ExpiryTracker};
+pub use cache::CacheOptions;
+pub use endpoint::{Creatable, Listable, Retrievable};
+pub use client::{
+    AppInfo, BodyDigest, BodyLogging, ClientOptions, Exchange, PayjpClient, PayjpPublicClient,
+    DEFAULT_BASE_URL,
+};
+pub use error::{
+    ApiError, CardError, DecodeError, ErrorDetails, PayjpError, PayjpResult, DECODE_SNIPPET_LEN,
+};
+pub use export::{ExportFormat, TimestampFormat, Timestamped};
+pub use livemode::LivemodeCheck;
+pub use merge::{CardMerge, CustomerMerge, MergeReport, MergeStep};
+#[cfg(feature = "subscriptions")]
+pub use merge::SubscriptionMerge;
+#[cfg(feature = "subscriptions")]
+pub use migration::{MigrationOptions, MigrationOutcome, MigrationReport, MigrationResult};
+pub use notification::Notification;
+pub use pagination::{ListCursor, ListStream};
+#[cfg(feature = "platform")]
+pub use platform::PlatformClient;
+pub use params::{ListParams, Metadata, MAX_LIMIT};
+#[cfg(feature = "subscriptions")]
+pub use pause::{AutoResumer, ResumeScheduler, RESUME_AT_METADATA_KEY};
+pub use query::{ChargeQuery, CustomerQuery};
+#[cfg(feature = "subscriptions")]
+pub use query::SubscriptionQuery;
+pub use receipt::{Receipt, TaxBreakdown};
+pub use refund::RefundTracker;
+pub use response::{Deleted, ListResponse};
+#[cfg(feature = "subscriptions")]
+pub use revenue::{RevenueReport, RevenueTotals};
+pub use shutdown::{AbandonedOperation, ShutdownReport};
+pub use sync::{
+    ResourceKind, ResourceStore, ResourceSync, SyncCheckpoint, SyncReport, SyncedResource,
+};
+#[cfg(all(feature = "platform", feature = "statements"))]
+pub use tenant_report::{BalanceTotals, TenantBalance, TenantBalanceReport};
+#[cfg(feature = "subscriptions")]
+pub use watch::{RenewalContext, RenewalWatcher};
 
 // Re-export resource types
 pub use resources::{
-    Account, AccountService, Balance, BalanceService, CancelSubscriptionParams, CaptureParams,
-    Card, CardDetails, CardOrId, CardService, CardThreeDSecureStatus, Charge, ChargeService,
-    CreateCardParams, CreateChargeParams, CreateCustomerParams, CreatePlanParams,
-    CreateSubscriptionParams, CreateThreeDSecureRequestParams, CreateTokenParams, Customer,
-    CustomerService, Event, EventData, EventService, EventType, ListChargeParams,
-    PauseSubscriptionParams, Plan, PlanInterval, PlanService, ReauthParams, RefundParams,
-    ResumeSubscriptionParams, Statement, StatementService, Subscription, SubscriptionService,
-    SubscriptionStatus, Term, TermService, ThreeDSecureRequest, ThreeDSecureRequestService,
-    ThreeDSecureStatus, Token, TokenService, PublicTokenService, Transfer, TransferService, UpdateCardParams,
-    UpdateChargeParams, UpdateCustomerParams, UpdatePlanParams, UpdateSubscriptionParams,
+    Account, AccountService, CaptureParams, Card, CardDetails, CardOrId, CardService,
+    CardThreeDSecureStatus, Charge, ChargeFailure, ChargeService, ChargeThreeDSecureParams, CreateCardParams,
+    CreateChargeParams, CreateChargeParamsRef, CreateCustomerParams, CustomerChargeService,
+    CreateThreeDSecureRequestParams, CreateTokenParams, Customer, CustomerService, Event,
+    EventData, EventService, EventType, ExpiringCards, FailureCode, FeeBreakdown, ListChargeParams, ListEventParams,
+    PayjpObject, PrettyJson, PublicTokenService, ReauthParams, RefundParams, ThreeDSecureOutcome,
+    ThreeDSecureRequest, ThreeDSecureRequestService, ThreeDSecureStatus, Token, TokenService,
+    Transfer, TransferService, TransferStatus, UpdateCardParams, UpdateChargeParams,
+    UpdateCustomerParams,
+};
+
+// Re-export plan and subscription types
+#[cfg(feature = "subscriptions")]
+pub use resources::{
+    CancelSubscriptionParams, CreatePlanParams, CreateSubscriptionParams,
+    CustomerSubscriptionService, ListSubscriptionParams, PauseSubscriptionParams, Plan, PlanInterval, PlanService, ResumeSubscriptionParams,
+    Subscription, SubscriptionService, SubscriptionStatus, UpdatePlanParams,
+    UpdateSubscriptionParams,
+};
+
+// Re-export statement, balance and term types
+#[cfg(feature = "statements")]
+pub use resources::{
+    Balance, BalanceService, BalanceState, ListStatementParams, Statement, StatementService,
+    StatementType, StatementUrls, StatementUrlsParams, Term, TermService,
 };
 
 // Re-export platform types
+#[cfg(feature = "platform")]
 pub use resources::platform::{
-    CreateTenantParams, Tenant, TenantService, TenantTransfer, TenantTransferService,
-    UpdateTenantParams,
+    CreateTenantParams, Tenant, TenantChargeService, TenantService, TenantTransfer,
+    TenantTransferService, TenantWrapper, TransferInterval, TransferSchedule, UpdateTenantParams,
 };
 
 // Add service accessor methods to PayjpClient
@@ -167,6 +263,7 @@ impl PayjpClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "subscriptions")]
     pub fn plans(&self) -> resources::PlanService<'_> {
         resources::PlanService::new(self)
     }
@@ -183,6 +280,7 @@ impl PayjpClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "subscriptions")]
     pub fn subscriptions(&self) -> resources::SubscriptionService<'_> {
         resources::SubscriptionService::new(self)
     }
@@ -247,6 +345,7 @@ impl PayjpClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "statements")]
     pub fn statements(&self) -> resources::StatementService<'_> {
         resources::StatementService::new(self)
     }
@@ -263,6 +362,7 @@ impl PayjpClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "statements")]
     pub fn balances(&self) -> resources::BalanceService<'_> {
         resources::BalanceService::new(self)
     }
@@ -279,43 +379,33 @@ impl PayjpClient {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "statements")]
     pub fn terms(&self) -> resources::TermService<'_> {
         resources::TermService::new(self)
     }
 
-    /// Access the 3D Secure requests service.
+    /// Access a specific term and the resources aggregated in it.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use payjp::PayjpClient;
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tds_request = client.three_d_secure_requests().retrieve("tdsr_xxxxx").await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn three_d_secure_requests(&self) -> resources::ThreeDSecureRequestService<'_> {
-        resources::ThreeDSecureRequestService::new(self)
-    }
-
-    /// Access the tenants service (Platform API).
-    ///
-    /// # Example
+    /// use futures::TryStreamExt;
     ///
-    /// ```no_run
-    /// # use payjp::PayjpClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tenant = client.tenants().retrieve("ten_xxxxx").await?;
+    /// let term = client.term("tm_xxxxx");
+    /// let statements: Vec<_> = term.statements().try_collect().await?;
+    /// let transfers: Vec<_> = term.transfers().try_collect().await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn tenants(&self) -> resources::platform::TenantService<'_> {
-        resources::platform::TenantService::new(self)
+    #[cfg(feature = "statements")]
+    pub fn term(&self, term_id: impl Into<String>) -> resources::term::TermWrapper<'_> {
+        resources::term::TermWrapper::new(self, term_id.into())
     }
 
-    /// Access the tenant transfers service (Platform API).
+    /// Access the 3D Secure requests service.
     ///
     /// # Example
     ///
@@ -323,12 +413,12 @@ impl PayjpClient {
     /// # use payjp::PayjpClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let transfer = client.tenant_transfers().retrieve("ttr_xxxxx").await?;
+    /// let tds_request = client.three_d_secure_requests().retrieve("tdsr_xxxxx").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn tenant_transfers(&self) -> resources::platform::TenantTransferService<'_> {
-        resources::platform::TenantTransferService::new(self)
+    pub fn three_d_secure_requests(&self) -> resources::ThreeDSecureRequestService<'_> {
+        resources::ThreeDSecureRequestService::new(self)
     }
 }
 
@@ -0,0 +1,160 @@
+//! Checks that live and test mode are not mixed up.
+//!
+//! With [`ClientOptions::livemode_check`] enabled, the client compares the mode
+//! of its API key (`sk_live_` or `sk_test_`) with where requests go and what
+//! comes back:
+//!
+//! - a live key used with a base URL other than [`DEFAULT_BASE_URL`], e.g. a
+//!   staging proxy or mock server left in a production config;
+//! - a response whose `livemode` does not match the key, e.g. a proxy that
+//!   forwards to a different account.
+//!
+//! [`LivemodeCheck::Warn`] logs a warning with target `payjp::livemode` (requires
+//! the `tracing` feature) and continues; [`LivemodeCheck::Error`] fails the
+//! request with [`PayjpError::LivemodeMismatch`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{ClientOptions, LivemodeCheck, PayjpClient};
+//!
+//! let options = ClientOptions::new().livemode_check(LivemodeCheck::Error);
+//! let client = PayjpClient::with_options("sk_live_xxxxx", options)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`ClientOptions::livemode_check`]: crate::ClientOptions::livemode_check
+//! [`DEFAULT_BASE_URL`]: crate::DEFAULT_BASE_URL
+
+use crate::auth::KeyKind;
+use crate::client::DEFAULT_BASE_URL;
+use crate::error::{PayjpError, PayjpResult};
+use serde::Deserialize;
+
+/// What to do when the API key's mode does not match the base URL or a response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LivemodeCheck {
+    /// Do not check (default).
+    #[default]
+    Off,
+
+    /// Log a warning and continue.
+    Warn,
+
+    /// Fail the request with [`PayjpError::LivemodeMismatch`].
+    Error,
+}
+
+/// Whether `api_key` is a live secret key, a test secret key, or neither.
+fn key_livemode(api_key: &str) -> Option<bool> {
+    let kind = KeyKind::of(api_key);
+    kind.is_secret().then(|| kind.is_live()).flatten()
+}
+
+/// The `livemode` of a response body, or of the first item of a list.
+fn response_livemode(body: &[u8]) -> Option<bool> {
+    #[derive(Deserialize)]
+    struct Probe {
+        livemode: Option<bool>,
+        data: Option<Vec<Probe>>,
+    }
+
+    let probe: Probe = serde_json::from_slice(body).ok()?;
+    probe
+        .livemode
+        .or_else(|| probe.data?.into_iter().next()?.livemode)
+}
+
+/// Check that a live key is only sent to the default base URL.
+pub(crate) fn check_base_url(
+    check: LivemodeCheck,
+    api_key: &str,
+    base_url: &str,
+) -> PayjpResult<()> {
+    if check == LivemodeCheck::Off || key_livemode(api_key) != Some(true) {
+        return Ok(());
+    }
+    if base_url.trim_end_matches('/') == DEFAULT_BASE_URL {
+        return Ok(());
+    }
+    report(
+        check,
+        format!("live API key used with non-default base URL {}", base_url),
+    )
+}
+
+/// Check that a response's `livemode` matches the key's mode.
+pub(crate) fn check_response(check: LivemodeCheck, api_key: &str, body: &[u8]) -> PayjpResult<()> {
+    if check == LivemodeCheck::Off {
+        return Ok(());
+    }
+    let (Some(expected), Some(actual)) = (key_livemode(api_key), response_livemode(body)) else {
+        return Ok(());
+    };
+    if expected == actual {
+        return Ok(());
+    }
+    let mode = |live: bool| if live { "live" } else { "test" };
+    report(
+        check,
+        format!(
+            "{} API key received a {} mode response",
+            mode(expected),
+            mode(actual)
+        ),
+    )
+}
+
+fn report(check: LivemodeCheck, message: String) -> PayjpResult<()> {
+    match check {
+        LivemodeCheck::Error => Err(PayjpError::LivemodeMismatch(message)),
+        _ => {
+            log_mismatch(&message);
+            Ok(())
+        }
+    }
+}
+
+/// Emit a mode mismatch warning with target `payjp::livemode` (requires the `tracing` feature).
+#[cfg(feature = "tracing")]
+fn log_mismatch(message: &str) {
+    tracing::warn!(target: "payjp::livemode", "{}", message);
+}
+
+#[cfg(not(feature = "tracing"))]
+fn log_mismatch(_message: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_livemode_reads_objects_and_lists() {
+        assert_eq!(response_livemode(br#"{"id":"ch_1","livemode":true}"#), Some(true));
+        assert_eq!(
+            response_livemode(br#"{"object":"list","data":[{"livemode":false}]}"#),
+            Some(false)
+        );
+        assert_eq!(response_livemode(br#"{"object":"list","data":[]}"#), None);
+    }
+
+    #[test]
+    fn test_checks_compare_key_mode() {
+        let check = LivemodeCheck::Error;
+        assert!(check_base_url(check, "sk_live_xxxxx", DEFAULT_BASE_URL).is_ok());
+        assert!(check_base_url(check, "sk_test_xxxxx", "http://localhost:8080").is_ok());
+        assert!(matches!(
+            check_base_url(check, "sk_live_xxxxx", "http://localhost:8080"),
+            Err(PayjpError::LivemodeMismatch(_))
+        ));
+        assert!(check_base_url(LivemodeCheck::Warn, "sk_live_xxxxx", "http://x").is_ok());
+
+        let body = br#"{"id":"ch_1","livemode":true}"#;
+        assert!(check_response(check, "sk_live_xxxxx", body).is_ok());
+        assert!(matches!(
+            check_response(check, "sk_test_xxxxx", body),
+            Err(PayjpError::LivemodeMismatch(_))
+        ));
+        assert!(check_response(LivemodeCheck::Off, "sk_test_xxxxx", body).is_ok());
+    }
+}
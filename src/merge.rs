@@ -0,0 +1,616 @@
+//! Merging duplicate customers.
+//!
+//! [`CustomerMerge`] moves what it can from a duplicate customer to the one being
+//! kept, then deletes the duplicate:
+//!
+//! - Cards cannot be copied between customers, so each card needs a new token
+//!   from the cardholder, supplied with [`CustomerMerge::card_token`]. Cards the
+//!   kept customer already has (same fingerprint) are skipped.
+//! - Subscriptions are re-created on the kept customer with the same plan, with a
+//!   trial until the end of the period already paid for, and the originals are
+//!   canceled. Trials without an end date are not moved, since the new
+//!   subscription would be billed immediately.
+//! - Metadata keys missing from the kept customer are copied over.
+//!
+//! Subscriptions and metadata are only moved once every card has been, so a
+//! merge that is missing card tokens changes nothing and can simply be run
+//! again. The duplicate is only deleted if every step succeeded. Run with
+//! [`CustomerMerge::dry_run`] first to see what would be done, and which cards
+//! still need a token.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{MergeStep, PayjpClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let report = client
+//!     .customers()
+//!     .merge("cus_keep", "cus_duplicate")
+//!     .card_token("car_old", "tok_from_payjp_js")
+//!     .dry_run(true)
+//!     .run()
+//!     .await?;
+//! for card in &report.cards {
+//!     if let MergeStep::Blocked(reason) = &card.step {
+//!         println!("{} ending in {}: {}", card.brand, card.last4, reason);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::params::{ListParams, Metadata};
+use crate::resources::{Card, CreateCardParams, Customer, UpdateCustomerParams};
+#[cfg(feature = "subscriptions")]
+use crate::resources::{
+    CancelSubscriptionParams, CreateSubscriptionParams, Subscription, SubscriptionStatus,
+};
+use futures::TryStreamExt;
+use std::collections::{HashMap, HashSet};
+
+/// Outcome of one step of a merge.
+#[derive(Debug)]
+pub enum MergeStep<T> {
+    /// The step would be performed (dry run).
+    Planned,
+
+    /// The step was performed; holds the resource created on the kept customer.
+    Done(T),
+
+    /// Nothing to do, e.g. the kept customer already has the card.
+    Unnecessary,
+
+    /// The step cannot be performed automatically, for the given reason.
+    Blocked(String),
+
+    /// The API request failed.
+    Failed(PayjpError),
+}
+
+impl<T> MergeStep<T> {
+    /// Whether the step is blocked or failed.
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, MergeStep::Blocked(_) | MergeStep::Failed(_))
+    }
+}
+
+/// How a card of the duplicate customer was handled.
+#[derive(Debug)]
+pub struct CardMerge {
+    /// Card ID on the duplicate customer.
+    pub card_id: String,
+
+    /// Card brand (e.g., "Visa").
+    pub brand: String,
+
+    /// Last four digits of the card number.
+    pub last4: String,
+
+    /// Outcome; `Done` holds the card added to the kept customer.
+    pub step: MergeStep<Card>,
+}
+
+/// How a subscription of the duplicate customer was handled.
+#[cfg(feature = "subscriptions")]
+#[derive(Debug)]
+pub struct SubscriptionMerge {
+    /// Subscription ID on the duplicate customer.
+    pub subscription_id: String,
+
+    /// Plan ID of the subscription.
+    pub plan: String,
+
+    /// Outcome; `Done` holds the subscription created on the kept customer.
+    ///
+    /// If canceling the original fails after the new subscription was created,
+    /// the step is `Failed` and both subscriptions are active.
+    pub step: MergeStep<Subscription>,
+}
+
+/// What a [`CustomerMerge`] did, or would do in a dry run.
+#[derive(Debug)]
+pub struct MergeReport {
+    /// ID of the customer that is kept.
+    pub winner: String,
+
+    /// ID of the duplicate customer.
+    pub loser: String,
+
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+
+    /// One entry per card of the duplicate customer.
+    pub cards: Vec<CardMerge>,
+
+    /// One entry per subscription of the duplicate customer that is not canceled.
+    #[cfg(feature = "subscriptions")]
+    pub subscriptions: Vec<SubscriptionMerge>,
+
+    /// Metadata copied (or to be copied) to the kept customer.
+    pub metadata_added: Metadata,
+
+    /// Outcome of copying `metadata_added` to the kept customer.
+    pub metadata: MergeStep<()>,
+
+    /// Metadata keys both customers have with different values; the kept
+    /// customer's values are not changed.
+    pub metadata_conflicts: Vec<String>,
+
+    /// Outcome of deleting the duplicate customer.
+    pub delete: MergeStep<()>,
+}
+
+impl MergeReport {
+    /// Whether everything was merged and the duplicate was deleted (or would be,
+    /// in a dry run).
+    pub fn is_complete(&self) -> bool {
+        !self.delete.is_incomplete()
+    }
+}
+
+/// Merges a duplicate customer into the customer that is kept.
+///
+/// Created with [`CustomerService::merge`](crate::CustomerService::merge). See the
+/// [module documentation](self) for what is merged.
+#[derive(Debug)]
+pub struct CustomerMerge<'a> {
+    client: &'a PayjpClient,
+    winner: String,
+    loser: String,
+    tokens: HashMap<String, String>,
+    dry_run: bool,
+}
+
+impl<'a> CustomerMerge<'a> {
+    /// Merge `loser` into `winner`.
+    pub fn new(
+        client: &'a PayjpClient,
+        winner: impl Into<String>,
+        loser: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            winner: winner.into(),
+            loser: loser.into(),
+            tokens: HashMap::new(),
+            dry_run: false,
+        }
+    }
+
+    /// Provide a new token for the duplicate's card `card_id`, created by the
+    /// cardholder with PAY.JP.js, so the card can be added to the kept customer.
+    pub fn card_token(mut self, card_id: impl Into<String>, token: impl Into<String>) -> Self {
+        self.tokens.insert(card_id.into(), token.into());
+        self
+    }
+
+    /// Only report what would be done, without changing either customer.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Run the merge.
+    ///
+    /// Failures of individual steps are recorded in the report; failing to load
+    /// either customer, their cards or subscriptions returns an error before
+    /// anything is changed.
+    pub async fn run(self) -> PayjpResult<MergeReport> {
+        if self.winner == self.loser {
+            return Err(PayjpError::InvalidRequest(format!(
+                "Cannot merge customer {} into itself",
+                self.winner
+            )));
+        }
+        let winner = self.client.customers().retrieve(&self.winner).await?;
+        let loser = self.client.customers().retrieve(&self.loser).await?;
+        let winner_cards = self.cards_of(&self.winner).await?;
+        let loser_cards = self.cards_of(&self.loser).await?;
+        #[cfg(feature = "subscriptions")]
+        let subscriptions: Vec<Subscription> = self
+            .client
+            .customer(&self.loser)
+            .subscriptions()
+            .list_all(ListParams::new().max_limit())
+            .filter_local(|subscription| subscription.status != SubscriptionStatus::Canceled)
+            .try_collect()
+            .await?;
+
+        let cards = self.merge_cards(&winner_cards, loser_cards).await;
+        let cards_moved = !cards.iter().any(|card| card.step.is_incomplete());
+        #[cfg(feature = "subscriptions")]
+        let subscriptions = self.merge_subscriptions(subscriptions, cards_moved).await;
+
+        let (metadata_added, metadata_conflicts) = merge_metadata(&winner, &loser);
+        let metadata = if metadata_added.is_empty() {
+            MergeStep::Unnecessary
+        } else if !cards_moved {
+            MergeStep::Blocked("Not copied until every card is moved".to_string())
+        } else if self.dry_run {
+            MergeStep::Planned
+        } else {
+            let params = UpdateCustomerParams::new().metadata_map(metadata_added.clone());
+            match self.client.customers().update(&self.winner, params).await {
+                Ok(_) => MergeStep::Done(()),
+                Err(e) => MergeStep::Failed(e),
+            }
+        };
+
+        let blocked = !cards_moved || metadata.is_incomplete();
+        #[cfg(feature = "subscriptions")]
+        let blocked = blocked
+            || subscriptions
+                .iter()
+                .any(|subscription| subscription.step.is_incomplete());
+        let delete = if blocked {
+            MergeStep::Blocked("Not every card, subscription and metadata key was moved".to_string())
+        } else if self.dry_run {
+            MergeStep::Planned
+        } else {
+            match self.client.customers().delete(&self.loser).await {
+                Ok(_) => MergeStep::Done(()),
+                Err(e) => MergeStep::Failed(e),
+            }
+        };
+        Ok(MergeReport {
+            winner: self.winner.clone(),
+            loser: self.loser.clone(),
+            dry_run: self.dry_run,
+            cards,
+            #[cfg(feature = "subscriptions")]
+            subscriptions,
+            metadata_added,
+            metadata,
+            metadata_conflicts,
+            delete,
+        })
+    }
+
+    async fn merge_cards(&self, winner_cards: &[Card], loser_cards: Vec<Card>) -> Vec<CardMerge> {
+        let fingerprints: HashSet<_> = winner_cards
+            .iter()
+            .filter_map(|card| card.fingerprint.as_deref())
+            .collect();
+
+        let mut merged = Vec::new();
+        for card in loser_cards {
+            let duplicate = card
+                .fingerprint
+                .as_ref()
+                .is_some_and(|fingerprint| fingerprints.contains(fingerprint.as_str()));
+            let step = match self.tokens.get(&card.id) {
+                _ if duplicate => MergeStep::Unnecessary,
+                None => MergeStep::Blocked(
+                    "Card data cannot be copied between customers; provide a new token"
+                        .to_string(),
+                ),
+                Some(_) if self.dry_run => MergeStep::Planned,
+                Some(token) => {
+                    let mut params = CreateCardParams::new(token.clone());
+                    params.metadata = card.metadata.clone();
+                    let cards = self.client.customer(&self.winner);
+                    match cards.cards().create(params).await {
+                        Ok(created) => MergeStep::Done(created),
+                        Err(e) => MergeStep::Failed(e),
+                    }
+                }
+            };
+            merged.push(CardMerge {
+                card_id: card.id,
+                brand: card.brand,
+                last4: card.last4,
+                step,
+            });
+        }
+        merged
+    }
+
+    async fn cards_of(&self, customer_id: &str) -> PayjpResult<Vec<Card>> {
+        let customer = self.client.customer(customer_id);
+        customer
+            .cards()
+            .list_all(ListParams::new().max_limit())
+            .try_collect()
+            .await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    async fn merge_subscriptions(
+        &self,
+        subscriptions: Vec<Subscription>,
+        cards_moved: bool,
+    ) -> Vec<SubscriptionMerge> {
+        let mut merged = Vec::new();
+        for subscription in subscriptions {
+            let step = match subscription.status {
+                _ if !cards_moved => {
+                    MergeStep::Blocked("Not moved until every card is moved".to_string())
+                }
+                SubscriptionStatus::Paused => MergeStep::Blocked(
+                    "Paused subscriptions are not moved; resume or cancel it first".to_string(),
+                ),
+                SubscriptionStatus::Trial if subscription.trial_end.is_none() => {
+                    MergeStep::Blocked(
+                        "Trial has no end date; the new subscription would be billed immediately"
+                            .to_string(),
+                    )
+                }
+                _ if self.dry_run => MergeStep::Planned,
+                _ => self.move_subscription(&subscription).await,
+            };
+            merged.push(SubscriptionMerge {
+                subscription_id: subscription.id,
+                plan: subscription.plan.id,
+                step,
+            });
+        }
+        merged
+    }
+
+    /// Re-create `subscription` on the kept customer, then cancel the original.
+    #[cfg(feature = "subscriptions")]
+    async fn move_subscription(&self, subscription: &Subscription) -> MergeStep<Subscription> {
+        // The duplicate has already paid for the current period
+        let paid_until = match subscription.status {
+            SubscriptionStatus::Trial => subscription.trial_end,
+            _ => subscription.current_period_end,
+        };
+        let mut params = CreateSubscriptionParams::new(&self.winner, &subscription.plan.id);
+        params.trial_end = paid_until;
+        params.metadata = subscription.metadata.clone();
+
+        let subscriptions = self.client.subscriptions();
+        let created = match subscriptions.create(params).await {
+            Ok(created) => created,
+            Err(e) => return MergeStep::Failed(e),
+        };
+        match subscriptions
+            .cancel(&subscription.id, CancelSubscriptionParams::new())
+            .await
+        {
+            Ok(_) => MergeStep::Done(created),
+            Err(e) => MergeStep::Failed(e),
+        }
+    }
+}
+
+/// Metadata of `loser` missing from `winner`, and keys whose values differ.
+fn merge_metadata(winner: &Customer, loser: &Customer) -> (Metadata, Vec<String>) {
+    let empty = Metadata::new();
+    let current = winner.metadata.as_ref().unwrap_or(&empty);
+    let mut added = Metadata::new();
+    let mut conflicts = Vec::new();
+    for (key, value) in loser.metadata.iter().flatten() {
+        match current.get(key) {
+            None => {
+                added.insert(key.clone(), value.clone());
+            }
+            Some(existing) if existing != value => conflicts.push(key.clone()),
+            Some(_) => {}
+        }
+    }
+    conflicts.sort();
+    (added, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "subscriptions")]
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn customer(id: &str, metadata: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "customer",
+            "livemode": false,
+            "created": 1704067200,
+            "metadata": metadata
+        })
+    }
+
+    fn card(id: &str, fingerprint: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "card",
+            "livemode": false,
+            "created": 1704067200,
+            "brand": "Visa",
+            "exp_month": 12,
+            "exp_year": 2030,
+            "last4": "4242",
+            "fingerprint": fingerprint
+        })
+    }
+
+    fn list(data: Vec<serde_json::Value>) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": false,
+            "url": "/v1/list",
+            "count": data.len()
+        }))
+    }
+
+    async fn mount(server: &MockServer, http_method: &str, url: &str, response: ResponseTemplate) {
+        Mock::given(method(http_method))
+            .and(path(url))
+            .respond_with(response)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_merge_moves_cards_and_metadata_then_deletes() {
+        let server = MockServer::start().await;
+        let ok = |body| ResponseTemplate::new(200).set_body_json(body);
+        mount(
+            &server,
+            "GET",
+            "/customers/cus_keep",
+            ok(customer("cus_keep", serde_json::json!({"plan": "gold"}))),
+        )
+        .await;
+        mount(
+            &server,
+            "GET",
+            "/customers/cus_dup",
+            ok(customer("cus_dup", serde_json::json!({"plan": "silver", "crm": "42"}))),
+        )
+        .await;
+        mount(&server, "GET", "/customers/cus_keep/cards", list(vec![card("car_a", "fp_a")])).await;
+        mount(
+            &server,
+            "GET",
+            "/customers/cus_dup/cards",
+            list(vec![card("car_same", "fp_a"), card("car_b", "fp_b")]),
+        )
+        .await;
+        mount(&server, "GET", "/customers/cus_dup/subscriptions", list(vec![])).await;
+        Mock::given(method("POST"))
+            .and(path("/customers/cus_keep/cards"))
+            .and(body_string_contains("card=tok_b"))
+            .respond_with(ok(card("car_new", "fp_b")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/customers/cus_keep"))
+            .and(body_string_contains("metadata%5Bcrm%5D=42"))
+            .respond_with(ok(customer("cus_keep", serde_json::json!({}))))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/customers/cus_dup"))
+            .respond_with(ok(serde_json::json!({
+                "id": "cus_dup",
+                "deleted": true,
+                "livemode": false
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let dry_run = client
+            .customers()
+            .merge("cus_keep", "cus_dup")
+            .dry_run(true)
+            .run()
+            .await
+            .unwrap();
+        assert!(matches!(dry_run.cards[0].step, MergeStep::Unnecessary));
+        assert!(matches!(dry_run.cards[1].step, MergeStep::Blocked(_)));
+        assert!(!dry_run.is_complete());
+
+        let report = client
+            .customers()
+            .merge("cus_keep", "cus_dup")
+            .card_token("car_b", "tok_b")
+            .run()
+            .await
+            .unwrap();
+        assert!(matches!(&report.cards[1].step, MergeStep::Done(card) if card.id == "car_new"));
+        assert_eq!(report.metadata_added.get("crm").map(String::as_str), Some("42"));
+        assert_eq!(report.metadata_conflicts, ["plan"]);
+        assert!(matches!(report.delete, MergeStep::Done(())));
+        assert!(report.is_complete());
+    }
+
+    /// Mount `cus_keep` and `cus_dup`, the latter with `cards` and `subscriptions`,
+    /// and expect no subscription to be created.
+    #[cfg(feature = "subscriptions")]
+    async fn mount_unmovable(
+        server: &MockServer,
+        cards: Vec<serde_json::Value>,
+        subscriptions: Vec<serde_json::Value>,
+    ) -> PayjpClient {
+        let ok = |body| ResponseTemplate::new(200).set_body_json(body);
+        let keep = customer("cus_keep", serde_json::json!({}));
+        mount(server, "GET", "/customers/cus_keep", ok(keep)).await;
+        mount(
+            server,
+            "GET",
+            "/customers/cus_dup",
+            ok(customer("cus_dup", serde_json::json!({"crm": "42"}))),
+        )
+        .await;
+        mount(server, "GET", "/customers/cus_keep/cards", list(vec![])).await;
+        mount(server, "GET", "/customers/cus_dup/cards", list(cards)).await;
+        mount(server, "GET", "/customers/cus_dup/subscriptions", list(subscriptions)).await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions"))
+            .respond_with(ok(fixtures::subscription("sub_new", "active")))
+            .expect(0)
+            .mount(server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/customers/cus_dup"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        PayjpClient::with_options("sk_test_xxxxx", options).expect("Failed to create client")
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[tokio::test]
+    async fn test_merge_moves_nothing_else_while_a_card_is_blocked() {
+        let server = MockServer::start().await;
+        let client = mount_unmovable(
+            &server,
+            vec![card("car_b", "fp_b")],
+            vec![fixtures::subscription("sub_1", "active")],
+        )
+        .await;
+        Mock::given(method("POST"))
+            .and(path("/customers/cus_keep"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let report = client.customers().merge("cus_keep", "cus_dup").run().await.unwrap();
+        assert!(matches!(report.cards[0].step, MergeStep::Blocked(_)));
+        assert!(matches!(report.subscriptions[0].step, MergeStep::Blocked(_)));
+        assert!(matches!(report.metadata, MergeStep::Blocked(_)));
+        assert!(matches!(report.delete, MergeStep::Blocked(_)));
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[tokio::test]
+    async fn test_merge_blocks_trial_without_end() {
+        let server = MockServer::start().await;
+        let client =
+            mount_unmovable(&server, vec![], vec![fixtures::subscription("sub_1", "trial")]).await;
+        Mock::given(method("POST"))
+            .and(path("/customers/cus_keep"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(customer(
+                "cus_keep",
+                serde_json::json!({"crm": "42"}),
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let report = client.customers().merge("cus_keep", "cus_dup").run().await.unwrap();
+        assert!(matches!(report.subscriptions[0].step, MergeStep::Blocked(_)));
+        assert!(matches!(report.metadata, MergeStep::Done(())));
+        assert!(!report.is_complete());
+    }
+}
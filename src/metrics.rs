@@ -0,0 +1,132 @@
+//! Per-endpoint request metrics.
+//!
+//! Register a [`MetricsSink`] with [`ClientOptions::metrics`] to receive the
+//! method, normalized path template, status and duration of every HTTP request
+//! the client sends, including retries and failover attempts. The sink is also
+//! told about every rate limit the client runs into, see
+//! [`MetricsSink::rate_limited`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::metrics::{MetricsSink, RequestMetrics};
+//! use payjp::{ClientOptions, PayjpClient};
+//!
+//! #[derive(Debug)]
+//! struct LogMetrics;
+//!
+//! impl MetricsSink for LogMetrics {
+//!     fn record(&self, metrics: &RequestMetrics) {
+//!         // e.g. "GET /charges/{id} 200 87ms"
+//!         println!(
+//!             "{} {} {:?} {}ms",
+//!             metrics.method,
+//!             metrics.path_template,
+//!             metrics.status,
+//!             metrics.duration.as_millis()
+//!         );
+//!     }
+//! }
+//!
+//! let options = ClientOptions::new().metrics(LogMetrics);
+//! let client = PayjpClient::with_options("sk_test_xxxxx", options)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`ClientOptions::metrics`]: crate::ClientOptions::metrics
+
+use std::fmt;
+use std::time::Duration;
+
+/// Timing data for a single HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// HTTP method (e.g. "GET").
+    pub method: String,
+
+    /// Request path with object IDs replaced by `{id}` (e.g. "/customers/{id}/cards").
+    pub path_template: String,
+
+    /// HTTP status code (absent if no response was received).
+    pub status: Option<u16>,
+
+    /// Time from sending the request until the response body was read.
+    pub duration: Duration,
+}
+
+/// What held a request back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RateLimitSource {
+    /// PAY.JP responded with 429 Too Many Requests.
+    Server,
+    /// A list stream delayed a page request to honour its
+    /// [`page_delay`](crate::ListStream::page_delay).
+    Pacing,
+}
+
+/// A request that was rate limited or delayed to stay under the rate limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitEvent {
+    /// HTTP method (e.g. "POST").
+    pub method: String,
+
+    /// Request path with object IDs replaced by `{id}` (e.g. "/charges/{id}").
+    pub path_template: String,
+
+    /// Whether the limit was hit on the server or applied by the client.
+    pub source: RateLimitSource,
+
+    /// How long the request waits before it is sent (again). Zero when a
+    /// rate-limited request is not retried.
+    pub wait: Duration,
+}
+
+/// Receives [`RequestMetrics`] for every request the client sends.
+///
+/// `record` is called on the request path, so implementations should be cheap,
+/// e.g. updating a histogram.
+pub trait MetricsSink: fmt::Debug + Send + Sync {
+    /// Record the metrics of a completed request.
+    fn record(&self, metrics: &RequestMetrics);
+
+    /// Record a rate limit hit, e.g. to size batch jobs from how often and how
+    /// long they are throttled. Called before the wait starts; does nothing by
+    /// default.
+    fn rate_limited(&self, event: &RateLimitEvent) {
+        let _ = event;
+    }
+}
+
+/// Normalize an API path into a low-cardinality template.
+///
+/// PAY.JP paths alternate between resource names and object IDs
+/// (`/customers/{id}/cards/{id}`), so every second segment is replaced with `{id}`.
+pub fn path_template(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .enumerate()
+        .fold(String::new(), |mut template, (i, segment)| {
+            template.push('/');
+            template.push_str(if i % 2 == 1 { "{id}" } else { segment });
+            template
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_template() {
+        assert_eq!(path_template("/charges"), "/charges");
+        assert_eq!(path_template("/charges/ch_xxx/refund"), "/charges/{id}/refund");
+        assert_eq!(
+            path_template("/customers/cus_xxx/cards/car_xxx"),
+            "/customers/{id}/cards/{id}"
+        );
+        assert_eq!(path_template("/account"), "/account");
+        assert_eq!(path_template("/events?limit=10"), "/events");
+    }
+}
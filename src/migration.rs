@@ -0,0 +1,283 @@
+//! Moving subscriptions from one plan to another.
+//!
+//! [`SubscriptionService::migrate_plan`] lists every subscription on a plan and
+//! updates it to another, one at a time, and reports the outcome for each
+//! subscription. Run it with [`MigrationOptions::dry_run`] first to see which
+//! subscriptions would be changed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{MigrationOptions, PayjpClient};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let options = MigrationOptions::new()
+//!     .prorate(false)
+//!     .delay(Duration::from_millis(200));
+//! let report = client
+//!     .subscriptions()
+//!     .migrate_plan("pln_old", "pln_new", options)
+//!     .await?;
+//! println!("{} migrated", report.migrated().count());
+//! for result in report.failed() {
+//!     eprintln!("{} was not migrated: {:?}", result.subscription_id, result.outcome);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SubscriptionService::migrate_plan`]: crate::SubscriptionService::migrate_plan
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::resources::{
+    ListSubscriptionParams, Subscription, SubscriptionStatus, UpdateSubscriptionParams,
+};
+use futures::TryStreamExt;
+use std::time::Duration;
+
+/// Options for [`SubscriptionService::migrate_plan`](crate::SubscriptionService::migrate_plan).
+#[derive(Debug, Clone, Default)]
+pub struct MigrationOptions {
+    /// Whether to prorate the plan change (API default when `None`).
+    pub prorate: Option<bool>,
+
+    /// Only list the subscriptions that would be migrated, without updating them.
+    pub dry_run: bool,
+
+    /// Pause between subscription updates.
+    pub delay: Duration,
+}
+
+impl MigrationOptions {
+    /// Create migration options with the API's proration default and no delay.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether to prorate the plan change.
+    pub fn prorate(mut self, prorate: bool) -> Self {
+        self.prorate = Some(prorate);
+        self
+    }
+
+    /// Set whether to only report the subscriptions that would be migrated.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the pause between subscription updates, to leave room in the
+    /// account's rate limit for other requests.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// What happened to one subscription during a migration.
+#[derive(Debug)]
+pub enum MigrationOutcome {
+    /// The subscription was moved to the new plan.
+    Migrated(Box<Subscription>),
+
+    /// The subscription would have been moved (dry run).
+    DryRun,
+
+    /// Updating the subscription failed; it is still on the old plan.
+    Failed(PayjpError),
+}
+
+/// Result of migrating one subscription.
+#[derive(Debug)]
+pub struct MigrationResult {
+    /// Subscription ID.
+    pub subscription_id: String,
+
+    /// Customer ID of the subscription.
+    pub customer: String,
+
+    /// Status of the subscription before the migration.
+    pub status: SubscriptionStatus,
+
+    /// What happened to the subscription.
+    pub outcome: MigrationOutcome,
+}
+
+/// Per-subscription results of a migration, in the order subscriptions were listed.
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    /// One result for each subscription that was on the old plan.
+    pub results: Vec<MigrationResult>,
+}
+
+impl MigrationReport {
+    /// Subscriptions that were moved to the new plan.
+    pub fn migrated(&self) -> impl Iterator<Item = &MigrationResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, MigrationOutcome::Migrated(_)))
+    }
+
+    /// Subscriptions whose update failed.
+    pub fn failed(&self) -> impl Iterator<Item = &MigrationResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, MigrationOutcome::Failed(_)))
+    }
+
+    /// Whether no subscription update failed.
+    pub fn is_complete(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+/// Move all subscriptions on `from_plan` that are not canceled to `to_plan`.
+///
+/// Subscriptions are listed in full before the first update, since updated
+/// subscriptions drop out of the plan filter and would otherwise shift the
+/// offsets of later pages.
+pub(crate) async fn migrate_plan(
+    client: &PayjpClient,
+    from_plan: &str,
+    to_plan: &str,
+    options: MigrationOptions,
+) -> PayjpResult<MigrationReport> {
+    if from_plan == to_plan {
+        return Err(PayjpError::InvalidRequest(format!(
+            "Cannot migrate subscriptions from plan {} to itself",
+            from_plan
+        )));
+    }
+    // Fail before touching any subscription if the new plan does not exist
+    client.plans().retrieve(to_plan).await?;
+
+    let params = ListSubscriptionParams::new().max_limit().plan(from_plan);
+    let subscriptions: Vec<Subscription> = client
+        .subscriptions()
+        .list_all(params)
+        .filter_local(|subscription| {
+            subscription.plan.id == from_plan
+                && subscription.status != SubscriptionStatus::Canceled
+        })
+        .try_collect()
+        .await?;
+
+    let mut report = MigrationReport::default();
+    for (i, subscription) in subscriptions.into_iter().enumerate() {
+        let outcome = if options.dry_run {
+            MigrationOutcome::DryRun
+        } else {
+            if i > 0 && !options.delay.is_zero() {
+                tokio::time::sleep(options.delay).await;
+            }
+            let mut params = UpdateSubscriptionParams::new().plan(to_plan);
+            if let Some(prorate) = options.prorate {
+                params = params.prorate(prorate);
+            }
+            match client.subscriptions().update(&subscription.id, params).await {
+                Ok(updated) => MigrationOutcome::Migrated(Box::new(updated)),
+                Err(e) => MigrationOutcome::Failed(e),
+            }
+        };
+        report.results.push(MigrationResult {
+            subscription_id: subscription.id,
+            customer: subscription.customer,
+            status: subscription.status,
+            outcome,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, plan};
+    use crate::ClientOptions;
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn subscription(id: &str, plan_id: &str, status: &str) -> serde_json::Value {
+        let fields = serde_json::json!({ "plan": plan(plan_id) });
+        fixtures::with(fixtures::subscription(id, status), fields)
+    }
+
+    #[tokio::test]
+    async fn test_migrate_plan_reports_each_subscription() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plans/pln_new"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(plan("pln_new")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/subscriptions"))
+            .and(query_param("plan", "pln_old"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    subscription("sub_1", "pln_old", "active"),
+                    subscription("sub_2", "pln_old", "canceled"),
+                    subscription("sub_3", "pln_old", "trial"),
+                ],
+                "has_more": false,
+                "url": "/v1/subscriptions",
+                "count": 3
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions/sub_1"))
+            .and(body_string_contains("plan=pln_new"))
+            .and(body_string_contains("prorate=false"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(subscription("sub_1", "pln_new", "active")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions/sub_3"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": {
+                    "status": 400,
+                    "type": "client_error",
+                    "message": "Cannot change plan"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let dry_run = client
+            .subscriptions()
+            .migrate_plan("pln_old", "pln_new", MigrationOptions::new().dry_run(true))
+            .await
+            .unwrap();
+        assert_eq!(dry_run.results.len(), 2);
+        assert!(dry_run
+            .results
+            .iter()
+            .all(|result| matches!(result.outcome, MigrationOutcome::DryRun)));
+
+        let report = client
+            .subscriptions()
+            .migrate_plan("pln_old", "pln_new", MigrationOptions::new().prorate(false))
+            .await
+            .unwrap();
+        let migrated: Vec<_> = report.migrated().map(|r| r.subscription_id.as_str()).collect();
+        let failed: Vec<_> = report.failed().map(|r| r.subscription_id.as_str()).collect();
+        assert_eq!(migrated, ["sub_1"]);
+        assert_eq!(failed, ["sub_3"]);
+        assert!(!report.is_complete());
+    }
+}
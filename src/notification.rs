@@ -0,0 +1,239 @@
+//! Business-level view of webhook events.
+//!
+//! [`Notification`] maps the raw [`Event`] types that most applications act on to
+//! variants carrying the typed resource, so webhook handlers can match on what
+//! happened instead of on event type strings and untyped JSON.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{Event, Notification};
+//!
+//! fn handle_webhook(event: Event) -> payjp::PayjpResult<()> {
+//!     match Notification::try_from(event)? {
+//!         Notification::PaymentSucceeded { charge } => {
+//!             println!("paid: {} ({} {})", charge.id, charge.amount, charge.currency);
+//!         }
+//!         Notification::PaymentFailed { charge, code } => {
+//!             println!("payment {} failed: {:?}", charge.id, code);
+//!         }
+//!         Notification::SubscriptionCanceled { subscription } => {
+//!             println!("subscription {} canceled", subscription.id);
+//!         }
+//!         _ => {}
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::error::{PayjpError, PayjpResult};
+#[cfg(feature = "subscriptions")]
+use crate::resources::Subscription;
+use crate::resources::{Card, Charge, Customer, Event, EventType, FailureCode, Transfer};
+use serde::de::DeserializeOwned;
+
+/// A webhook event, classified by its business meaning.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    /// A charge was paid (`charge.succeeded`).
+    PaymentSucceeded {
+        /// The paid charge.
+        charge: Charge,
+    },
+
+    /// A charge attempt failed (`charge.failed`).
+    PaymentFailed {
+        /// The failed charge.
+        charge: Charge,
+
+        /// Failure code reported by PAY.JP (optional).
+        code: Option<FailureCode>,
+    },
+
+    /// An authorized charge was captured (`charge.captured`).
+    PaymentCaptured {
+        /// The captured charge.
+        charge: Charge,
+    },
+
+    /// A charge was fully or partially refunded (`charge.refunded`).
+    PaymentRefunded {
+        /// The refunded charge.
+        charge: Charge,
+    },
+
+    /// A customer was created (`customer.created`).
+    CustomerCreated {
+        /// The new customer.
+        customer: Customer,
+    },
+
+    /// A card was added to a customer (`customer.card.created`).
+    CardAdded {
+        /// The new card.
+        card: Card,
+    },
+
+    #[cfg(feature = "subscriptions")]
+    /// A subscription was created (`subscription.created`).
+    SubscriptionStarted {
+        /// The new subscription.
+        subscription: Subscription,
+    },
+
+    #[cfg(feature = "subscriptions")]
+    /// A subscription was renewed for a new billing period (`subscription.renewed`).
+    SubscriptionRenewed {
+        /// The renewed subscription.
+        subscription: Subscription,
+    },
+
+    #[cfg(feature = "subscriptions")]
+    /// A subscription was paused (`subscription.paused`).
+    SubscriptionPaused {
+        /// The paused subscription.
+        subscription: Subscription,
+    },
+
+    #[cfg(feature = "subscriptions")]
+    /// A paused or canceled subscription was resumed (`subscription.resumed`).
+    SubscriptionResumed {
+        /// The resumed subscription.
+        subscription: Subscription,
+    },
+
+    #[cfg(feature = "subscriptions")]
+    /// A subscription was canceled (`subscription.canceled`).
+    SubscriptionCanceled {
+        /// The canceled subscription.
+        subscription: Subscription,
+    },
+
+    /// A payout to the merchant's bank account was created (`transfer.created`).
+    PayoutCreated {
+        /// The payout.
+        transfer: Transfer,
+    },
+}
+
+impl TryFrom<Event> for Notification {
+    type Error = PayjpError;
+
+    /// Classify an event.
+    ///
+    /// Fails with `InvalidRequest` for event types without a notification, and
+    /// with `Serialization` if the event data does not match the expected resource.
+    fn try_from(event: Event) -> PayjpResult<Self> {
+        let notification = match event.event_type {
+            EventType::ChargeSucceeded => Notification::PaymentSucceeded {
+                charge: resource(event)?,
+            },
+            EventType::ChargeFailed => {
+                let charge: Charge = resource(event)?;
+                Notification::PaymentFailed {
+                    code: charge.failure_code.as_deref().map(FailureCode::from),
+                    charge,
+                }
+            }
+            EventType::ChargeCaptured => Notification::PaymentCaptured {
+                charge: resource(event)?,
+            },
+            EventType::ChargeRefunded => Notification::PaymentRefunded {
+                charge: resource(event)?,
+            },
+            EventType::CustomerCreated => Notification::CustomerCreated {
+                customer: resource(event)?,
+            },
+            EventType::CustomerCardCreated => Notification::CardAdded {
+                card: resource(event)?,
+            },
+            #[cfg(feature = "subscriptions")]
+            EventType::SubscriptionCreated => Notification::SubscriptionStarted {
+                subscription: resource(event)?,
+            },
+            #[cfg(feature = "subscriptions")]
+            EventType::SubscriptionRenewed => Notification::SubscriptionRenewed {
+                subscription: resource(event)?,
+            },
+            #[cfg(feature = "subscriptions")]
+            EventType::SubscriptionPaused => Notification::SubscriptionPaused {
+                subscription: resource(event)?,
+            },
+            #[cfg(feature = "subscriptions")]
+            EventType::SubscriptionResumed => Notification::SubscriptionResumed {
+                subscription: resource(event)?,
+            },
+            #[cfg(feature = "subscriptions")]
+            EventType::SubscriptionCanceled => Notification::SubscriptionCanceled {
+                subscription: resource(event)?,
+            },
+            EventType::TransferCreated => Notification::PayoutCreated {
+                transfer: resource(event)?,
+            },
+            other => {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "Event {} of type {} has no notification",
+                    event.id, other
+                )))
+            }
+        };
+        Ok(notification)
+    }
+}
+
+/// Deserialize the resource carried by an event.
+fn resource<T: DeserializeOwned>(event: Event) -> PayjpResult<T> {
+    Ok(serde_json::from_value(event.data.object)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str, object: serde_json::Value) -> Event {
+        serde_json::from_value(serde_json::json!({
+            "id": "evnt_1",
+            "object": "event",
+            "livemode": false,
+            "created": 1700000000,
+            "type": event_type,
+            "data": { "object": object }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_try_from_event() {
+        let charge = serde_json::json!({
+            "id": "ch_1",
+            "object": "charge",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "paid": false,
+            "captured": false,
+            "refunded": false,
+            "amount_refunded": 0,
+            "failure_code": "card_declined"
+        });
+
+        match Notification::try_from(event("charge.failed", charge.clone())) {
+            Ok(Notification::PaymentFailed { charge, code }) => {
+                assert_eq!(charge.id, "ch_1");
+                assert_eq!(code, Some(FailureCode::CardDeclined));
+            }
+            other => panic!("Expected PaymentFailed, got {:?}", other),
+        }
+
+        assert!(matches!(
+            Notification::try_from(event("charge.updated", charge.clone())),
+            Err(PayjpError::InvalidRequest(_))
+        ));
+        #[cfg(feature = "subscriptions")]
+        assert!(matches!(
+            Notification::try_from(event("subscription.canceled", charge)),
+            Err(PayjpError::Serialization(_))
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//! W3C trace context propagation from the current `tracing` span.
+
+use opentelemetry::trace::{SpanContext, TraceContextExt};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Header carrying the trace ID, parent span ID and trace flags.
+pub(crate) const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Header carrying vendor-specific trace state.
+pub(crate) const TRACESTATE_HEADER: &str = "tracestate";
+
+/// Trace context headers for the current span, if it is part of a valid trace.
+pub(crate) fn current_headers() -> Vec<(&'static str, String)> {
+    let context = tracing::Span::current().context();
+    headers(context.span().span_context())
+}
+
+fn headers(span_context: &SpanContext) -> Vec<(&'static str, String)> {
+    if !span_context.is_valid() {
+        return Vec::new();
+    }
+    let mut headers = vec![(
+        TRACEPARENT_HEADER,
+        format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        ),
+    )];
+    let trace_state = span_context.trace_state().header();
+    if !trace_state.is_empty() {
+        headers.push((TRACESTATE_HEADER, trace_state));
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanId, TraceFlags, TraceId, TraceState};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_headers_from_span_context() {
+        let span_context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::from_str("rojo=00f067aa0ba902b7").unwrap(),
+        );
+        assert_eq!(
+            headers(&span_context),
+            [
+                (
+                    TRACEPARENT_HEADER,
+                    "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string()
+                ),
+                (TRACESTATE_HEADER, "rojo=00f067aa0ba902b7".to_string()),
+            ]
+        );
+
+        assert!(headers(&SpanContext::empty_context()).is_empty());
+    }
+}
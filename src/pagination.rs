@@ -0,0 +1,879 @@
+//! Auto-pagination streams for list endpoints.
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::metrics::RateLimitSource;
+use crate::params::{ListParams, Metadata, MAX_LIMIT};
+use crate::resources::{self, charge::ListChargeParams, event::ListEventParams};
+use crate::response::ListResponse;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, FuturesOrdered, Stream, StreamExt};
+use futures::TryStreamExt;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::ops::RangeBounds;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// List parameters that support offset-based pagination.
+pub(crate) trait PageParams: Serialize + Clone + Send + Sync {
+    /// Get the page size limit.
+    fn page_limit(&self) -> Option<i64>;
+
+    /// Get the current offset.
+    fn page_offset(&self) -> Option<i64>;
+
+    /// Set the offset for the next page.
+    fn set_page_offset(&mut self, offset: i64);
+
+    /// Check `limit` and `offset` against the API's bounds before sending.
+    fn validate(&self) -> PayjpResult<()> {
+        if let Some(limit) = self.page_limit() {
+            if !(1..=MAX_LIMIT).contains(&limit) {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "limit must be between 1 and {}, got {}",
+                    MAX_LIMIT, limit
+                )));
+            }
+        }
+        if let Some(offset) = self.page_offset() {
+            if offset < 0 {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "offset must not be negative, got {}",
+                    offset
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_page_params {
+    ($($ty:ty),* $(,)?) => {
+        $(impl PageParams for $ty {
+            fn page_limit(&self) -> Option<i64> {
+                self.limit
+            }
+
+            fn page_offset(&self) -> Option<i64> {
+                self.offset
+            }
+
+            fn set_page_offset(&mut self, offset: i64) {
+                self.offset = Some(offset);
+            }
+        })*
+    };
+}
+
+impl_page_params!(
+    ListParams,
+    ListChargeParams,
+    ListEventParams,
+);
+
+#[cfg(feature = "statements")]
+impl_page_params!(resources::statement::ListStatementParams);
+
+#[cfg(feature = "subscriptions")]
+impl_page_params!(resources::subscription::ListSubscriptionParams);
+
+/// Query parameters restored from a [`ListCursor`].
+#[derive(Clone)]
+struct CursorParams {
+    query: Vec<(String, String)>,
+    offset: i64,
+}
+
+impl Serialize for CursorParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.query.len() + 1))?;
+        for pair in &self.query {
+            seq.serialize_element(pair)?;
+        }
+        seq.serialize_element(&("offset", self.offset))?;
+        seq.end()
+    }
+}
+
+impl PageParams for CursorParams {
+    fn page_limit(&self) -> Option<i64> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == "limit")
+            .and_then(|(_, value)| value.parse().ok())
+    }
+
+    fn page_offset(&self) -> Option<i64> {
+        Some(self.offset)
+    }
+
+    fn set_page_offset(&mut self, offset: i64) {
+        self.offset = offset;
+    }
+}
+
+/// A serializable checkpoint of a [`ListStream`]'s position.
+///
+/// Take one with [`ListStream::cursor`] after processing each item (or batch),
+/// persist it, and pass it to [`ListStream::resume`] after a restart to continue
+/// where the previous run stopped instead of from the first page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListCursor {
+    /// Path of the list endpoint (e.g., `"/charges"`).
+    pub path: String,
+
+    /// Query parameters other than `offset`, such as `limit` and filters.
+    pub params: Vec<(String, String)>,
+
+    /// Offset of the next item to yield.
+    pub offset: i64,
+
+    /// ID of the last item yielded (optional).
+    ///
+    /// PAY.JP lists are newest first, so records created while a job is stopped
+    /// shift older ones to higher offsets. On resume, items up to and including
+    /// this one are skipped so they are not yielded twice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_id: Option<String>,
+}
+
+/// Resources that carry an ID.
+pub trait HasId {
+    /// Get the resource ID (e.g., `"ch_xxxxx"`).
+    fn id(&self) -> &str;
+}
+
+/// Resources that carry a metadata map.
+pub trait HasMetadata {
+    /// Get the resource's metadata.
+    fn metadata(&self) -> Option<&Metadata>;
+}
+
+/// Resources that carry an amount.
+pub trait HasAmount {
+    /// Get the resource's amount in the smallest currency unit.
+    fn amount(&self) -> i64;
+}
+
+/// Resources that carry a creation timestamp.
+pub trait HasCreated {
+    /// Get the resource's creation timestamp (Unix timestamp).
+    fn created(&self) -> i64;
+}
+
+macro_rules! impl_has_id {
+    ($($ty:ty),* $(,)?) => {
+        $(impl HasId for $ty {
+            fn id(&self) -> &str {
+                &self.id
+            }
+        })*
+    };
+}
+
+macro_rules! impl_has_metadata {
+    ($($ty:ty),* $(,)?) => {
+        $(impl HasMetadata for $ty {
+            fn metadata(&self) -> Option<&Metadata> {
+                self.metadata.as_ref()
+            }
+        })*
+    };
+}
+
+macro_rules! impl_has_amount {
+    ($($ty:ty),* $(,)?) => {
+        $(impl HasAmount for $ty {
+            fn amount(&self) -> i64 {
+                self.amount
+            }
+        })*
+    };
+}
+
+macro_rules! impl_has_created {
+    ($($ty:ty),* $(,)?) => {
+        $(impl HasCreated for $ty {
+            fn created(&self) -> i64 {
+                self.created
+            }
+        })*
+    };
+}
+
+impl_has_id!(
+    resources::Account,
+    resources::Card,
+    resources::Charge,
+    resources::Customer,
+    resources::Event,
+    resources::ThreeDSecureRequest,
+    resources::Token,
+    resources::Transfer,
+);
+
+impl_has_metadata!(
+    resources::Account,
+    resources::Card,
+    resources::Charge,
+    resources::Customer,
+);
+
+impl_has_amount!(resources::Charge, resources::Transfer);
+
+impl_has_created!(
+    resources::Account,
+    resources::Card,
+    resources::Charge,
+    resources::Customer,
+    resources::Event,
+    resources::ThreeDSecureRequest,
+    resources::Token,
+    resources::Transfer,
+);
+
+#[cfg(feature = "subscriptions")]
+impl_has_id!(resources::Plan, resources::Subscription);
+#[cfg(feature = "subscriptions")]
+impl_has_metadata!(resources::Plan, resources::Subscription);
+#[cfg(feature = "subscriptions")]
+impl_has_amount!(resources::Plan);
+#[cfg(feature = "subscriptions")]
+impl_has_created!(resources::Plan, resources::Subscription);
+
+#[cfg(feature = "statements")]
+impl_has_id!(resources::Balance, resources::Statement, resources::Term);
+#[cfg(feature = "statements")]
+impl_has_created!(resources::Balance, resources::Statement);
+
+#[cfg(feature = "platform")]
+impl_has_id!(resources::platform::Tenant, resources::platform::TenantTransfer);
+#[cfg(feature = "platform")]
+impl_has_metadata!(resources::platform::Tenant);
+#[cfg(feature = "platform")]
+impl_has_amount!(resources::platform::TenantTransfer);
+#[cfg(feature = "platform")]
+impl_has_created!(resources::platform::Tenant, resources::platform::TenantTransfer);
+
+/// A stream of resources that transparently fetches further pages as it is consumed.
+///
+/// Items are yielded in the order returned by the API. If a page request fails, the
+/// error is yielded and the stream ends.
+///
+/// # Example
+///
+/// ```no_run
+/// # use payjp::{PayjpClient, ListChargeParams};
+/// use futures::TryStreamExt;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = PayjpClient::new("sk_test_xxxxx")?;
+/// let charges: Vec<_> = client
+///     .charges()
+///     .list_all(ListChargeParams::new().limit(100))
+///     .filter_metadata("order_id", "1234")
+///     .amount_between(1000, 5000)
+///     .try_collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ListStream<'a, T> {
+    inner: BoxStream<'a, PayjpResult<T>>,
+    /// Shared with the paging state so it can be set after adapters have been
+    /// applied.
+    pacing: Arc<Pacing>,
+    /// Position after the last item yielded by the paging state.
+    cursor: Arc<Mutex<ListCursor>>,
+}
+
+/// Page request pacing of a [`ListStream`].
+struct Pacing {
+    /// Minimum time between page request starts, in nanoseconds.
+    page_delay: AtomicU64,
+    /// Maximum number of page requests in flight.
+    prefetch: AtomicUsize,
+}
+
+/// Page size the API uses when `limit` is not set.
+const DEFAULT_PAGE_LIMIT: i64 = 10;
+
+impl<'a, T: Send + 'a> ListStream<'a, T> {
+    /// Create a stream that pages through `path` starting at the params' offset.
+    pub(crate) fn new<P>(client: &'a PayjpClient, path: String, params: P) -> Self
+    where
+        T: DeserializeOwned + HasId,
+        P: PageParams + 'a,
+    {
+        let query = serde_urlencoded::to_string(&params)
+            .ok()
+            .and_then(|query| serde_urlencoded::from_str::<Vec<(String, String)>>(&query).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| key != "offset")
+            .collect();
+        let cursor = ListCursor {
+            path,
+            params: query,
+            offset: params.page_offset().unwrap_or(0),
+            last_id: None,
+        };
+        Self::paginate(client, params, cursor, None)
+    }
+
+    /// Resume a stream from a cursor saved with [`cursor`](Self::cursor).
+    ///
+    /// The item type must match the stream the cursor was taken from.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{Charge, ListCursor, ListStream, PayjpClient};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example(saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let cursor: ListCursor = serde_json::from_str(saved)?;
+    /// let mut charges = ListStream::<Charge>::resume(&client, cursor);
+    /// while let Some(charge) = charges.try_next().await? {
+    ///     println!("{}", charge.id);
+    ///     let checkpoint = serde_json::to_string(&charges.cursor())?;
+    ///     // persist `checkpoint`
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume(client: &'a PayjpClient, cursor: ListCursor) -> Self
+    where
+        T: DeserializeOwned + HasId,
+    {
+        // Refetch the last seen item so a shift in offsets can be detected.
+        let resume_after = cursor.last_id.clone().filter(|_| cursor.offset > 0);
+        let offset = cursor.offset - i64::from(resume_after.is_some());
+        let params = CursorParams {
+            query: cursor.params.clone(),
+            offset,
+        };
+        Self::paginate(client, params, ListCursor { offset, ..cursor }, resume_after)
+    }
+
+    fn paginate<P>(
+        client: &'a PayjpClient,
+        params: P,
+        cursor: ListCursor,
+        resume_after: Option<String>,
+    ) -> Self
+    where
+        T: DeserializeOwned + HasId,
+        P: PageParams + 'a,
+    {
+        type PageResult<T> = (i64, PayjpResult<ListResponse<T>>);
+
+        struct State<'a, T, P> {
+            client: &'a PayjpClient,
+            path: String,
+            params: P,
+            buffer: VecDeque<T>,
+            in_flight: FuturesOrdered<BoxFuture<'a, PageResult<T>>>,
+            done: bool,
+            last_start: Option<Instant>,
+            pacing: Arc<Pacing>,
+            cursor: Arc<Mutex<ListCursor>>,
+            resume_after: Option<String>,
+        }
+
+        let pacing = Arc::new(Pacing {
+            page_delay: AtomicU64::new(0),
+            prefetch: AtomicUsize::new(1),
+        });
+        let path = cursor.path.clone();
+        let cursor = Arc::new(Mutex::new(cursor));
+        let state = State {
+            client,
+            path,
+            params,
+            buffer: VecDeque::<T>::new(),
+            in_flight: FuturesOrdered::new(),
+            done: false,
+            last_start: None,
+            pacing: pacing.clone(),
+            cursor: cursor.clone(),
+            resume_after,
+        };
+
+        let inner = stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.buffer.pop_front() {
+                    let mut cursor = state.cursor.lock().unwrap_or_else(|e| e.into_inner());
+                    cursor.offset += 1;
+                    cursor.last_id = Some(item.id().to_string());
+                    drop(cursor);
+                    return Some((Ok(item), state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                // Request pages ahead at consecutive offsets, spacing their starts
+                // by the page delay.
+                let prefetch = state.pacing.prefetch.load(Ordering::Relaxed).max(1);
+                let delay = Duration::from_nanos(state.pacing.page_delay.load(Ordering::Relaxed));
+                while state.in_flight.len() < prefetch {
+                    let now = Instant::now();
+                    let at = state.last_start.map_or(now, |last| (last + delay).max(now));
+                    state.last_start = Some(at);
+
+                    let start = state.params.page_offset().unwrap_or(0);
+                    let limit = state.params.page_limit().unwrap_or(DEFAULT_PAGE_LIMIT);
+                    let (client, path, params) =
+                        (state.client, state.path.clone(), state.params.clone());
+                    state.in_flight.push_back(Box::pin(async move {
+                        let wait = at.saturating_duration_since(Instant::now());
+                        if !wait.is_zero() {
+                            client.record_rate_limit(
+                                &Method::GET,
+                                &path,
+                                RateLimitSource::Pacing,
+                                wait,
+                            );
+                        }
+                        tokio::time::sleep_until(at).await;
+                        (start, client.get_list(&path, &params).await)
+                    }));
+                    state.params.set_page_offset(start + limit);
+                }
+
+                let (start, result) = state.in_flight.next().await.expect("page requested");
+                let page = match result {
+                    Ok(page) => page,
+                    Err(e) => {
+                        state.done = true;
+                        state.in_flight = FuturesOrdered::new();
+                        return Some((Err(e), state));
+                    }
+                };
+
+                let fetched = page.data.len() as i64;
+                state.done = !page.has_more || fetched == 0;
+                if state.done {
+                    // Pages requested past the end are not needed
+                    state.in_flight = FuturesOrdered::new();
+                } else if state.in_flight.is_empty() {
+                    state.params.set_page_offset(start + fetched);
+                }
+                state.buffer.extend(page.data);
+
+                // Skip through the last item seen before resuming, or just the
+                // refetched position if it is no longer on this page.
+                let mut skipped = 0;
+                if let Some(last_id) = state.resume_after.take() {
+                    skipped = state
+                        .buffer
+                        .iter()
+                        .position(|item| item.id() == last_id)
+                        .map_or(1, |i| i + 1)
+                        .min(state.buffer.len());
+                    state.buffer.drain(..skipped);
+                }
+                state.cursor.lock().unwrap_or_else(|e| e.into_inner()).offset =
+                    start + skipped as i64;
+            }
+        });
+
+        Self {
+            inner: inner.boxed(),
+            pacing,
+            cursor,
+        }
+    }
+
+    /// A checkpoint of the stream's position, for resuming with
+    /// [`resume`](Self::resume).
+    ///
+    /// The cursor points just past the last item yielded by the underlying pages,
+    /// including items dropped by local filters.
+    pub fn cursor(&self) -> ListCursor {
+        self.cursor.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Start page requests at least `delay` apart.
+    ///
+    /// Paces background jobs such as exports and reconciliations, so they leave
+    /// room in the account's rate limit for interactive requests. For example, a
+    /// delay of 200ms caps a stream at five list requests per second, also when
+    /// pages are requested ahead with [`buffered`](Self::buffered).
+    pub fn page_delay(self, delay: Duration) -> Self {
+        let nanos = u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX);
+        self.pacing.page_delay.store(nanos, Ordering::Relaxed);
+        self
+    }
+
+    /// Keep up to `pages` page requests in flight, so the next pages are already
+    /// loading while the current one is consumed.
+    ///
+    /// Requests are made at consecutive offsets and their items are yielded in
+    /// order. Each request goes through the client's retry and backoff on rate
+    /// limiting, and starts are still spaced by [`page_delay`](Self::page_delay).
+    /// Near the end of the list, up to `pages - 1` requests may return pages that
+    /// are discarded. The default is 1, which requests one page at a time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListChargeParams};
+    /// use futures::TryStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut batches = client
+    ///     .charges()
+    ///     .list_all(ListChargeParams::new().max_limit())
+    ///     .page_delay(Duration::from_millis(100))
+    ///     .buffered(3)
+    ///     .chunks(500);
+    /// while let Some(batch) = batches.try_next().await? {
+    ///     println!("inserting {} charges", batch.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn buffered(self, pages: usize) -> Self {
+        self.pacing.prefetch.store(pages.max(1), Ordering::Relaxed);
+        self
+    }
+
+    /// Group items into batches of `size`, for example to insert rows in bulk.
+    ///
+    /// The last batch may be smaller. If a page request fails, the items collected
+    /// so far are yielded as a batch before the error. A size of 0 is treated as 1.
+    pub fn chunks(self, size: usize) -> ListStream<'a, Vec<T>> {
+        let size = size.max(1);
+        // The inner stream is dropped once it ends, as it must not be polled again.
+        let inner = stream::unfold(
+            (Some(self.inner), None),
+            move |(mut inner, pending): (Option<BoxStream<'a, _>>, Option<PayjpError>)| async move {
+                if let Some(e) = pending {
+                    return Some((Err(e), (inner, None)));
+                }
+                let mut chunk = Vec::with_capacity(size);
+                while chunk.len() < size {
+                    match inner.as_mut()?.next().await {
+                        Some(Ok(item)) => chunk.push(item),
+                        Some(Err(e)) if chunk.is_empty() => return Some((Err(e), (inner, None))),
+                        Some(Err(e)) => return Some((Ok(chunk), (inner, Some(e)))),
+                        None => {
+                            inner = None;
+                            break;
+                        }
+                    }
+                }
+                (!chunk.is_empty()).then_some((Ok(chunk), (inner, None)))
+            },
+        );
+
+        ListStream {
+            inner: inner.boxed(),
+            pacing: self.pacing,
+            cursor: self.cursor,
+        }
+    }
+
+    /// Keep only items matching the predicate, evaluated locally while paging.
+    ///
+    /// Errors are always passed through.
+    pub fn filter_local<F>(self, mut predicate: F) -> Self
+    where
+        F: FnMut(&T) -> bool + Send + 'a,
+    {
+        let inner = self.inner.filter(move |item| {
+            let keep = match item {
+                Ok(item) => predicate(item),
+                Err(_) => true,
+            };
+            futures::future::ready(keep)
+        });
+
+        Self {
+            inner: inner.boxed(),
+            pacing: self.pacing,
+            cursor: self.cursor,
+        }
+    }
+
+    /// Map each item with an async function, dropping items it maps to `None`.
+    ///
+    /// An error from the function is yielded like a page error.
+    pub(crate) fn try_filter_map_local<U, F, Fut>(self, f: F) -> ListStream<'a, U>
+    where
+        U: Send + 'a,
+        F: FnMut(T) -> Fut + Send + 'a,
+        Fut: Future<Output = PayjpResult<Option<U>>> + Send + 'a,
+    {
+        ListStream {
+            inner: self.inner.try_filter_map(f).boxed(),
+            pacing: self.pacing,
+            cursor: self.cursor,
+        }
+    }
+
+    /// End the stream after `n` items.
+    pub(crate) fn take_local(self, n: usize) -> Self {
+        Self {
+            inner: self.inner.take(n).boxed(),
+            pacing: self.pacing,
+            cursor: self.cursor,
+        }
+    }
+
+    /// Keep only items whose metadata contains `key` with the given `value`.
+    pub fn filter_metadata(self, key: impl Into<String>, value: impl Into<String>) -> Self
+    where
+        T: HasMetadata,
+    {
+        let key = key.into();
+        let value = value.into();
+        self.filter_local(move |item| {
+            item.metadata()
+                .and_then(|metadata| metadata.get(&key))
+                .is_some_and(|v| *v == value)
+        })
+    }
+
+    /// Keep only items whose amount is between `min` and `max` (inclusive).
+    pub fn amount_between(self, min: i64, max: i64) -> Self
+    where
+        T: HasAmount,
+    {
+        self.filter_local(move |item| (min..=max).contains(&item.amount()))
+    }
+
+    /// Keep only items whose creation timestamp falls within `range`.
+    pub fn created_in<R>(self, range: R) -> Self
+    where
+        T: HasCreated,
+        R: RangeBounds<i64> + Send + 'a,
+    {
+        self.filter_local(move |item| range.contains(&item.created()))
+    }
+}
+
+impl<T> Stream for ListStream<'_, T> {
+    type Item = PayjpResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+    use crate::{Charge, ClientOptions, ListChargeParams, ListCursor, ListStream, PayjpClient};
+    use futures::TryStreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn charge(id: &str, amount: i64) -> serde_json::Value {
+        fixtures::with(fixtures::charge(id), serde_json::json!({ "amount": amount }))
+    }
+
+    fn page(data: Vec<serde_json::Value>, has_more: bool) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": has_more,
+            "url": "/v1/charges",
+            "count": 3
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_list_all_follows_pages_and_filters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("offset", "2"))
+            .respond_with(page(vec![charge("ch_3", 3000)], false))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(page(vec![charge("ch_1", 1000), charge("ch_2", 2000)], true))
+            .with_priority(2)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let charges: Vec<_> = client
+            .charges()
+            .list_all(ListChargeParams::new().limit(2))
+            .amount_between(1500, 5000)
+            .try_collect()
+            .await
+            .expect("Failed to list charges");
+
+        let ids: Vec<_> = charges.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["ch_2", "ch_3"]);
+    }
+
+    #[tokio::test]
+    async fn test_page_delay_paces_page_fetches() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("offset", "2"))
+            .respond_with(page(vec![charge("ch_3", 3000)], false))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(page(vec![charge("ch_1", 1000), charge("ch_2", 2000)], true))
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let started = std::time::Instant::now();
+        let charges: Vec<_> = client
+            .charges()
+            .list_all(ListChargeParams::new().limit(2))
+            .amount_between(0, 5000)
+            .page_delay(std::time::Duration::from_millis(200))
+            .try_collect()
+            .await
+            .expect("Failed to list charges");
+
+        assert_eq!(charges.len(), 3);
+        assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_buffered_pages_are_chunked_in_order() {
+        let server = MockServer::start().await;
+        for (offset, data, has_more) in [
+            ("2", vec![charge("ch_3", 3000), charge("ch_4", 4000)], true),
+            ("4", vec![charge("ch_5", 5000)], false),
+            ("6", vec![], false),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/charges"))
+                .and(query_param("offset", offset))
+                .respond_with(page(data, has_more))
+                .with_priority(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(page(vec![charge("ch_1", 1000), charge("ch_2", 2000)], true))
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let chunks: Vec<Vec<_>> = client
+            .charges()
+            .list_all(ListChargeParams::new().limit(2))
+            .buffered(2)
+            .chunks(2)
+            .try_collect()
+            .await
+            .expect("Failed to list charges");
+
+        let ids: Vec<Vec<_>> = chunks
+            .iter()
+            .map(|chunk| chunk.iter().map(|c| c.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, [vec!["ch_1", "ch_2"], vec!["ch_3", "ch_4"], vec!["ch_5"]]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_cursor_skips_items_already_seen() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("limit", "2"))
+            .and(query_param("offset", "2"))
+            .respond_with(page(vec![charge("ch_2", 2000), charge("ch_3", 3000)], false))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        // A charge created while stopped shifts the last seen one to offset 1
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("limit", "2"))
+            .and(query_param("offset", "0"))
+            .respond_with(page(vec![charge("ch_new", 500), charge("ch_1", 1000)], true))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .respond_with(page(vec![charge("ch_1", 1000), charge("ch_2", 2000)], true))
+            .with_priority(2)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let mut charges = client.charges().list_all(ListChargeParams::new().limit(2));
+        let first = charges.try_next().await.unwrap().unwrap();
+        assert_eq!(first.id, "ch_1");
+        let saved = serde_json::to_string(&charges.cursor()).unwrap();
+
+        let cursor: ListCursor = serde_json::from_str(&saved).unwrap();
+        assert_eq!(cursor.offset, 1);
+        assert_eq!(cursor.last_id.as_deref(), Some("ch_1"));
+        let rest: Vec<Charge> = ListStream::resume(&client, cursor)
+            .try_collect()
+            .await
+            .expect("Failed to resume");
+        let ids: Vec<_> = rest.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["ch_2", "ch_3"]);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_limit_is_rejected_before_sending() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(page(vec![], false))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let result = client
+            .charges()
+            .list(ListChargeParams::new().limit(101))
+            .await;
+        assert!(matches!(result, Err(crate::PayjpError::InvalidRequest(_))));
+    }
+}
@@ -0,0 +1,427 @@
+//! Typed fields of params structs, for mapping API error parameters back to inputs.
+//!
+//! Every enum here lists the fields of one params struct together with the form
+//! parameter each is sent as. [`ParamField::from_param`] turns the `param` of an
+//! [`ApiError`](crate::ApiError) or [`CardError`](crate::CardError) back into the
+//! field, so a form layer can highlight the offending input:
+//!
+//! ```no_run
+//! use payjp::params::{CreateTokenField, ParamField};
+//! use payjp::{CardDetails, CreateTokenParams, PayjpClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let card = CardDetails::new("4242424242424242", 13, 2030, "123");
+//!
+//! if let Err(err) = client.tokens().create(CreateTokenParams::from_card(card)).await {
+//!     match err.param_field::<CreateTokenField>() {
+//!         Some(CreateTokenField::ExpMonth) => println!("highlight the expiry month"),
+//!         Some(field) => println!("highlight {}", field.name()),
+//!         None => println!("{}", err),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+/// A field of a params struct, named after the form parameter it is sent as.
+pub trait ParamField: Sized + Copy + 'static {
+    /// Every field, in declaration order.
+    const ALL: &'static [Self];
+
+    /// Form parameter name sent to the API (e.g., `card[exp_month]`).
+    fn name(self) -> &'static str;
+
+    /// The field an API error `param` refers to.
+    ///
+    /// Nested names match with or without their wrapper (`card[exp_month]` and
+    /// `exp_month`), and any `metadata[key]` matches the metadata field.
+    fn from_param(param: &str) -> Option<Self> {
+        let param = base_name(param.trim());
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|field| base_name(field.name()) == param)
+    }
+}
+
+/// Strip the wrapper of a nested form name: `card[exp_month]` becomes `exp_month`,
+/// and `metadata[key]` becomes `metadata`.
+fn base_name(name: &str) -> &str {
+    let Some((outer, rest)) = name.split_once('[') else {
+        return name;
+    };
+    match rest.strip_suffix(']') {
+        Some(_) if outer == "metadata" => outer,
+        Some(inner) => inner,
+        None => name,
+    }
+}
+
+macro_rules! param_fields {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $param:literal),* $(,)? }
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $($(#[$variant_meta])* $variant),*
+            }
+
+            impl ParamField for $name {
+                const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+                fn name(self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $param),*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+param_fields! {
+    /// Fields of [`CreateChargeParams`](crate::CreateChargeParams).
+    CreateChargeField {
+        /// `amount`
+        Amount => "amount",
+        /// `currency`
+        Currency => "currency",
+        /// `card`
+        Card => "card",
+        /// `customer`
+        Customer => "customer",
+        /// `description`
+        Description => "description",
+        /// `capture`
+        Capture => "capture",
+        /// `expiry_days`
+        ExpiryDays => "expiry_days",
+        /// `metadata`
+        Metadata => "metadata",
+        /// `three_d_secure`
+        ThreeDSecure => "three_d_secure",
+        /// `tenant`, only sent by charges created through
+        /// [`PlatformClient::tenant`](crate::PlatformClient::tenant).
+        Tenant => "tenant",
+        /// `platform_fee`, only sent by charges created through
+        /// [`PlatformClient::tenant`](crate::PlatformClient::tenant).
+        PlatformFee => "platform_fee",
+    }
+
+    /// Fields of [`CreateCustomerParams`](crate::CreateCustomerParams).
+    CreateCustomerField {
+        /// `email`
+        Email => "email",
+        /// `description`
+        Description => "description",
+        /// `card`
+        Card => "card",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`UpdateCustomerParams`](crate::UpdateCustomerParams).
+    UpdateCustomerField {
+        /// `email`
+        Email => "email",
+        /// `description`
+        Description => "description",
+        /// `default_card`
+        DefaultCard => "default_card",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`CreateCardParams`](crate::CreateCardParams).
+    CreateCardField {
+        /// `card`
+        Card => "card",
+        /// `metadata`
+        Metadata => "metadata",
+        /// `default`
+        Default => "default",
+    }
+
+    /// Fields of [`UpdateCardParams`](crate::UpdateCardParams).
+    UpdateCardField {
+        /// `exp_month`
+        ExpMonth => "exp_month",
+        /// `exp_year`
+        ExpYear => "exp_year",
+        /// `name`
+        Name => "name",
+        /// `address_line1`
+        AddressLine1 => "address_line1",
+        /// `address_line2`
+        AddressLine2 => "address_line2",
+        /// `address_city`
+        AddressCity => "address_city",
+        /// `address_state`
+        AddressState => "address_state",
+        /// `address_zip`
+        AddressZip => "address_zip",
+        /// `country`
+        Country => "country",
+        /// `email`
+        Email => "email",
+        /// `phone`
+        Phone => "phone",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`CreateTokenParams`](crate::CreateTokenParams), including those of
+    /// its [`CardDetails`](crate::CardDetails).
+    CreateTokenField {
+        /// `card[number]`
+        Number => "card[number]",
+        /// `card[exp_month]`
+        ExpMonth => "card[exp_month]",
+        /// `card[exp_year]`
+        ExpYear => "card[exp_year]",
+        /// `card[cvc]`
+        Cvc => "card[cvc]",
+        /// `card[name]`
+        Name => "card[name]",
+        /// `card[address_line1]`
+        AddressLine1 => "card[address_line1]",
+        /// `card[address_line2]`
+        AddressLine2 => "card[address_line2]",
+        /// `card[address_city]`
+        AddressCity => "card[address_city]",
+        /// `card[address_state]`
+        AddressState => "card[address_state]",
+        /// `card[address_zip]`
+        AddressZip => "card[address_zip]",
+        /// `card[country]`
+        Country => "card[country]",
+        /// `card[email]`
+        Email => "card[email]",
+        /// `card[phone]`
+        Phone => "card[phone]",
+        /// `tenant`
+        Tenant => "tenant",
+    }
+
+    /// Fields of [`UpdateChargeParams`](crate::UpdateChargeParams).
+    UpdateChargeField {
+        /// `description`
+        Description => "description",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`RefundParams`](crate::RefundParams).
+    RefundField {
+        /// `amount`
+        Amount => "amount",
+        /// `refund_reason`
+        RefundReason => "refund_reason",
+    }
+
+    /// Fields of [`CaptureParams`](crate::CaptureParams).
+    CaptureField {
+        /// `amount`
+        Amount => "amount",
+    }
+
+    /// Fields of [`ReauthParams`](crate::ReauthParams).
+    ReauthField {
+        /// `expiry_days`
+        ExpiryDays => "expiry_days",
+    }
+
+    /// Fields of [`ChargeThreeDSecureParams`](crate::ChargeThreeDSecureParams).
+    ChargeThreeDSecureField {
+        /// `publickey`
+        PublicKey => "publickey",
+        /// `back`
+        Back => "back",
+    }
+
+    /// Fields of [`CreateThreeDSecureRequestParams`](crate::CreateThreeDSecureRequestParams).
+    CreateThreeDSecureRequestField {
+        /// `resource_id`
+        ResourceId => "resource_id",
+        /// `tenant`, only sent by requests created through
+        /// [`PlatformClient::tenant`](crate::PlatformClient::tenant).
+        Tenant => "tenant",
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+param_fields! {
+    /// Fields of [`CreatePlanParams`](crate::CreatePlanParams).
+    CreatePlanField {
+        /// `amount`
+        Amount => "amount",
+        /// `currency`
+        Currency => "currency",
+        /// `interval`
+        Interval => "interval",
+        /// `id`
+        Id => "id",
+        /// `name`
+        Name => "name",
+        /// `trial_days`
+        TrialDays => "trial_days",
+        /// `billing_day`
+        BillingDay => "billing_day",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`CreateSubscriptionParams`](crate::CreateSubscriptionParams).
+    CreateSubscriptionField {
+        /// `customer`
+        Customer => "customer",
+        /// `plan`
+        Plan => "plan",
+        /// `trial_end`
+        TrialEnd => "trial_end",
+        /// `prorate`
+        Prorate => "prorate",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`UpdatePlanParams`](crate::UpdatePlanParams).
+    UpdatePlanField {
+        /// `name`
+        Name => "name",
+        /// `trial_days`
+        TrialDays => "trial_days",
+        /// `billing_day`
+        BillingDay => "billing_day",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`UpdateSubscriptionParams`](crate::UpdateSubscriptionParams).
+    UpdateSubscriptionField {
+        /// `plan`
+        Plan => "plan",
+        /// `trial_end`
+        TrialEnd => "trial_end",
+        /// `prorate`
+        Prorate => "prorate",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+}
+
+#[cfg(feature = "platform")]
+param_fields! {
+    /// Fields of [`CreateTenantParams`](crate::CreateTenantParams), including the flattened
+    /// [`BankAccount`](crate::resources::platform::tenant::BankAccount) and
+    /// [`TransferSchedule`](crate::TransferSchedule).
+    CreateTenantField {
+        /// `id`
+        Id => "id",
+        /// `name`
+        Name => "name",
+        /// `business_name`
+        BusinessName => "business_name",
+        /// `business_url`
+        BusinessUrl => "business_url",
+        /// `business_phone`
+        BusinessPhone => "business_phone",
+        /// `platform_fee_rate`
+        PlatformFeeRate => "platform_fee_rate",
+        /// `minimum_transfer_amount`
+        MinimumTransferAmount => "minimum_transfer_amount",
+        /// `payjp_fee_included`
+        PayjpFeeIncluded => "payjp_fee_included",
+        /// `bank_code`
+        BankCode => "bank_code",
+        /// `bank_branch_code`
+        BankBranchCode => "bank_branch_code",
+        /// `bank_account_type`
+        BankAccountType => "bank_account_type",
+        /// `bank_account_number`
+        BankAccountNumber => "bank_account_number",
+        /// `bank_account_holder_name`
+        BankAccountHolderName => "bank_account_holder_name",
+        /// `transfer_interval`
+        TransferInterval => "transfer_interval",
+        /// `transfer_monthly_anchor`
+        TransferMonthlyAnchor => "transfer_monthly_anchor",
+        /// `transfer_weekly_anchor`
+        TransferWeeklyAnchor => "transfer_weekly_anchor",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+
+    /// Fields of [`UpdateTenantParams`](crate::UpdateTenantParams), including the flattened
+    /// [`BankAccount`](crate::resources::platform::tenant::BankAccount) and
+    /// [`TransferSchedule`](crate::TransferSchedule).
+    UpdateTenantField {
+        /// `name`
+        Name => "name",
+        /// `platform_fee_rate`
+        PlatformFeeRate => "platform_fee_rate",
+        /// `minimum_transfer_amount`
+        MinimumTransferAmount => "minimum_transfer_amount",
+        /// `bank_code`
+        BankCode => "bank_code",
+        /// `bank_branch_code`
+        BankBranchCode => "bank_branch_code",
+        /// `bank_account_type`
+        BankAccountType => "bank_account_type",
+        /// `bank_account_number`
+        BankAccountNumber => "bank_account_number",
+        /// `bank_account_holder_name`
+        BankAccountHolderName => "bank_account_holder_name",
+        /// `transfer_interval`
+        TransferInterval => "transfer_interval",
+        /// `transfer_monthly_anchor`
+        TransferMonthlyAnchor => "transfer_monthly_anchor",
+        /// `transfer_weekly_anchor`
+        TransferWeeklyAnchor => "transfer_weekly_anchor",
+        /// `metadata`
+        Metadata => "metadata",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_param_matches_nested_and_metadata_names() {
+        assert_eq!(
+            CreateTokenField::from_param("card[exp_month]"),
+            Some(CreateTokenField::ExpMonth)
+        );
+        assert_eq!(CreateTokenField::from_param("exp_year"), Some(CreateTokenField::ExpYear));
+        assert_eq!(CreateTokenField::from_param("tenant"), Some(CreateTokenField::Tenant));
+        assert_eq!(
+            UpdateCardField::from_param("card[exp_month]"),
+            Some(UpdateCardField::ExpMonth)
+        );
+        assert_eq!(
+            CreateChargeField::from_param("metadata[order_id]"),
+            Some(CreateChargeField::Metadata)
+        );
+        assert_eq!(CreateChargeField::from_param("amount"), Some(CreateChargeField::Amount));
+        assert_eq!(CreateChargeField::from_param("unknown"), None);
+    }
+
+    #[cfg(feature = "platform")]
+    #[test]
+    fn test_from_param_matches_flattened_tenant_fields() {
+        assert_eq!(
+            CreateTenantField::from_param("bank_branch_code"),
+            Some(CreateTenantField::BankBranchCode)
+        );
+        assert_eq!(
+            UpdateTenantField::from_param("transfer_monthly_anchor"),
+            Some(UpdateTenantField::TransferMonthlyAnchor)
+        );
+        assert_eq!(UpdateTenantField::from_param("payjp_fee_included"), None);
+    }
+}
@@ -1,14 +1,218 @@
 //! Parameter types for PAY.JP API requests.
+//!
+//! # Clearing fields
+//!
+//! Fields left as `None` are not sent, so an update keeps their stored values.
+//! To unset an optional text field instead, the update params builders have a
+//! `clear_*` setter (e.g. `UpdateCustomerParams::clear_email`), which sends the
+//! field with an empty value. Fields that cannot be unset, such as a
+//! subscription's plan, have none.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+
+mod fields;
+
+pub use fields::{
+    CaptureField, ChargeThreeDSecureField, CreateCardField, CreateChargeField,
+    CreateCustomerField, CreateThreeDSecureRequestField, CreateTokenField, ParamField,
+    ReauthField, RefundField, UpdateCardField, UpdateChargeField, UpdateCustomerField,
+};
+#[cfg(feature = "platform")]
+pub use fields::{CreateTenantField, UpdateTenantField};
+#[cfg(feature = "subscriptions")]
+pub use fields::{
+    CreatePlanField, CreateSubscriptionField, UpdatePlanField, UpdateSubscriptionField,
+};
 
 /// Metadata type for arbitrary key-value pairs.
 ///
 /// PAY.JP supports up to 20 keys, with each key up to 40 characters
 /// and each value up to 500 characters.
+///
+/// # Update semantics
+///
+/// PAY.JP merges the metadata sent in an update into the stored metadata: keys
+/// that are sent are added or overwritten, keys that are not sent are kept, and
+/// keys sent with an empty value are deleted. The update params builders expose
+/// this explicitly:
+///
+/// - `metadata(key, value)`, `metadata_from(pairs)` and `metadata_map(map)` set
+///   the given keys and keep all other stored keys. Every params builder with
+///   metadata also implements [`Extend`] for key-value pairs.
+/// - `metadata_remove(key)` deletes a key.
+/// - `metadata_replace(current, map)` makes the stored metadata exactly `map`,
+///   deleting every key in `current` that is not in `map`.
+///
+/// Setting and removing keys merges into the metadata built so far, and a later
+/// call wins for the same key. `metadata_replace` starts over instead, discarding
+/// metadata set by earlier calls, so call it first and add keys after it.
 pub type Metadata = HashMap<String, String>;
 
+/// Implement the metadata setters and [`Extend`] for a params builder with a
+/// `metadata: Option<Metadata>` field, naming the resource `$noun` in the docs.
+///
+/// With `update`, the setters that delete stored keys are added too.
+macro_rules! impl_metadata_setters {
+    ($ty:ident, $noun:literal) => {
+        impl $ty {
+            #[doc = concat!("Add metadata to the ", $noun, ".")]
+            pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+                self.metadata
+                    .get_or_insert_with(Default::default)
+                    .insert(key.into(), value.into());
+                self
+            }
+
+            #[doc = concat!("Add every key-value pair of `metadata` to the ", $noun, ".")]
+            pub fn metadata_from<K, V>(
+                mut self,
+                metadata: impl IntoIterator<Item = (K, V)>,
+            ) -> Self
+            where
+                K: Into<String>,
+                V: Into<String>,
+            {
+                self.extend(metadata);
+                self
+            }
+
+            #[doc = concat!("Add every entry of `metadata` to the ", $noun, ".")]
+            pub fn metadata_map(self, metadata: $crate::params::Metadata) -> Self {
+                self.metadata_from(metadata)
+            }
+        }
+
+        #[doc = concat!(
+            "Extending the params adds metadata, like [`",
+            stringify!($ty),
+            "::metadata_from`]."
+        )]
+        impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for $ty {
+            fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+                self.metadata
+                    .get_or_insert_with(Default::default)
+                    .extend(iter.into_iter().map(|(key, value)| (key.into(), value.into())));
+            }
+        }
+    };
+    ($ty:ident, $noun:literal, update) => {
+        $crate::params::impl_metadata_setters!($ty, $noun);
+
+        impl $ty {
+            #[doc = concat!("Replace the ", $noun, "'s stored metadata with `metadata`.")]
+            #[doc = ""]
+            #[doc = concat!(
+                "Pass the ", $noun, "'s current metadata so that keys missing from ",
+                "`metadata` are deleted rather than left in place. Metadata set by ",
+                "earlier calls is discarded."
+            )]
+            pub fn metadata_replace(
+                mut self,
+                current: Option<&$crate::params::Metadata>,
+                metadata: $crate::params::Metadata,
+            ) -> Self {
+                self.metadata = Some($crate::params::replace_metadata(current, metadata));
+                self
+            }
+
+            #[doc = concat!("Delete `key` from the ", $noun, "'s stored metadata.")]
+            pub fn metadata_remove(mut self, key: impl Into<String>) -> Self {
+                self.metadata
+                    .get_or_insert_with(Default::default)
+                    .insert(key.into(), String::new());
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use impl_metadata_setters;
+
+/// Implement `clear_*` setters for optional text fields of an update params
+/// builder, naming the resource `$noun` in the docs.
+macro_rules! impl_clear_setters {
+    ($ty:ident, $noun:literal, { $($setter:ident => $field:ident),* $(,)? }) => {
+        impl $ty {
+            $(
+                #[doc = concat!("Remove the ", $noun, "'s `", stringify!($field), "`.")]
+                #[doc = ""]
+                #[doc = "See [clearing fields](crate::params#clearing-fields)."]
+                pub fn $setter(mut self) -> Self {
+                    self.$field = Some(String::new());
+                    self
+                }
+            )*
+        }
+    };
+}
+
+pub(crate) use impl_clear_setters;
+
+/// Build the metadata to send so that the stored metadata becomes exactly `metadata`.
+///
+/// Keys present in `current` but not in `metadata` are sent with an empty value,
+/// which deletes them.
+pub(crate) fn replace_metadata(current: Option<&Metadata>, mut metadata: Metadata) -> Metadata {
+    for key in current.into_iter().flat_map(|current| current.keys()) {
+        metadata.entry(key.clone()).or_default();
+    }
+    metadata
+}
+
+/// Serialize metadata as flat `metadata[key]=value` form fields, sorted by key.
+///
+/// Used with `#[serde(flatten)]` on params structs, since form encoding has no
+/// nested maps.
+pub(crate) fn serialize_metadata<S: Serializer>(
+    metadata: &Option<Metadata>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut entries: Vec<_> = metadata.iter().flatten().collect();
+    entries.sort();
+    serializer.collect_map(
+        entries
+            .into_iter()
+            .map(|(key, value)| (MetadataKey(key), value)),
+    )
+}
+
+/// Serialize borrowed metadata pairs as flat `metadata[key]=value` form fields,
+/// sorted by key.
+pub(crate) fn serialize_metadata_pairs<S: Serializer>(
+    metadata: &[(Cow<'_, str>, Cow<'_, str>)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut entries: Vec<_> = metadata.iter().collect();
+    entries.sort();
+    serializer.collect_map(
+        entries
+            .into_iter()
+            .map(|(key, value)| (MetadataKey(key), value)),
+    )
+}
+
+/// Form field name `metadata[key]` of a metadata key, written straight to the
+/// serializer instead of being formatted into a new `String` first.
+struct MetadataKey<'a>(&'a str);
+
+impl fmt::Display for MetadataKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "metadata[{}]", self.0)
+    }
+}
+
+impl Serialize for MetadataKey<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Maximum page size accepted by list endpoints.
+pub const MAX_LIMIT: i64 = 100;
+
 /// Common parameters for list endpoints with pagination.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ListParams {
@@ -41,6 +245,12 @@ impl ListParams {
         self
     }
 
+    /// Request the largest page size the API allows ([`MAX_LIMIT`]).
+    pub fn max_limit(mut self) -> Self {
+        self.limit = Some(MAX_LIMIT);
+        self
+    }
+
     /// Set the offset for pagination.
     pub fn offset(mut self, offset: i64) -> Self {
         self.offset = Some(offset);
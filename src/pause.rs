@@ -0,0 +1,235 @@
+//! Pausing subscriptions with a scheduled resume.
+//!
+//! [`SubscriptionService::pause_until`] pauses a subscription and records the resume
+//! date in its metadata under [`RESUME_AT_METADATA_KEY`]. An [`AutoResumer`] then
+//! resumes it once the date has passed, either by polling paused subscriptions with
+//! [`AutoResumer::run`], or from a job that a [`ResumeScheduler`] enqueued at pause
+//! time calling [`AutoResumer::resume_if_due`].
+//!
+//! [`SubscriptionService::pause_until`]: crate::SubscriptionService::pause_until
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{AutoResumer, PayjpClient};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! // "Pause my subscription for two months"
+//! # let now = 1_700_000_000;
+//! let resume_at = now + 60 * 24 * 60 * 60;
+//! client.subscriptions().pause_until("sub_xxxxx", resume_at).await?;
+//!
+//! // In a background task
+//! AutoResumer::new(&client).run(Duration::from_secs(3600)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::resources::{
+    PauseSubscriptionParams, ResumeSubscriptionParams, Subscription, SubscriptionStatus,
+    UpdateSubscriptionParams,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use std::time::Duration;
+
+/// Metadata key holding the scheduled resume date (Unix timestamp) of a subscription
+/// paused with [`AutoResumer::pause_until`].
+pub const RESUME_AT_METADATA_KEY: &str = "payjp_resume_at";
+
+/// Hook for scheduling resumes with an application's own job system.
+///
+/// The scheduled job should call [`AutoResumer::resume_if_due`] at `resume_at`.
+#[async_trait]
+pub trait ResumeScheduler: Send + Sync {
+    /// Schedule a resume of `subscription_id` at `resume_at` (Unix timestamp).
+    async fn schedule(&self, subscription_id: &str, resume_at: i64) -> PayjpResult<()>;
+}
+
+/// Pauses subscriptions until a date and resumes them once it has passed.
+pub struct AutoResumer<'a> {
+    client: &'a PayjpClient,
+    params: ResumeSubscriptionParams,
+    scheduler: Option<&'a dyn ResumeScheduler>,
+}
+
+impl<'a> AutoResumer<'a> {
+    /// Create a resumer that resumes with default parameters.
+    pub fn new(client: &'a PayjpClient) -> Self {
+        Self {
+            client,
+            params: ResumeSubscriptionParams::new(),
+            scheduler: None,
+        }
+    }
+
+    /// Set the parameters used to resume subscriptions (e.g., proration).
+    pub fn resume_params(mut self, params: ResumeSubscriptionParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Notify `scheduler` of every resume date set by [`pause_until`](Self::pause_until).
+    pub fn scheduler(mut self, scheduler: &'a dyn ResumeScheduler) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Pause a subscription and schedule it to resume at `resume_at` (Unix timestamp).
+    ///
+    /// The resume date is recorded in the subscription's metadata before pausing, so
+    /// polling with [`run`](Self::run) resumes the subscription even if the
+    /// scheduler hook fails.
+    pub async fn pause_until(
+        &self,
+        subscription_id: &str,
+        resume_at: i64,
+    ) -> PayjpResult<Subscription> {
+        if resume_at <= Utc::now().timestamp() {
+            return Err(PayjpError::InvalidRequest(format!(
+                "resume_at {} is not in the future",
+                resume_at
+            )));
+        }
+
+        let subscriptions = self.client.subscriptions();
+        let params = UpdateSubscriptionParams::new()
+            .metadata(RESUME_AT_METADATA_KEY, resume_at.to_string());
+        subscriptions.update(subscription_id, params).await?;
+        let subscription = subscriptions
+            .pause(subscription_id, PauseSubscriptionParams::new())
+            .await?;
+
+        if let Some(scheduler) = self.scheduler {
+            scheduler.schedule(&subscription.id, resume_at).await?;
+        }
+        Ok(subscription)
+    }
+
+    /// Resume a subscription if its scheduled resume date has passed.
+    ///
+    /// Returns the resumed subscription, or `None` if it is not paused or not due.
+    pub async fn resume_if_due(&self, subscription_id: &str) -> PayjpResult<Option<Subscription>> {
+        let subscription = self.client.subscriptions().retrieve(subscription_id).await?;
+        self.resume(&subscription, Utc::now().timestamp()).await
+    }
+
+    /// Resume every paused subscription whose scheduled resume date has passed.
+    ///
+    /// Returns the resumed subscriptions.
+    pub async fn resume_due(&self) -> PayjpResult<Vec<Subscription>> {
+        let now = Utc::now().timestamp();
+        let paused: Vec<Subscription> = self
+            .client
+            .subscriptions()
+            .stream_by_status(SubscriptionStatus::Paused)
+            .try_collect()
+            .await?;
+
+        let mut resumed = Vec::new();
+        for subscription in &paused {
+            if let Some(subscription) = self.resume(subscription, now).await? {
+                resumed.push(subscription);
+            }
+        }
+        Ok(resumed)
+    }
+
+    /// Resume due subscriptions every `interval` until an error occurs.
+    pub async fn run(self, interval: Duration) -> PayjpResult<()> {
+        loop {
+            self.resume_due().await?;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Resume `subscription` if it is due at `now`, then clear its resume date.
+    async fn resume(
+        &self,
+        subscription: &Subscription,
+        now: i64,
+    ) -> PayjpResult<Option<Subscription>> {
+        if subscription.scheduled_resume().is_none_or(|resume_at| resume_at > now) {
+            return Ok(None);
+        }
+
+        let subscriptions = self.client.subscriptions();
+        subscriptions.resume(&subscription.id, self.params.clone()).await?;
+        let params = UpdateSubscriptionParams::new().metadata_remove(RESUME_AT_METADATA_KEY);
+        subscriptions.update(&subscription.id, params).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn subscription(id: &str, paused_at: i64, resume_at: Option<i64>) -> serde_json::Value {
+        let metadata =
+            resume_at.map(|at| serde_json::json!({ RESUME_AT_METADATA_KEY: at.to_string() }));
+        let fields = serde_json::json!({ "paused_at": paused_at, "metadata": metadata });
+        fixtures::with(fixtures::subscription(id, "paused"), fields)
+    }
+
+    #[tokio::test]
+    async fn test_resume_due_resumes_only_due_subscriptions() {
+        let server = MockServer::start().await;
+        let past = Utc::now().timestamp() - 60;
+        let future = Utc::now().timestamp() + 3600;
+        Mock::given(method("GET"))
+            .and(path("/subscriptions"))
+            .and(query_param("status", "paused"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    subscription("sub_due", past - 600, Some(past)),
+                    subscription("sub_later", past - 600, Some(future)),
+                    subscription("sub_stale", past, Some(past - 600)),
+                    subscription("sub_manual", past, None),
+                ],
+                "has_more": false,
+                "url": "/v1/subscriptions",
+                "count": 4
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions/sub_due/resume"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(subscription("sub_due", 0, Some(past))),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions/sub_due"))
+            .and(body_string_contains("metadata%5Bpayjp_resume_at%5D="))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(subscription("sub_due", 0, None)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let resumed = AutoResumer::new(&client)
+            .resume_due()
+            .await
+            .expect("Failed to resume subscriptions");
+        let ids: Vec<_> = resumed.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["sub_due"]);
+    }
+}
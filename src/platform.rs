@@ -0,0 +1,174 @@
+//! Client for PAY.JP Platform API accounts.
+//!
+//! A platform charges on behalf of its tenants (sub-merchants). [`PlatformClient`]
+//! manages tenants and tenant transfers, and [`PlatformClient::tenant`] scopes
+//! charges and 3D Secure requests to one tenant. The `tenant` and `platform_fee`
+//! parameters can only be sent this way, so merchant integrations cannot send them
+//! by mistake.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{CreateChargeParams, CreateTenantParams, PlatformClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let platform = PlatformClient::new("sk_test_xxxxx")?;
+//!
+//! let tenant = platform
+//!     .tenants()
+//!     .create(CreateTenantParams::new().name("Sub-merchant").platform_fee_rate("10.0"))
+//!     .await?;
+//!
+//! let charge = platform
+//!     .tenant(&tenant.id)
+//!     .charges()
+//!     .create_with_fee(CreateChargeParams::new(1000, "jpy").card("tok_xxxxx"), 100)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::auth::KeyProvider;
+use crate::client::{ClientOptions, PayjpClient};
+use crate::error::PayjpResult;
+use crate::resources::platform::{TenantService, TenantTransferService, TenantWrapper};
+
+/// Client for a PAY.JP platform account, constructed from the platform's secret key.
+#[derive(Debug, Clone)]
+pub struct PlatformClient {
+    client: PayjpClient,
+}
+
+impl PlatformClient {
+    /// Create a platform client with the platform's secret key and default options.
+    pub fn new(api_key: impl Into<String>) -> PayjpResult<Self> {
+        PayjpClient::new(api_key).map(Self::from)
+    }
+
+    /// Create a platform client with the platform's secret key and custom options.
+    pub fn with_options(api_key: impl Into<String>, options: ClientOptions) -> PayjpResult<Self> {
+        PayjpClient::with_options(api_key, options).map(Self::from)
+    }
+
+    /// Create a platform client that looks up the platform's key before every request.
+    pub fn with_key_provider(
+        provider: impl KeyProvider + 'static,
+        options: ClientOptions,
+    ) -> PayjpResult<Self> {
+        PayjpClient::with_key_provider(provider, options).map(Self::from)
+    }
+
+    /// The underlying client, for operations on the platform's own account.
+    pub fn client(&self) -> &PayjpClient {
+        &self.client
+    }
+
+    /// Access the tenants service.
+    pub fn tenants(&self) -> TenantService<'_> {
+        TenantService::new(&self.client)
+    }
+
+    /// Access the tenant transfers service.
+    pub fn tenant_transfers(&self) -> TenantTransferService<'_> {
+        TenantTransferService::new(&self.client)
+    }
+
+    /// Act on behalf of a specific tenant.
+    pub fn tenant(&self, tenant_id: impl Into<String>) -> TenantWrapper<'_> {
+        TenantWrapper::new(&self.client, tenant_id.into())
+    }
+}
+
+impl From<PayjpClient> for PlatformClient {
+    fn from(client: PayjpClient) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CreateChargeParams, CreateThreeDSecureRequestParams, ListChargeParams};
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_tenant_scope_sends_tenant_and_platform_fee() {
+        let server = MockServer::start().await;
+        let charge = serde_json::json!({
+            "id": "ch_1",
+            "object": "charge",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "paid": true,
+            "captured": true,
+            "refunded": false,
+            "amount_refunded": 0,
+            "tenant": "ten_1",
+            "platform_fee": 100
+        });
+        Mock::given(method("POST"))
+            .and(path("/charges"))
+            .and(body_string_contains("tenant=ten_1"))
+            .and(body_string_contains("platform_fee=100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(charge.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("tenant", "ten_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [charge],
+                "has_more": false,
+                "url": "/v1/charges",
+                "count": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/three_d_secure_requests"))
+            .and(body_string_contains("tenant=ten_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "tdsr_1",
+                "object": "three_d_secure_request",
+                "livemode": false,
+                "created": 1700000000,
+                "resource_id": "car_1",
+                "state": "created",
+                "tenant": "ten_1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let platform = PlatformClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let tenant = platform.tenant("ten_1");
+
+        let params = CreateChargeParams::new(1000, "jpy").card("tok_xxxxx");
+        let charge = tenant
+            .charges()
+            .create_with_fee(params, 100)
+            .await
+            .expect("Failed to create charge");
+        assert_eq!(charge.tenant.as_deref(), Some("ten_1"));
+
+        let charges = tenant
+            .charges()
+            .list(ListChargeParams::new())
+            .await
+            .expect("Failed to list charges");
+        assert_eq!(charges.data.len(), 1);
+
+        tenant
+            .create_three_d_secure_request(CreateThreeDSecureRequestParams::new("car_1"))
+            .await
+            .expect("Failed to create 3DS request");
+    }
+}
@@ -0,0 +1,374 @@
+//! Fluent queries over list endpoints.
+//!
+//! [`ChargeQuery`], [`CustomerQuery`] and [`SubscriptionQuery`] describe which
+//! items to list with one set of builder methods. Conditions the API can filter
+//! on (such as the customer or creation time) are sent as list parameters;
+//! the rest (such as metadata or amount) are checked locally while paging.
+//! Run a query with the `query` method of the matching service to get a
+//! [`ListStream`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{ChargeQuery, PayjpClient};
+//! use futures::TryStreamExt;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let query = ChargeQuery::new()
+//!     .customer("cus_xxxxx")
+//!     .created_last_days(30)
+//!     .refunded(false)
+//!     .metadata("order_id", "1234")
+//!     .limit(100);
+//! let charges: Vec<_> = client.charges().query(query).try_collect().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::pagination::{HasMetadata, ListStream};
+use crate::params::{ListParams, MAX_LIMIT};
+use crate::resources::{Charge, Customer, ListChargeParams};
+#[cfg(feature = "subscriptions")]
+use crate::resources::{ListSubscriptionParams, Subscription, SubscriptionStatus};
+use chrono::Utc;
+use std::fmt;
+
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Builder methods and execution shared by all query types.
+macro_rules! impl_query {
+    ($query:ident, $item:ty, $params:ty) => {
+        impl Default for $query {
+            fn default() -> Self {
+                Self {
+                    params: <$params>::default(),
+                    filters: Vec::new(),
+                    limit: None,
+                }
+            }
+        }
+
+        impl fmt::Debug for $query {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($query))
+                    .field("params", &self.params)
+                    .field("filters", &self.filters.len())
+                    .field("limit", &self.limit)
+                    .finish()
+            }
+        }
+
+        impl $query {
+            /// Create a query matching every item.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Only items created since `timestamp` (Unix timestamp, server-side).
+            pub fn created_since(mut self, timestamp: i64) -> Self {
+                self.params.since = Some(timestamp);
+                self
+            }
+
+            /// Only items created until `timestamp` (Unix timestamp, server-side).
+            pub fn created_until(mut self, timestamp: i64) -> Self {
+                self.params.until = Some(timestamp);
+                self
+            }
+
+            /// Only items created in the last `days` days, counted back from now
+            /// (server-side).
+            pub fn created_last_days(self, days: u32) -> Self {
+                self.created_since(Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60)
+            }
+
+            /// Stop after `limit` matching items.
+            ///
+            /// Unlike the `limit` list parameter, this caps the total number of
+            /// items yielded, not the page size.
+            pub fn limit(mut self, limit: usize) -> Self {
+                self.limit = Some(limit);
+                self
+            }
+
+            /// Only items matching `predicate` (local).
+            pub fn filter<F>(mut self, predicate: F) -> Self
+            where
+                F: Fn(&$item) -> bool + Send + Sync + 'static,
+            {
+                self.filters.push(Box::new(predicate));
+                self
+            }
+
+            /// Only items whose metadata contains `key` with the given `value` (local).
+            pub fn metadata(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+                let key = key.into();
+                let value = value.into();
+                self.filter(move |item| {
+                    item.metadata()
+                        .and_then(|metadata| metadata.get(&key))
+                        .is_some_and(|v| *v == value)
+                })
+            }
+
+            /// List with the query's server-side filters and apply the local ones.
+            ///
+            /// Pages are as small as the limit allows when every item counts
+            /// towards it, and as large as possible when items are filtered out
+            /// locally.
+            pub(crate) fn run<'a>(
+                self,
+                list: impl FnOnce($params) -> ListStream<'a, $item>,
+            ) -> ListStream<'a, $item> {
+                let mut params = self.params;
+                params.limit = Some(match self.limit {
+                    Some(limit) if self.filters.is_empty() => (limit as i64).clamp(1, MAX_LIMIT),
+                    _ => MAX_LIMIT,
+                });
+                let mut stream = list(params);
+                if !self.filters.is_empty() {
+                    let filters = self.filters;
+                    stream = stream.filter_local(move |item| filters.iter().all(|f| f(item)));
+                }
+                match self.limit {
+                    Some(limit) => stream.take_local(limit),
+                    None => stream,
+                }
+            }
+        }
+    };
+}
+
+/// A query over charges, run with [`ChargeService::query`](crate::ChargeService::query).
+pub struct ChargeQuery {
+    params: ListChargeParams,
+    filters: Vec<Filter<Charge>>,
+    limit: Option<usize>,
+}
+
+impl_query!(ChargeQuery, Charge, ListChargeParams);
+
+impl ChargeQuery {
+    /// Only charges of `customer` (server-side).
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.params.customer = Some(customer.into());
+        self
+    }
+
+    /// Only charges created by `subscription` (server-side).
+    pub fn subscription(mut self, subscription: impl Into<String>) -> Self {
+        self.params.subscription = Some(subscription.into());
+        self
+    }
+
+    /// Platform API: only charges of `tenant` (server-side).
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.params.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Only charges in `term` (server-side).
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.params.term = Some(term.into());
+        self
+    }
+
+    /// Only charges that are (or are not) captured (local).
+    pub fn captured(self, captured: bool) -> Self {
+        self.filter(move |charge| charge.captured == captured)
+    }
+
+    /// Only charges that are (or are not) fully or partially refunded (local).
+    pub fn refunded(self, refunded: bool) -> Self {
+        self.filter(move |charge| {
+            (charge.refunded || charge.amount_refunded > 0) == refunded
+        })
+    }
+
+    /// Only charges whose amount is between `min` and `max` (inclusive, local).
+    pub fn amount_between(self, min: i64, max: i64) -> Self {
+        self.filter(move |charge| (min..=max).contains(&charge.amount))
+    }
+}
+
+/// A query over customers, run with [`CustomerService::query`](crate::CustomerService::query).
+pub struct CustomerQuery {
+    params: ListParams,
+    filters: Vec<Filter<Customer>>,
+    limit: Option<usize>,
+}
+
+impl_query!(CustomerQuery, Customer, ListParams);
+
+impl CustomerQuery {
+    /// Only customers whose email matches `email`, ignoring case (local).
+    pub fn email(self, email: &str) -> Self {
+        let email = email.trim().to_lowercase();
+        self.filter(move |customer| {
+            customer
+                .email
+                .as_deref()
+                .is_some_and(|e| e.trim().to_lowercase() == email)
+        })
+    }
+}
+
+/// A query over subscriptions, run with
+/// [`SubscriptionService::query`](crate::SubscriptionService::query).
+#[cfg(feature = "subscriptions")]
+pub struct SubscriptionQuery {
+    params: ListSubscriptionParams,
+    filters: Vec<Filter<Subscription>>,
+    limit: Option<usize>,
+}
+
+#[cfg(feature = "subscriptions")]
+impl_query!(SubscriptionQuery, Subscription, ListSubscriptionParams);
+
+#[cfg(feature = "subscriptions")]
+impl SubscriptionQuery {
+    /// Only subscriptions of `customer` (server-side).
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.params.customer = Some(customer.into());
+        self
+    }
+
+    /// Only subscriptions on `plan` (server-side).
+    pub fn plan(mut self, plan: impl Into<String>) -> Self {
+        self.params.plan = Some(plan.into());
+        self
+    }
+
+    /// Only subscriptions with `status` (server-side).
+    pub fn status(mut self, status: SubscriptionStatus) -> Self {
+        self.params.status = Some(status);
+        self
+    }
+
+    /// Platform API: only subscriptions of `tenant` (local).
+    pub fn tenant(self, tenant: impl Into<String>) -> Self {
+        let tenant = tenant.into();
+        self.filter(move |subscription| subscription.tenant.as_deref() == Some(tenant.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{ClientOptions, PayjpClient};
+    use futures::TryStreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn charge(id: &str, amount: i64, order_id: &str) -> serde_json::Value {
+        let fields = serde_json::json!({ "amount": amount, "metadata": { "order_id": order_id } });
+        fixtures::with(fixtures::charge(id), fields)
+    }
+
+    #[tokio::test]
+    async fn test_charge_query_splits_server_and_local_filters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("customer", "cus_1"))
+            .and(query_param("since", "1704067200"))
+            .and(query_param("limit", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    charge("ch_1", 500, "a"),
+                    charge("ch_2", 1500, "b"),
+                    charge("ch_3", 2000, "b"),
+                    charge("ch_4", 3000, "b"),
+                ],
+                "has_more": false,
+                "url": "/v1/charges",
+                "count": 4
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let query = ChargeQuery::new()
+            .customer("cus_1")
+            .created_since(1704067200)
+            .metadata("order_id", "b")
+            .amount_between(1000, 5000)
+            .limit(2);
+        let charges: Vec<Charge> = client.charges().query(query).try_collect().await.unwrap();
+        let ids: Vec<_> = charges.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["ch_2", "ch_3"]);
+    }
+
+    #[tokio::test]
+    async fn test_charge_query_filters_tenant_server_side() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("tenant", "ten_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [charge("ch_1", 1000, "a")],
+                "has_more": false,
+                "url": "/v1/charges",
+                "count": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let charges: Vec<Charge> = client
+            .charges()
+            .query(ChargeQuery::new().tenant("ten_1"))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(charges.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_without_local_filters_sets_page_size() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers"))
+            .and(query_param("limit", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [{
+                    "id": "cus_1",
+                    "object": "customer",
+                    "livemode": false,
+                    "created": 1704067200
+                }],
+                "has_more": true,
+                "url": "/v1/customers",
+                "count": 2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let customers: Vec<Customer> = client
+            .customers()
+            .query(CustomerQuery::new().limit(1))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(customers.len(), 1);
+    }
+}
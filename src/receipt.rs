@@ -0,0 +1,328 @@
+//! Receipt data and rendering for charges.
+//!
+//! [`Receipt`] collects what a customer receipt needs from a [`Charge`] and the
+//! merchant's [`Account`], and renders it as plain text or HTML.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{PayjpClient, Receipt, TaxBreakdown};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let charge = client.charges().retrieve("ch_xxxxx").await?;
+//! let account = client.account().retrieve().await?;
+//!
+//! let receipt = Receipt::from_charge(&charge)
+//!     .merchant(&account)
+//!     .tax(TaxBreakdown::inclusive(charge.amount, 10));
+//! println!("{}", receipt);
+//! let html = receipt.to_html();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::resources::{Account, CardOrId, Charge};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write as _};
+
+/// Consumption tax included in or added to a receipt amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxBreakdown {
+    /// Tax rate in percent (e.g., 10, or 8 for the reduced rate).
+    pub rate_percent: u32,
+
+    /// Amount excluding tax.
+    pub subtotal: i64,
+
+    /// Tax amount.
+    pub tax: i64,
+}
+
+impl TaxBreakdown {
+    /// Split a tax-inclusive amount, truncating fractional yen of tax.
+    pub fn inclusive(amount: i64, rate_percent: u32) -> Self {
+        let rate = i64::from(rate_percent);
+        let tax = amount * rate / (100 + rate);
+        Self {
+            rate_percent,
+            subtotal: amount - tax,
+            tax,
+        }
+    }
+
+    /// Compute tax on a tax-exclusive amount, truncating fractional yen of tax.
+    pub fn exclusive(subtotal: i64, rate_percent: u32) -> Self {
+        Self {
+            rate_percent,
+            subtotal,
+            tax: subtotal * i64::from(rate_percent) / 100,
+        }
+    }
+}
+
+/// Receipt data for a charge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// Charge ID.
+    pub charge_id: String,
+
+    /// Merchant name (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_name: Option<String>,
+
+    /// Merchant contact email address (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_email: Option<String>,
+
+    /// Description of the charge (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Charged amount.
+    pub amount: i64,
+
+    /// Amount refunded.
+    pub amount_refunded: i64,
+
+    /// Three-letter ISO currency code.
+    pub currency: String,
+
+    /// PAY.JP fee for the charge, for merchant records; not rendered (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<i64>,
+
+    /// Tax breakdown of the charged amount (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax: Option<TaxBreakdown>,
+
+    /// Card brand (e.g., "Visa", optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_brand: Option<String>,
+
+    /// Last four digits of the card number (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_last4: Option<String>,
+
+    /// Charge creation timestamp (Unix timestamp).
+    pub created: i64,
+
+    /// Capture timestamp (Unix timestamp, optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captured_at: Option<i64>,
+}
+
+impl Receipt {
+    /// Build a receipt from a charge.
+    pub fn from_charge(charge: &Charge) -> Self {
+        let card = charge.card.as_ref().and_then(CardOrId::as_card);
+        Self {
+            charge_id: charge.id.clone(),
+            merchant_name: None,
+            merchant_email: None,
+            description: charge.description.clone(),
+            amount: charge.amount,
+            amount_refunded: charge.amount_refunded,
+            currency: charge.currency.clone(),
+            fee: charge.fee_breakdown().map(|fees| fees.fee),
+            tax: None,
+            card_brand: card.map(|card| card.brand.clone()),
+            card_last4: card.map(|card| card.last4.clone()),
+            created: charge.created,
+            captured_at: charge.captured_at,
+        }
+    }
+
+    /// Set the merchant name and email from the account.
+    pub fn merchant(mut self, account: &Account) -> Self {
+        self.merchant_name = account.merchant_name.clone();
+        self.merchant_email = account.email.clone();
+        self
+    }
+
+    /// Set the tax breakdown.
+    pub fn tax(mut self, tax: TaxBreakdown) -> Self {
+        self.tax = Some(tax);
+        self
+    }
+
+    /// Amount paid after refunds.
+    pub fn total(&self) -> i64 {
+        self.amount - self.amount_refunded
+    }
+
+    /// Render the receipt as plain text.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (label, value) in self.lines() {
+            let _ = match label {
+                Some(label) => writeln!(text, "{}: {}", label, value),
+                None => writeln!(text, "{}", value),
+            };
+        }
+        text
+    }
+
+    /// Render the receipt as an HTML fragment (a `<table>`), escaping all values.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table class=\"payjp-receipt\">\n");
+        for (label, value) in self.lines() {
+            let _ = match label {
+                Some(label) => writeln!(
+                    html,
+                    "  <tr><th>{}</th><td>{}</td></tr>",
+                    escape_html(&label),
+                    escape_html(&value)
+                ),
+                None => writeln!(
+                    html,
+                    "  <tr><th colspan=\"2\">{}</th></tr>",
+                    escape_html(&value)
+                ),
+            };
+        }
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// The receipt as labelled lines, shared by the renderers.
+    fn lines(&self) -> Vec<(Option<String>, String)> {
+        let mut lines = Vec::new();
+        let mut line = |label: Option<&str>, value: String| {
+            lines.push((label.map(str::to_string), value));
+        };
+
+        line(None, self.merchant_name.clone().unwrap_or_else(|| "Receipt".to_string()));
+        line(Some("Receipt"), self.charge_id.clone());
+        line(Some("Date"), format_timestamp(self.captured_at.unwrap_or(self.created)));
+        if let Some(description) = &self.description {
+            line(Some("Description"), description.clone());
+        }
+        line(Some("Amount"), self.format_amount(self.amount));
+        if let Some(tax) = &self.tax {
+            line(Some("Subtotal (excl. tax)"), self.format_amount(tax.subtotal));
+            line(
+                Some(&format!("Tax ({}%)", tax.rate_percent)),
+                self.format_amount(tax.tax),
+            );
+        }
+        if self.amount_refunded > 0 {
+            line(Some("Refunded"), format!("-{}", self.format_amount(self.amount_refunded)));
+            line(Some("Total"), self.format_amount(self.total()));
+        }
+        if let (Some(brand), Some(last4)) = (&self.card_brand, &self.card_last4) {
+            line(Some("Paid with"), format!("{} ending in {}", brand, last4));
+        }
+        if let Some(email) = &self.merchant_email {
+            line(Some("Contact"), email.clone());
+        }
+        lines
+    }
+
+    /// Format an amount with thousands separators, as yen for JPY.
+    fn format_amount(&self, amount: i64) -> String {
+        let digits = amount.unsigned_abs().to_string();
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        let sign = if amount < 0 { "-" } else { "" };
+        if self.currency.eq_ignore_ascii_case("jpy") {
+            format!("{}¥{}", sign, grouped)
+        } else {
+            format!("{}{} {}", sign, grouped, self.currency.to_uppercase())
+        }
+    }
+}
+
+impl fmt::Display for Receipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+/// Format a Unix timestamp in Japan Standard Time.
+fn format_timestamp(timestamp: i64) -> String {
+    let jst = FixedOffset::east_opt(9 * 3600).expect("valid offset");
+    match DateTime::from_timestamp(timestamp, 0) {
+        Some(time) => time.with_timezone(&jst).format("%Y-%m-%d %H:%M JST").to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn charge() -> Charge {
+        let fields = serde_json::json!({
+            "created": 1704034800,
+            "amount": 11000,
+            "refunded": true,
+            "amount_refunded": 1100,
+            "description": "Tea & <cakes>",
+            "fee_rate": "3.00",
+            "card": {
+                "id": "car_1",
+                "object": "card",
+                "livemode": false,
+                "created": 1704034800,
+                "brand": "Visa",
+                "exp_month": 12,
+                "exp_year": 2030,
+                "last4": "4242"
+            }
+        });
+        serde_json::from_value(fixtures::with(fixtures::charge("ch_1"), fields)).unwrap()
+    }
+
+    #[test]
+    fn test_receipt_from_charge() {
+        let receipt = Receipt::from_charge(&charge()).tax(TaxBreakdown::inclusive(11000, 10));
+        assert_eq!(receipt.fee, Some(330));
+        assert_eq!(receipt.total(), 9900);
+
+        let text = receipt.to_text();
+        assert!(text.starts_with("Receipt\nReceipt: ch_1\nDate: 2024-01-01 00:00 JST\n"));
+        assert!(text.contains("Amount: ¥11,000\n"));
+        assert!(text.contains("Subtotal (excl. tax): ¥10,000\nTax (10%): ¥1,000\n"));
+        assert!(text.contains("Refunded: -¥1,100\nTotal: ¥9,900\n"));
+        assert!(text.contains("Paid with: Visa ending in 4242\n"));
+
+        let html = receipt.to_html();
+        assert!(html.contains("<td>Tea &amp; &lt;cakes&gt;</td>"));
+    }
+
+    #[test]
+    fn test_tax_breakdown_truncates() {
+        assert_eq!(
+            TaxBreakdown::inclusive(1999, 8),
+            TaxBreakdown {
+                rate_percent: 8,
+                subtotal: 1851,
+                tax: 148
+            }
+        );
+        assert_eq!(TaxBreakdown::exclusive(1999, 10).tax, 199);
+    }
+}
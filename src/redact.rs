@@ -0,0 +1,131 @@
+//! Helpers for masking sensitive card data before it is logged or stored.
+
+use serde_json::Value;
+use url::form_urlencoded;
+
+/// Mask all but the last four characters of a card number.
+pub(crate) fn mask_card_number(number: &str) -> String {
+    let digits: Vec<char> = number.chars().filter(|c| !c.is_whitespace()).collect();
+    let visible = digits.len().min(4);
+    let masked = digits.len() - visible;
+    let mut out = "*".repeat(masked);
+    out.extend(&digits[masked..]);
+    out
+}
+
+/// Whether a form field name carries a card number.
+fn is_number_field(name: &str) -> bool {
+    name == "number" || name.ends_with("[number]")
+}
+
+/// Whether a form field name carries a card security code.
+fn is_cvc_field(name: &str) -> bool {
+    name == "cvc" || name.ends_with("[cvc]")
+}
+
+/// Redact card numbers and CVCs from a form-encoded request body or query string.
+pub(crate) fn redact_form(encoded: &str) -> String {
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (name, value) in form_urlencoded::parse(encoded.as_bytes()) {
+        if is_number_field(&name) {
+            serializer.append_pair(&name, &mask_card_number(&value));
+        } else if is_cvc_field(&name) {
+            serializer.append_pair(&name, "***");
+        } else {
+            serializer.append_pair(&name, &value);
+        }
+    }
+    serializer.finish()
+}
+
+/// Redact card numbers, CVCs and card fingerprints in a JSON value, recursively.
+pub(crate) fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map.iter_mut() {
+                match (name.as_str(), &*value) {
+                    (name, Value::String(number)) if is_number_field(name) => {
+                        *value = Value::String(mask_card_number(number));
+                    }
+                    (name, Value::String(_)) if is_cvc_field(name) || name == "fingerprint" => {
+                        *value = Value::String("***".to_string());
+                    }
+                    _ => redact_json(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Redact a response body: by field name when it is JSON, and as free text in
+/// either case, since messages may echo card numbers.
+pub(crate) fn redact_body(body: &[u8]) -> String {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut value) => {
+            redact_json(&mut value);
+            redact_text(&value.to_string())
+        }
+        Err(_) => redact_text(&String::from_utf8_lossy(body)),
+    }
+}
+
+/// Mask card numbers and CVCs in free text, such as an error message or
+/// parameter echoed back by the API.
+///
+/// `name=value` pairs for number and CVC fields are masked by name, and any
+/// other run of 12 to 19 digits (optionally separated by spaces or hyphens) is
+/// masked as a card number.
+pub(crate) fn redact_text(text: &str) -> String {
+    let assigned: String = text
+        .split_inclusive(|c: char| c.is_whitespace() || c == '&' || c == ',')
+        .map(|piece| {
+            let end = piece.trim_end_matches(|c: char| c.is_whitespace() || c == '&' || c == ',');
+            match end.split_once('=') {
+                Some((name, _)) if is_cvc_field(name) => {
+                    format!("{}=***{}", name, &piece[end.len()..])
+                }
+                Some((name, value)) if is_number_field(name) => {
+                    format!("{}={}{}", name, mask_card_number(value), &piece[end.len()..])
+                }
+                _ => piece.to_string(),
+            }
+        })
+        .collect();
+    mask_digit_runs(&assigned)
+}
+
+/// Mask runs of 12 to 19 digits, allowing single spaces or hyphens between them.
+fn mask_digit_runs(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let mut digits = String::new();
+        while i < chars.len() {
+            if chars[i].is_ascii_digit() {
+                digits.push(chars[i]);
+                i += 1;
+            } else if matches!(chars[i], ' ' | '-')
+                && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit())
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        if (12..=19).contains(&digits.len()) {
+            out.push_str(&mask_card_number(&digits));
+        } else {
+            out.extend(&chars[start..i]);
+        }
+    }
+    out
+}
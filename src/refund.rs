@@ -0,0 +1,138 @@
+//! Partial refund tracking.
+//!
+//! [`RefundTracker`] wraps a charge and checks each refund against the amount
+//! already refunded before calling the API, so an over-refund is reported
+//! locally instead of failing at PAY.JP.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{PayjpClient, RefundParams, RefundTracker};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let charge = client.charges().retrieve("ch_xxxxx").await?;
+//!
+//! let mut tracker = RefundTracker::new(&client, charge);
+//! let remaining = tracker
+//!     .refund(RefundParams::new().amount(500).reason("Damaged item"))
+//!     .await?;
+//! println!("{} yen can still be refunded", remaining);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::resources::{Charge, RefundParams};
+
+/// A charge and the amount that can still be refunded from it.
+#[derive(Debug, Clone)]
+pub struct RefundTracker<'a> {
+    client: &'a PayjpClient,
+    charge: Charge,
+}
+
+impl<'a> RefundTracker<'a> {
+    /// Track refunds for `charge`.
+    ///
+    /// Pass a recently retrieved charge; refunds made elsewhere are only seen
+    /// after [`refresh`](Self::refresh).
+    pub fn new(client: &'a PayjpClient, charge: Charge) -> Self {
+        Self { client, charge }
+    }
+
+    /// Get the underlying charge, as of the last refund or refresh.
+    pub fn charge(&self) -> &Charge {
+        &self.charge
+    }
+
+    /// Consume the tracker and return the underlying charge.
+    pub fn into_charge(self) -> Charge {
+        self.charge
+    }
+
+    /// Get the amount that can still be refunded.
+    pub fn refundable(&self) -> i64 {
+        self.charge.refundable_amount()
+    }
+
+    /// Refund the charge and return the amount that can still be refunded.
+    ///
+    /// Without an `amount`, the whole refundable amount is refunded. Fails with
+    /// `InvalidRequest` without calling the API if the amount is not positive or
+    /// exceeds the refundable amount.
+    pub async fn refund(&mut self, params: RefundParams) -> PayjpResult<i64> {
+        let refundable = self.refundable();
+        let amount = params.amount.unwrap_or(refundable);
+        if amount <= 0 || amount > refundable {
+            return Err(PayjpError::InvalidRequest(format!(
+                "Refund amount {} for charge {} must be between 1 and the refundable amount {}",
+                amount, self.charge.id, refundable
+            )));
+        }
+
+        self.charge = self
+            .client
+            .charges()
+            .refund(&self.charge.id, params.amount(amount))
+            .await?;
+        Ok(self.refundable())
+    }
+
+    /// Reload the charge from the API.
+    pub async fn refresh(&mut self) -> PayjpResult<&Charge> {
+        self.charge = self.client.charges().retrieve(&self.charge.id).await?;
+        Ok(&self.charge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use wiremock::matchers::{body_string, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn charge(amount_refunded: i64) -> serde_json::Value {
+        let fields = serde_json::json!({
+            "refunded": amount_refunded > 0,
+            "amount_refunded": amount_refunded
+        });
+        fixtures::with(fixtures::charge("ch_1"), fields)
+    }
+
+    #[tokio::test]
+    async fn test_refund_validates_remaining_amount() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/charges/ch_1/refund"))
+            .and(body_string("amount=300"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(charge(1000)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client =
+            PayjpClient::with_options("sk_test_xxxxx", options).expect("Failed to create client");
+
+        let mut tracker = RefundTracker::new(&client, serde_json::from_value(charge(700)).unwrap());
+        assert_eq!(tracker.refundable(), 300);
+        assert!(matches!(
+            tracker.refund(RefundParams::new().amount(500)).await,
+            Err(PayjpError::InvalidRequest(_))
+        ));
+
+        let remaining = tracker
+            .refund(RefundParams::new())
+            .await
+            .expect("Failed to refund");
+        assert_eq!(remaining, 0);
+        assert!(matches!(
+            tracker.refund(RefundParams::new()).await,
+            Err(PayjpError::InvalidRequest(_))
+        ));
+    }
+}
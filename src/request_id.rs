@@ -0,0 +1,187 @@
+//! Request and correlation IDs.
+//!
+//! Every API call is tagged with a client-generated request ID, sent in the
+//! [`REQUEST_ID_HEADER`] header and reused across retries. The ID is attached to
+//! [`ApiError`](crate::ApiError)s, captured exchanges and retry log events, so a
+//! failure in application logs can be matched to the HTTP traffic.
+//!
+//! To trace a call across services, run it inside [`with_correlation_id`]; the
+//! ID is sent in the [`CORRELATION_ID_HEADER`] header of every request made
+//! within the scope.
+//!
+//! Rate-limited POST requests are only retried when they carry an idempotency
+//! key, so a retry can never create a second charge. Run a single POST inside
+//! [`with_idempotency_key`] to send the key in the [`IDEMPOTENCY_KEY_HEADER`]
+//! header; GET and DELETE requests are always retried. Reusing a key with
+//! different parameters fails with [`PayjpError::IdempotencyConflict`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::request_id::with_correlation_id;
+//! use payjp::{CreateChargeParams, PayjpClient, PayjpError};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! let result = with_correlation_id("order-1234", async {
+//!     client
+//!         .charges()
+//!         .create(CreateChargeParams::new(1000, "jpy").card("tok_xxxxx"))
+//!         .await
+//! })
+//! .await;
+//!
+//! if let Err(PayjpError::Api(err)) = &result {
+//!     eprintln!("charge failed (request {:?}): {}", err.request_id, err);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`PayjpError::IdempotencyConflict`]: crate::PayjpError::IdempotencyConflict
+
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+
+/// Header carrying the client-generated request ID.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Header carrying the caller-supplied correlation ID.
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+/// Header carrying the caller-supplied idempotency key of a POST request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+    static IDEMPOTENCY_KEY: String;
+}
+
+/// Generate a unique request ID (e.g. `req_3f2a...`).
+pub fn generate_request_id() -> String {
+    format!("req_{:032x}", rand::rng().random::<u128>())
+}
+
+/// Run `future` with `correlation_id` attached to every API request it makes.
+pub async fn with_correlation_id<F: Future>(
+    correlation_id: impl Into<String>,
+    future: F,
+) -> F::Output {
+    CORRELATION_ID.scope(correlation_id.into(), future).await
+}
+
+/// Get the correlation ID of the current [`with_correlation_id`] scope, if any.
+pub fn correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` with `key` attached to the POST requests it makes.
+///
+/// PAY.JP returns the original result for a repeated key instead of processing
+/// the request again, which makes rate-limited POSTs safe to retry. Use a new
+/// key for every operation, such as an order ID, and wrap only one POST per
+/// scope: a second POST with the same key would get the first one's result, or
+/// fail with [`PayjpError::IdempotencyConflict`] if its parameters differ.
+///
+/// [`PayjpError::IdempotencyConflict`]: crate::PayjpError::IdempotencyConflict
+///
+/// # Example
+///
+/// ```no_run
+/// use payjp::request_id::with_idempotency_key;
+/// use payjp::{CreateChargeParams, PayjpClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = PayjpClient::new("sk_test_xxxxx")?;
+///
+/// let charge = with_idempotency_key("order-1234-charge", async {
+///     client
+///         .charges()
+///         .create(CreateChargeParams::new(1000, "jpy").card("tok_xxxxx"))
+///         .await
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_idempotency_key<F: Future>(key: impl Into<String>, future: F) -> F::Output {
+    IDEMPOTENCY_KEY.scope(key.into(), future).await
+}
+
+/// Get the idempotency key of the current [`with_idempotency_key`] scope, if any.
+pub fn idempotency_key() -> Option<String> {
+    IDEMPOTENCY_KEY.try_with(Clone::clone).ok()
+}
+
+/// Maximum number of idempotency keys remembered by an [`IdempotencyLog`].
+const IDEMPOTENCY_LOG_SIZE: usize = 1024;
+
+/// The request ID that first used each idempotency key, shared between clones
+/// of a client.
+///
+/// Only the most recent [`IDEMPOTENCY_LOG_SIZE`] keys are kept.
+#[derive(Debug, Default)]
+pub(crate) struct IdempotencyLog {
+    entries: Mutex<(HashMap<String, String>, VecDeque<String>)>,
+}
+
+impl IdempotencyLog {
+    /// Remember `request_id` as the first request sent with `key`, unless one is
+    /// already known.
+    pub(crate) fn record(&self, key: &str, request_id: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (requests, order) = &mut *entries;
+        if requests.contains_key(key) {
+            return;
+        }
+        if order.len() >= IDEMPOTENCY_LOG_SIZE {
+            if let Some(oldest) = order.pop_front() {
+                requests.remove(&oldest);
+            }
+        }
+        requests.insert(key.to_string(), request_id.to_string());
+        order.push_back(key.to_string());
+    }
+
+    /// The ID of the first request sent with `key`, if it is still remembered.
+    pub(crate) fn original(&self, key: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.0.get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_correlation_id_scope() {
+        assert_eq!(correlation_id(), None);
+        let inner = with_correlation_id("order-1234", async { correlation_id() }).await;
+        assert_eq!(inner.as_deref(), Some("order-1234"));
+        assert_eq!(correlation_id(), None);
+
+        let id = generate_request_id();
+        assert!(id.starts_with("req_"));
+        assert_eq!(id.len(), 36);
+        assert_ne!(id, generate_request_id());
+    }
+
+    #[test]
+    fn test_idempotency_log_keeps_first_request() {
+        let log = IdempotencyLog::default();
+        log.record("order-1", "req_1");
+        log.record("order-1", "req_2");
+        assert_eq!(log.original("order-1").as_deref(), Some("req_1"));
+        assert_eq!(log.original("order-2"), None);
+
+        for i in 0..IDEMPOTENCY_LOG_SIZE {
+            log.record(&format!("key-{}", i), "req_x");
+        }
+        assert_eq!(log.original("order-1"), None);
+        assert_eq!(log.original("key-1").as_deref(), Some("req_x"));
+    }
+}
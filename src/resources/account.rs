@@ -3,6 +3,7 @@
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
 use crate::params::Metadata;
+use crate::resources::ObjectKind;
 use serde::{Deserialize, Serialize};
 
 /// Account information for the authenticated merchant.
@@ -12,6 +13,7 @@ pub struct Account {
     pub id: String,
 
     /// Object type (always "account").
+    #[serde(deserialize_with = "Account::deserialize_object")]
     pub object: String,
 
     /// Whether this account is in live mode.
@@ -49,6 +51,10 @@ pub struct Account {
     pub metadata: Option<Metadata>,
 }
 
+impl ObjectKind for Account {
+    const OBJECT: &'static str = "account";
+}
+
 /// Service for retrieving account information.
 pub struct AccountService<'a> {
     client: &'a PayjpClient,
@@ -2,8 +2,10 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
+use crate::pagination::ListStream;
 use crate::params::ListParams;
-use crate::resources::statement::StatementUrls;
+use crate::resources::{string_enum, ObjectKind};
+use crate::resources::statement::{StatementUrls, StatementUrlsParams};
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +16,7 @@ pub struct Balance {
     pub id: String,
 
     /// Object type (always "balance").
+    #[serde(deserialize_with = "Balance::deserialize_object")]
     pub object: String,
 
     /// Whether this balance was created in live mode.
@@ -31,9 +34,9 @@ pub struct Balance {
     /// Pending balance amount.
     pub pending: i64,
 
-    /// Balance state ("processing", "confirmed", etc., optional).
+    /// Balance state (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub state: Option<String>,
+    pub state: Option<BalanceState>,
 
     /// Tenant ID (Platform API, optional).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,6 +55,30 @@ pub struct Balance {
     pub due_date: Option<i64>,
 }
 
+impl ObjectKind for Balance {
+    const OBJECT: &'static str = "balance";
+}
+
+string_enum! {
+    /// Where a balance is in the payout cycle.
+    ///
+    /// Unknown states are preserved as [`BalanceState::Other`] together with the
+    /// raw `state` string.
+    pub enum BalanceState {
+        /// Other balance states not explicitly handled (raw `state` string).
+        Other(String),
+
+        /// Sales are still being collected for the term.
+        Collecting => "collecting",
+
+        /// A positive balance is scheduled to be transferred.
+        Transfer => "transfer",
+
+        /// A negative balance is being claimed from the merchant.
+        Claim => "claim",
+    }
+}
+
 /// Bank account information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BankInfo {
@@ -95,8 +122,7 @@ impl<'a> BalanceService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, balance_id: &str) -> PayjpResult<Balance> {
-        let path = format!("/balances/{}", balance_id);
-        self.client.get(&path).await
+        self.client.retrieve(balance_id).await
     }
 
     /// Get download URLs for a balance statement.
@@ -112,8 +138,31 @@ impl<'a> BalanceService<'a> {
     /// # }
     /// ```
     pub async fn statement_urls(&self, balance_id: &str) -> PayjpResult<StatementUrls> {
+        self.statement_urls_with(balance_id, StatementUrlsParams::new()).await
+    }
+
+    /// Get download URLs for a balance statement with additional parameters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, StatementUrlsParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let urls = client.balances().statement_urls_with(
+    ///     "ba_xxxxx",
+    ///     StatementUrlsParams::new().platformer(true)
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn statement_urls_with(
+        &self,
+        balance_id: &str,
+        params: StatementUrlsParams,
+    ) -> PayjpResult<StatementUrls> {
         let path = format!("/balances/{}/statement_urls", balance_id);
-        self.client.post(&path, &serde_json::json!({})).await
+        self.client.post(&path, &params).await
     }
 
     /// List all balances.
@@ -131,6 +180,52 @@ impl<'a> BalanceService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Balance>> {
-        self.client.get_with_params("/balances", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all balances, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut balances = client.balances().list_all(ListParams::new().max_limit());
+    /// while let Some(balance) = balances.try_next().await? {
+    ///     println!("{}", balance.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Balance> {
+        self.client.stream_resource(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_state_preserves_raw_string() {
+        let balance: Balance = serde_json::from_value(serde_json::json!({
+            "id": "ba_1",
+            "object": "balance",
+            "livemode": false,
+            "created": 1700000000,
+            "total": 1000,
+            "available": 1000,
+            "pending": 0,
+            "state": "transfer"
+        }))
+        .unwrap();
+        assert_eq!(balance.state, Some(BalanceState::Transfer));
+
+        let unknown: BalanceState = serde_json::from_str("\"closed\"").unwrap();
+        assert_eq!(unknown, BalanceState::Other("closed".to_string()));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"closed\"");
     }
 }
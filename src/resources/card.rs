@@ -2,8 +2,12 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
-use crate::response::ListResponse;
+use crate::pagination::ListStream;
+use crate::params::{impl_clear_setters, impl_metadata_setters, ListParams, Metadata};
+use crate::resources::ObjectKind;
+use crate::response::{Deleted, ListResponse};
+use chrono::{DateTime, Datelike, Utc};
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 
 /// A card object represents a credit or debit card associated with a customer.
@@ -13,6 +17,7 @@ pub struct Card {
     pub id: String,
 
     /// Object type (always "card").
+    #[serde(deserialize_with = "Card::deserialize_object")]
     pub object: String,
 
     /// Whether this card was created in live mode.
@@ -94,6 +99,31 @@ pub struct Card {
     pub metadata: Option<Metadata>,
 }
 
+impl ObjectKind for Card {
+    const OBJECT: &'static str = "card";
+}
+
+impl Card {
+    /// Months from the month of `now` (UTC) to the card's expiry month.
+    ///
+    /// Cards are valid through the end of their expiry month, so `0` means the
+    /// card expires at the end of the current month and a negative value means
+    /// it has already expired.
+    pub fn months_until_expiry_at(&self, now: DateTime<Utc>) -> i32 {
+        let current = now.year() * 12 + now.month0() as i32;
+        let expiry = self.exp_year * 12 + (self.exp_month - 1);
+        expiry - current
+    }
+
+    /// Whether the card expires within `months` months, counting the current
+    /// month, or has already expired.
+    ///
+    /// `expires_within(1)` matches cards expiring at the end of this month.
+    pub fn expires_within(&self, months: u32) -> bool {
+        i64::from(self.months_until_expiry_at(Utc::now())) < i64::from(months)
+    }
+}
+
 /// 3D Secure status for a card.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -114,6 +144,71 @@ pub enum CardThreeDSecureStatus {
     Error,
 }
 
+/// Result of finishing 3D Secure authentication on a charge or token.
+///
+/// Returned by [`ChargeService::tds_finish_outcome`](crate::ChargeService::tds_finish_outcome)
+/// and [`TokenService::tds_finish_outcome`](crate::TokenService::tds_finish_outcome).
+/// PAY.JP does not expose the raw ECI value, so outcomes are classified from
+/// `three_d_secure_status`.
+#[derive(Debug, Clone)]
+pub enum ThreeDSecureOutcome<T> {
+    /// The cardholder was authenticated (`verified`).
+    Verified(T),
+
+    /// Authentication was attempted but the issuer did not take part (`attempted`).
+    ///
+    /// Liability usually still shifts to the issuer.
+    Attempted(T),
+
+    /// Authentication failed, errored, or did not complete.
+    Failed {
+        /// The charge or token as returned by `tds_finish`.
+        resource: T,
+
+        /// Reported 3D Secure status (`failed`, `error`, `unverified`, or absent).
+        status: Option<CardThreeDSecureStatus>,
+
+        /// Failure code reported by PAY.JP (e.g. "three_d_secure_failed", optional).
+        code: Option<String>,
+    },
+}
+
+impl<T> ThreeDSecureOutcome<T> {
+    /// Classify a resource by its 3D Secure status.
+    pub fn new(resource: T, status: Option<CardThreeDSecureStatus>, code: Option<String>) -> Self {
+        match status {
+            Some(CardThreeDSecureStatus::Verified) => Self::Verified(resource),
+            Some(CardThreeDSecureStatus::Attempted) => Self::Attempted(resource),
+            status => Self::Failed {
+                resource,
+                status,
+                code,
+            },
+        }
+    }
+
+    /// Whether the payment may proceed (verified or attempted).
+    pub fn is_authenticated(&self) -> bool {
+        !matches!(self, Self::Failed { .. })
+    }
+
+    /// Get the charge or token.
+    pub fn resource(&self) -> &T {
+        match self {
+            Self::Verified(resource) | Self::Attempted(resource) => resource,
+            Self::Failed { resource, .. } => resource,
+        }
+    }
+
+    /// Take the charge or token.
+    pub fn into_resource(self) -> T {
+        match self {
+            Self::Verified(resource) | Self::Attempted(resource) => resource,
+            Self::Failed { resource, .. } => resource,
+        }
+    }
+}
+
 /// Parameters for creating a card.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct CreateCardParams {
@@ -122,7 +217,7 @@ pub struct CreateCardParams {
     pub card: Option<String>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 
     /// Whether to set this card as the default for the customer.
@@ -139,14 +234,6 @@ impl CreateCardParams {
         }
     }
 
-    /// Add metadata to the card.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
-
     /// Set this card as the default for the customer.
     pub fn set_default(mut self, default: bool) -> Self {
         self.default = Some(default);
@@ -154,6 +241,8 @@ impl CreateCardParams {
     }
 }
 
+impl_metadata_setters!(CreateCardParams, "card");
+
 /// Parameters for updating a card.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct UpdateCardParams {
@@ -202,7 +291,7 @@ pub struct UpdateCardParams {
     pub phone: Option<String>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -224,16 +313,22 @@ impl UpdateCardParams {
         self.name = Some(name.into());
         self
     }
-
-    /// Add metadata to the card.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_clear_setters!(UpdateCardParams, "card", {
+    clear_name => name,
+    clear_address_line1 => address_line1,
+    clear_address_line2 => address_line2,
+    clear_address_city => address_city,
+    clear_address_state => address_state,
+    clear_address_zip => address_zip,
+    clear_country => country,
+    clear_email => email,
+    clear_phone => phone,
+});
+
+impl_metadata_setters!(UpdateCardParams, "card", update);
+
 /// Service for managing cards associated with a customer.
 pub struct CardService<'a> {
     client: &'a PayjpClient,
@@ -264,6 +359,7 @@ impl<'a> CardService<'a> {
     /// # }
     /// ```
     pub async fn create(&self, params: CreateCardParams) -> PayjpResult<Card> {
+        self.client.verify_token(params.card.as_deref()).await?;
         let path = format!("/customers/{}/cards", self.customer_id);
         self.client.post(&path, &params).await
     }
@@ -338,19 +434,143 @@ impl<'a> CardService<'a> {
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Card>> {
         let path = format!("/customers/{}/cards", self.customer_id);
-        self.client.get_with_params(&path, &params).await
+        self.client.get_list(&path, &params).await
+    }
+
+    /// Stream all of the customer's cards, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let customer = client.customer("cus_xxxxx");
+    /// let mut cards = customer.cards().list_all(ListParams::new().max_limit());
+    /// while let Some(card) = cards.try_next().await? {
+    ///     println!("{} ending in {}", card.brand, card.last4);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Card> {
+        let path = format!("/customers/{}/cards", self.customer_id);
+        ListStream::new(self.client, path, params)
+    }
+
+    /// Count the customer's cards by paging through all of them.
+    pub async fn count(&self) -> PayjpResult<usize> {
+        self.list_all(ListParams::new().max_limit())
+            .try_fold(0, |count, _| async move { Ok(count + 1) })
+            .await
     }
 }
 
 /// Response from deleting a card.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeletedCard {
-    /// Unique identifier for the card.
-    pub id: String,
+pub type DeletedCard = Deleted<Card>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn card(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "card",
+            "livemode": false,
+            "created": 1700000000,
+            "brand": "Visa",
+            "exp_month": 12,
+            "exp_year": 2030,
+            "last4": "4242"
+        })
+    }
 
-    /// Whether the deletion was successful.
-    pub deleted: bool,
+    #[tokio::test]
+    async fn test_count_pages_through_cards() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1/cards"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [card("car_3")],
+                "has_more": false,
+                "url": "/v1/customers/cus_1/cards",
+                "count": 1
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1/cards"))
+            .and(query_param("limit", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [card("car_1"), card("car_2")],
+                "has_more": true,
+                "url": "/v1/customers/cus_1/cards",
+                "count": 2
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let count = client.customer("cus_1").cards().count().await.expect("Failed to count cards");
+        assert_eq!(count, 3);
+    }
 
-    /// Whether this card was in live mode.
-    pub livemode: bool,
+    #[test]
+    fn test_months_until_expiry() {
+        let card: Card = serde_json::from_value(card("car_1")).unwrap();
+        let at = |date: &str| format!("{}T00:00:00Z", date).parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(card.months_until_expiry_at(at("2030-12-31")), 0);
+        assert_eq!(card.months_until_expiry_at(at("2030-10-01")), 2);
+        assert_eq!(card.months_until_expiry_at(at("2029-12-01")), 12);
+        assert_eq!(card.months_until_expiry_at(at("2031-01-01")), -1);
+    }
+
+    #[test]
+    fn test_three_d_secure_outcome() {
+        let outcome = ThreeDSecureOutcome::new("ch_1", Some(CardThreeDSecureStatus::Attempted), None);
+        assert!(matches!(outcome, ThreeDSecureOutcome::Attempted("ch_1")));
+        assert!(outcome.is_authenticated());
+
+        let outcome = ThreeDSecureOutcome::new(
+            "ch_2",
+            Some(CardThreeDSecureStatus::Error),
+            Some("three_d_secure_failed".to_string()),
+        );
+        assert!(!outcome.is_authenticated());
+        match outcome {
+            ThreeDSecureOutcome::Failed {
+                resource,
+                status,
+                code,
+            } => {
+                assert_eq!(resource, "ch_2");
+                assert_eq!(status, Some(CardThreeDSecureStatus::Error));
+                assert_eq!(code.as_deref(), Some("three_d_secure_failed"));
+            }
+            other => panic!("Expected Failed, got {:?}", other),
+        }
+
+        let outcome = ThreeDSecureOutcome::new("ch_3", None, None);
+        assert_eq!(outcome.into_resource(), "ch_3");
+    }
+
+    #[test]
+    fn test_clear_setters_serialize_empty_values() {
+        let params = UpdateCardParams::new().clear_name().clear_phone().expiration(12, 2030);
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "exp_month=12&exp_year=2030&name=&phone=");
+    }
 }
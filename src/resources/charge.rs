@@ -1,11 +1,23 @@
 //! Charge resource and service implementation.
 
+use crate::authorization::{self, Authorization};
 use crate::client::PayjpClient;
-use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
-use crate::resources::card::{Card, CardThreeDSecureStatus};
+use crate::error::{PayjpError, PayjpResult};
+use crate::export::{self, ExportFormat};
+use crate::pagination::ListStream;
+use crate::params::{impl_clear_setters, impl_metadata_setters, ListParams, Metadata, MAX_LIMIT};
+use crate::query::ChargeQuery;
+use crate::resources::{string_enum, ObjectKind};
+use crate::resources::card::{CardThreeDSecureStatus, ThreeDSecureOutcome};
+use crate::resources::customer::CardOrId;
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+use url::Url;
 
 /// A charge represents a payment against a card or customer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +26,7 @@ pub struct Charge {
     pub id: String,
 
     /// Object type (always "charge").
+    #[serde(deserialize_with = "Charge::deserialize_object")]
     pub object: String,
 
     /// Whether this charge was created in live mode.
@@ -39,8 +52,9 @@ pub struct Charge {
     pub captured_at: Option<i64>,
 
     /// Card used for this charge (optional).
+    /// Usually a full Card object, but may be only the card ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub card: Option<Card>,
+    pub card: Option<CardOrId>,
 
     /// Customer ID (if charge was made against a customer, optional).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -105,116 +119,392 @@ pub struct Charge {
     pub total_platform_fee: Option<i64>,
 }
 
-/// Parameters for creating a charge.
-#[derive(Debug, Default, Clone, Serialize)]
-pub struct CreateChargeParams {
-    /// Amount in the smallest currency unit (JPY: 50-9999999).
-    pub amount: i64,
+impl ObjectKind for Charge {
+    const OBJECT: &'static str = "charge";
+}
 
-    /// Three-letter ISO currency code (currently only "jpy" is supported).
-    pub currency: String,
+impl Charge {
+    /// Get the amount that can still be refunded.
+    pub fn refundable_amount(&self) -> i64 {
+        (self.amount - self.amount_refunded).max(0)
+    }
 
-    /// Card token ID (required if customer is not provided).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub card: Option<String>,
+    /// Compute the fees for this charge from the returned rates.
+    ///
+    /// Rates are percentages (e.g., `"3.00"`) and are applied to `amount` with
+    /// exact decimal arithmetic, truncating fractional yen. Platform fees returned
+    /// by the API are used as-is. Returns `None` if the charge has no `fee_rate`
+    /// or a rate cannot be parsed.
+    pub fn fee_breakdown(&self) -> Option<FeeBreakdown> {
+        let fee = apply_rate(self.amount, self.fee_rate.as_deref()?)?;
+        let rate_fee = match self.platform_fee_rate.as_deref() {
+            Some(rate) => Some(apply_rate(self.amount, rate)?),
+            None => None,
+        };
+        let total_platform_fee = match (self.total_platform_fee, rate_fee, self.platform_fee) {
+            (Some(total), _, _) => Some(total),
+            (None, None, None) => None,
+            (None, rate_fee, fixed) => Some(rate_fee.unwrap_or(0) + fixed.unwrap_or(0)),
+        };
+
+        Some(FeeBreakdown {
+            fee,
+            net: self.amount - fee,
+            platform_fee: self.platform_fee,
+            platform_fee_from_rate: rate_fee,
+            total_platform_fee,
+        })
+    }
 
-    /// Customer ID (required if card is not provided).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub customer: Option<String>,
+    /// Get the typed failure code and message, if the charge failed.
+    pub fn failure(&self) -> Option<ChargeFailure> {
+        let code = self.failure_code.as_deref()?;
+        Some(ChargeFailure {
+            code: FailureCode::from(code),
+            message: self.failure_message.clone(),
+        })
+    }
+}
 
-    /// Description of the charge.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+/// Why a charge failed, returned by [`Charge::failure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChargeFailure {
+    /// Failure code.
+    pub code: FailureCode,
 
-    /// Whether to immediately capture the charge (default: true).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub capture: Option<bool>,
+    /// Failure message (optional).
+    pub message: Option<String>,
+}
 
-    /// Number of days before uncaptured charge expires (1-60, default: 7).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub expiry_days: Option<i64>,
+string_enum! {
+    /// Documented failure code of a charge.
+    ///
+    /// Unknown codes are preserved as [`FailureCode::Other`] together with the raw
+    /// `failure_code` string.
+    #[non_exhaustive]
+    pub enum FailureCode {
+        /// Other failure codes not explicitly handled (raw `failure_code` string).
+        Other(String),
 
-    /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<Metadata>,
+        /// The card number is invalid.
+        InvalidNumber => "invalid_number",
+
+        /// The card security code (CVC) is invalid.
+        InvalidCvc => "invalid_cvc",
+
+        /// The card expiration date is invalid.
+        InvalidExpirationDate => "invalid_expiration_date",
+
+        /// The card expiration month is invalid.
+        InvalidExpiryMonth => "invalid_expiry_month",
+
+        /// The card expiration year is invalid.
+        InvalidExpiryYear => "invalid_expiry_year",
+
+        /// The card has expired.
+        ExpiredCard => "expired_card",
+
+        /// The card details do not match the issuer's records.
+        IncorrectCardData => "incorrect_card_data",
+
+        /// The card does not have enough funds or credit available.
+        InsufficientFunds => "insufficient_funds",
+
+        /// The card was declined by the issuer.
+        CardDeclined => "card_declined",
+
+        /// The card is temporarily blocked after repeated declines.
+        CardFlagged => "card_flagged",
+
+        /// A temporary error occurred while processing the card.
+        ProcessingError => "processing_error",
+
+        /// The card brand is not accepted by the account.
+        UnacceptableBrand => "unacceptable_brand",
+
+        /// 3D Secure authentication was not completed.
+        ThreeDSecureIncompleted => "three_d_secure_incompleted",
+
+        /// 3D Secure authentication failed.
+        ThreeDSecureFailed => "three_d_secure_failed",
+    }
+}
+
+impl FailureCode {
+    /// Whether the same card may succeed if the charge is retried later
+    /// (insufficient funds or a temporary processing error).
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, FailureCode::InsufficientFunds | FailureCode::ProcessingError)
+    }
+
+    /// Whether the customer needs to provide a different card or fix its details.
+    pub fn requires_new_card(&self) -> bool {
+        matches!(
+            self,
+            FailureCode::InvalidNumber
+                | FailureCode::InvalidCvc
+                | FailureCode::InvalidExpirationDate
+                | FailureCode::InvalidExpiryMonth
+                | FailureCode::InvalidExpiryYear
+                | FailureCode::ExpiredCard
+                | FailureCode::IncorrectCardData
+                | FailureCode::UnacceptableBrand
+        )
+    }
+}
+
+/// Fees for a charge, computed by [`Charge::fee_breakdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    /// PAY.JP fee (`amount` × `fee_rate`).
+    pub fee: i64,
+
+    /// Amount after the PAY.JP fee.
+    pub net: i64,
 
-    /// Whether to use 3D Secure authentication.
+    /// Platform API: Fixed platform fee set on the charge (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub three_d_secure: Option<bool>,
+    pub platform_fee: Option<i64>,
 
-    /// Platform API: Tenant ID.
+    /// Platform API: Platform fee from `platform_fee_rate` (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tenant: Option<String>,
+    pub platform_fee_from_rate: Option<i64>,
 
-    /// Platform API: Platform fee amount.
+    /// Platform API: Total platform fee, as returned by the API or computed from
+    /// the fixed fee and the rate (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub platform_fee: Option<i64>,
+    pub total_platform_fee: Option<i64>,
 }
 
-impl CreateChargeParams {
-    /// Create new charge parameters with an amount and currency.
-    pub fn new(amount: i64, currency: impl Into<String>) -> Self {
-        Self {
-            amount,
-            currency: currency.into(),
-            ..Default::default()
-        }
+/// Apply a percentage rate such as `"3.25"` to an amount, truncating fractional yen.
+fn apply_rate(amount: i64, rate: &str) -> Option<i64> {
+    let (whole, fraction) = rate.trim().split_once('.').unwrap_or((rate.trim(), ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || fraction.len() > 18 {
+        return None;
     }
+    let scale = 10i128.pow(fraction.len() as u32);
+    let rate = whole.parse::<i128>().ok()? * scale + fraction.parse::<i128>().unwrap_or(0);
+    let fee = i128::from(amount) * rate / (100 * scale);
+    i64::try_from(fee).ok()
+}
 
-    /// Set the card token to charge.
-    pub fn card(mut self, card: impl Into<String>) -> Self {
-        self.card = Some(card.into());
-        self
-    }
+/// Declare a create-charge params struct and its setters, with string fields of
+/// type `$str` and metadata of type `$metadata`.
+///
+/// [`CreateChargeParams`] and [`CreateChargeParamsRef`] share this field list, so
+/// a field added to one cannot be forgotten in the other.
+macro_rules! create_charge_params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident $(<$lt:lifetime>)? {
+            string: $str:ty,
+            $(#[$metadata_meta:meta])*
+            metadata: $metadata:ty,
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Serialize)]
+        pub struct $name $(<$lt>)? {
+            /// Amount in the smallest currency unit (JPY: 50-9999999).
+            pub amount: i64,
+
+            /// Three-letter ISO currency code (currently only "jpy" is supported).
+            pub currency: $str,
+
+            /// Card token ID (required if customer is not provided).
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub card: Option<$str>,
+
+            /// Customer ID (required if card is not provided).
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub customer: Option<$str>,
+
+            /// Description of the charge.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub description: Option<$str>,
+
+            /// Whether to immediately capture the charge (default: true).
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub capture: Option<bool>,
+
+            /// Number of days before uncaptured charge expires (1-60, default: 7).
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub expiry_days: Option<i64>,
+
+            $(#[$metadata_meta])*
+            pub metadata: $metadata,
+
+            /// Whether to use 3D Secure authentication.
+            ///
+            /// The created charge awaits authentication; redirect the customer to
+            /// [`ChargeService::three_d_secure_url`] and finish with
+            /// [`ChargeService::tds_finish`].
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub three_d_secure: Option<bool>,
+
+            /// Platform API: Tenant ID, set through `PlatformClient::tenant`.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub(crate) tenant: Option<$str>,
+
+            /// Platform API: Platform fee amount, set through `PlatformClient::tenant`.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub(crate) platform_fee: Option<i64>,
+        }
 
-    /// Set the customer to charge.
-    pub fn customer(mut self, customer: impl Into<String>) -> Self {
-        self.customer = Some(customer.into());
-        self
-    }
+        impl $(<$lt>)? $name $(<$lt>)? {
+            /// Create new charge parameters with an amount and currency.
+            pub fn new(amount: i64, currency: impl Into<$str>) -> Self {
+                Self {
+                    amount,
+                    currency: currency.into(),
+                    ..Default::default()
+                }
+            }
+
+            /// Set the card token to charge.
+            pub fn card(mut self, card: impl Into<$str>) -> Self {
+                self.card = Some(card.into());
+                self
+            }
+
+            /// Set the customer to charge.
+            pub fn customer(mut self, customer: impl Into<$str>) -> Self {
+                self.customer = Some(customer.into());
+                self
+            }
+
+            /// Set the description.
+            pub fn description(mut self, description: impl Into<$str>) -> Self {
+                self.description = Some(description.into());
+                self
+            }
+
+            /// Set whether to capture immediately.
+            pub fn capture(mut self, capture: bool) -> Self {
+                self.capture = Some(capture);
+                self
+            }
+
+            /// Set the number of days before expiration for uncaptured charges.
+            pub fn expiry_days(mut self, days: i64) -> Self {
+                self.expiry_days = Some(days);
+                self
+            }
+
+            /// Enable 3D Secure authentication.
+            pub fn three_d_secure(mut self, enabled: bool) -> Self {
+                self.three_d_secure = Some(enabled);
+                self
+            }
+        }
+    };
+}
 
-    /// Set the description.
-    pub fn description(mut self, description: impl Into<String>) -> Self {
-        self.description = Some(description.into());
-        self
+create_charge_params! {
+    /// Parameters for creating a charge.
+    pub struct CreateChargeParams {
+        string: String,
+        /// Set of key-value pairs for storing additional information.
+        #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
+        metadata: Option<Metadata>,
     }
+}
 
-    /// Set whether to capture immediately.
-    pub fn capture(mut self, capture: bool) -> Self {
-        self.capture = Some(capture);
-        self
-    }
+impl_metadata_setters!(CreateChargeParams, "charge");
 
-    /// Set the number of days before expiration for uncaptured charges.
-    pub fn expiry_days(mut self, days: i64) -> Self {
-        self.expiry_days = Some(days);
-        self
+create_charge_params! {
+    /// Borrowed parameters for creating a charge.
+    ///
+    /// The same fields as [`CreateChargeParams`], but string fields are
+    /// [`Cow`]s, so IDs and descriptions the caller already holds are sent without
+    /// being copied into new `String`s. Use with [`ChargeService::create_borrowed`]
+    /// when creating charges at high volume.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{CreateChargeParamsRef, PayjpClient};
+    /// # async fn example(
+    /// #     client: &PayjpClient,
+    /// #     orders: &[(String, i64, String)],
+    /// # ) -> payjp::PayjpResult<()> {
+    /// for (customer, amount, order_id) in orders {
+    ///     let params = CreateChargeParamsRef::new(*amount, "jpy")
+    ///         .customer(customer.as_str())
+    ///         .metadata("order_id", order_id.as_str());
+    ///     client.charges().create_borrowed(&params).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub struct CreateChargeParamsRef<'a> {
+        string: Cow<'a, str>,
+        /// Key-value pairs for storing additional information.
+        #[serde(flatten, serialize_with = "crate::params::serialize_metadata_pairs")]
+        metadata: Vec<(Cow<'a, str>, Cow<'a, str>)>,
     }
+}
 
+impl<'a> CreateChargeParamsRef<'a> {
     /// Add metadata to the charge.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
+    pub fn metadata(
+        mut self,
+        key: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.metadata.push((key.into(), value.into()));
         self
     }
 
-    /// Enable 3D Secure authentication.
-    pub fn three_d_secure(mut self, enabled: bool) -> Self {
-        self.three_d_secure = Some(enabled);
+    /// Add every key-value pair of `metadata` to the charge.
+    pub fn metadata_from<K, V>(mut self, metadata: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        self.extend(metadata);
         self
     }
 
-    /// Set platform fee (Platform API).
-    pub fn platform_fee(mut self, fee: i64) -> Self {
-        self.platform_fee = Some(fee);
-        self
+    /// Add every entry of `metadata` to the charge.
+    pub fn metadata_map(self, metadata: Metadata) -> Self {
+        self.metadata_from(metadata)
     }
+}
 
-    /// Set tenant (Platform API).
-    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
-        self.tenant = Some(tenant.into());
-        self
+/// Extending the params adds metadata, like [`CreateChargeParamsRef::metadata_from`].
+impl<'a, K, V> Extend<(K, V)> for CreateChargeParamsRef<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: Into<Cow<'a, str>>,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.metadata
+            .extend(iter.into_iter().map(|(key, value)| (key.into(), value.into())));
+    }
+}
+
+impl<'a> From<&'a CreateChargeParams> for CreateChargeParamsRef<'a> {
+    fn from(params: &'a CreateChargeParams) -> Self {
+        Self {
+            amount: params.amount,
+            currency: Cow::Borrowed(&params.currency),
+            card: params.card.as_deref().map(Cow::Borrowed),
+            customer: params.customer.as_deref().map(Cow::Borrowed),
+            description: params.description.as_deref().map(Cow::Borrowed),
+            capture: params.capture,
+            expiry_days: params.expiry_days,
+            metadata: params
+                .metadata
+                .iter()
+                .flatten()
+                .map(|(key, value)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(value.as_str())))
+                .collect(),
+            three_d_secure: params.three_d_secure,
+            tenant: params.tenant.as_deref().map(Cow::Borrowed),
+            platform_fee: params.platform_fee,
+        }
     }
 }
 
@@ -226,7 +516,7 @@ pub struct UpdateChargeParams {
     pub description: Option<String>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -241,16 +531,14 @@ impl UpdateChargeParams {
         self.description = Some(description.into());
         self
     }
-
-    /// Add metadata to the charge.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_clear_setters!(UpdateChargeParams, "charge", {
+    clear_description => description,
+});
+
+impl_metadata_setters!(UpdateChargeParams, "charge", update);
+
 /// Parameters for refunding a charge.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct RefundParams {
@@ -324,6 +612,36 @@ impl ReauthParams {
     }
 }
 
+/// Parameters for the 3D Secure redirect of a charge created with `three_d_secure`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChargeThreeDSecureParams {
+    /// Public key (`pk_...`) identifying the account to the authentication page.
+    #[serde(rename = "publickey")]
+    pub public_key: String,
+
+    /// Name of the redirect destination registered in the dashboard (optional).
+    ///
+    /// The customer is sent back to this destination once authentication finishes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub back: Option<String>,
+}
+
+impl ChargeThreeDSecureParams {
+    /// Create new 3D Secure redirect parameters.
+    pub fn new(public_key: impl Into<String>) -> Self {
+        Self {
+            public_key: public_key.into(),
+            back: None,
+        }
+    }
+
+    /// Set the registered redirect destination to return to.
+    pub fn back(mut self, back: impl Into<String>) -> Self {
+        self.back = Some(back.into());
+        self
+    }
+}
+
 /// Parameters for listing charges.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct ListChargeParams {
@@ -351,9 +669,13 @@ pub struct ListChargeParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subscription: Option<String>,
 
-    /// Filter by tenant ID (Platform API).
+    /// Filter by tenant ID, set by [`TenantChargeService`] (Platform API).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tenant: Option<String>,
+    pub(crate) tenant: Option<String>,
+
+    /// Filter by term ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
 }
 
 impl From<ListParams> for ListChargeParams {
@@ -380,6 +702,12 @@ impl ListChargeParams {
         self
     }
 
+    /// Request the largest page size the API allows ([`MAX_LIMIT`]).
+    pub fn max_limit(mut self) -> Self {
+        self.limit = Some(MAX_LIMIT);
+        self
+    }
+
     /// Set the offset for pagination.
     pub fn offset(mut self, offset: i64) -> Self {
         self.offset = Some(offset);
@@ -397,6 +725,12 @@ impl ListChargeParams {
         self.subscription = Some(subscription.into());
         self
     }
+
+    /// Filter by term ID.
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.term = Some(term.into());
+        self
+    }
 }
 
 /// Service for managing charges.
@@ -427,7 +761,36 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn create(&self, params: CreateChargeParams) -> PayjpResult<Charge> {
-        self.client.post("/charges", &params).await
+        self.client.create::<Charge>(params).await
+    }
+
+    /// Create a new charge from borrowed parameters.
+    ///
+    /// Behaves like [`create`](Self::create), without requiring owned strings.
+    pub async fn create_borrowed(&self, params: &CreateChargeParamsRef<'_>) -> PayjpResult<Charge> {
+        self.client.verify_token(params.card.as_deref()).await?;
+        self.client.post("/charges", params).await
+    }
+
+    /// Create an uncaptured charge and return an [`Authorization`] helper for it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, CreateChargeParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut auth = client.charges().authorize(
+    ///     CreateChargeParams::new(5000, "jpy")
+    ///         .card("tok_xxxxx")
+    ///         .expiry_days(7)
+    /// ).await?;
+    /// auth.capture(5000).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn authorize(&self, params: CreateChargeParams) -> PayjpResult<Authorization<'a>> {
+        Authorization::create(self.client, params).await
     }
 
     /// Retrieve a charge by ID.
@@ -443,8 +806,34 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, charge_id: &str) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}", charge_id);
-        self.client.get(&path).await
+        self.client.retrieve(charge_id).await
+    }
+
+    /// Retrieve many charges by ID, with at most `concurrency` requests in flight.
+    ///
+    /// Repeated IDs are retrieved once; see [`PayjpClient::retrieve_many`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charges = client.charges().retrieve_many(["ch_1", "ch_2"], 4).await;
+    /// let first = charges["ch_1"].as_ref();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve_many<I>(
+        &self,
+        ids: I,
+        concurrency: usize,
+    ) -> HashMap<String, PayjpResult<Charge>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.client.retrieve_many(ids, concurrency).await
     }
 
     /// Update a charge.
@@ -463,8 +852,7 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn update(&self, charge_id: &str, params: UpdateChargeParams) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}", charge_id);
-        self.client.post(&path, &params).await
+        self.client.update_resource(charge_id, &params).await
     }
 
     /// Capture a previously authorized charge.
@@ -480,8 +868,7 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn capture(&self, charge_id: &str, params: CaptureParams) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}/capture", charge_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(charge_id, "capture", &params).await
     }
 
     /// Refund a charge.
@@ -500,8 +887,7 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn refund(&self, charge_id: &str, params: RefundParams) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}/refund", charge_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(charge_id, "refund", &params).await
     }
 
     /// Re-authorize a charge (extend expiration for uncaptured charge).
@@ -517,8 +903,7 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn reauth(&self, charge_id: &str, params: ReauthParams) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}/reauth", charge_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(charge_id, "reauth", &params).await
     }
 
     /// Finish 3D Secure authentication for a charge.
@@ -534,8 +919,89 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn tds_finish(&self, charge_id: &str) -> PayjpResult<Charge> {
-        let path = format!("/charges/{}/tds_finish", charge_id);
-        self.client.post(&path, &serde_json::json!({})).await
+        self.client.resource_action(charge_id, "tds_finish", &serde_json::json!({})).await
+    }
+
+    /// Finish 3D Secure authentication for a charge and classify the result.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ThreeDSecureOutcome};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// match client.charges().tds_finish_outcome("ch_xxxxx").await? {
+    ///     ThreeDSecureOutcome::Verified(charge) | ThreeDSecureOutcome::Attempted(charge) => {
+    ///         println!("charge {} authenticated", charge.id);
+    ///     }
+    ///     ThreeDSecureOutcome::Failed { resource, status, code } => {
+    ///         println!("charge {} failed 3DS: {:?} {:?}", resource.id, status, code);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tds_finish_outcome(
+        &self,
+        charge_id: &str,
+    ) -> PayjpResult<ThreeDSecureOutcome<Charge>> {
+        let charge = self.tds_finish(charge_id).await?;
+        let status = charge.three_d_secure_status.clone().or_else(|| {
+            charge
+                .card
+                .as_ref()
+                .and_then(CardOrId::as_card)
+                .and_then(|card| card.three_d_secure_status.clone())
+        });
+        let code = charge.failure_code.clone();
+        Ok(ThreeDSecureOutcome::new(charge, status, code))
+    }
+
+    /// Build the URL to redirect the customer to for 3D Secure authentication of a charge.
+    ///
+    /// The charge must have been created with `three_d_secure(true)` and still be
+    /// awaiting authentication. After the customer returns, call
+    /// [`tds_finish`](Self::tds_finish) to complete the charge.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ChargeThreeDSecureParams, CreateChargeParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charge = client.charges().create(
+    ///     CreateChargeParams::new(1000, "jpy")
+    ///         .card("tok_xxxxx")
+    ///         .three_d_secure(true)
+    /// ).await?;
+    /// let url = client.charges().three_d_secure_url(
+    ///     &charge,
+    ///     &ChargeThreeDSecureParams::new("pk_test_xxxxx").back("shop"),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn three_d_secure_url(
+        &self,
+        charge: &Charge,
+        params: &ChargeThreeDSecureParams,
+    ) -> PayjpResult<Url> {
+        if charge.three_d_secure_status != Some(CardThreeDSecureStatus::Unverified) {
+            return Err(PayjpError::InvalidRequest(format!(
+                "Charge {} is not awaiting 3D Secure authentication",
+                charge.id
+            )));
+        }
+
+        let mut url = Url::parse(&format!(
+            "{}/tds/{}/start",
+            self.client.base_url().trim_end_matches('/'),
+            charge.id
+        ))?;
+        let query = serde_urlencoded::to_string(params)
+            .map_err(|e| PayjpError::InvalidRequest(format!("Failed to encode query: {}", e)))?;
+        url.set_query(Some(&query));
+        Ok(url)
     }
 
     /// List all charges.
@@ -553,6 +1019,368 @@ impl<'a> ChargeService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListChargeParams) -> PayjpResult<ListResponse<Charge>> {
-        self.client.get_with_params("/charges", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// List all charges, automatically fetching further pages as the stream is consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListChargeParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut charges = client.charges().list_all(
+    ///     ListChargeParams::new().limit(100).customer("cus_xxxxx")
+    /// );
+    /// while let Some(charge) = charges.try_next().await? {
+    ///     println!("{}: {}", charge.id, charge.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListChargeParams) -> ListStream<'a, Charge> {
+        self.client.stream_resource(params)
+    }
+
+    /// Stream the charges matching `query`.
+    ///
+    /// See the [`query`](crate::query) module for which conditions are
+    /// filtered server-side.
+    pub fn query(&self, query: ChargeQuery) -> ListStream<'a, Charge> {
+        query.run(|params| self.list_all(params))
+    }
+
+    /// Stream uncaptured charges whose authorization expires within `window`.
+    ///
+    /// Already expired, captured and voided charges are skipped. To be notified
+    /// about the charges or re-authorize them, use
+    /// [`ExpiryTracker`](crate::authorization::ExpiryTracker).
+    pub fn expiring(&self, window: Duration) -> ListStream<'a, Charge> {
+        authorization::expiring(self.client, window, chrono::Utc::now().timestamp())
+    }
+
+    /// Export charges created within `range` (Unix timestamps) to `writer`.
+    ///
+    /// Pages are fetched at the maximum page size and written as they arrive, so
+    /// memory use stays flat for large exports. Returns the number of charges
+    /// written. See the [`export`](crate::export) module for an example.
+    pub async fn export<W>(
+        &self,
+        range: impl RangeBounds<i64>,
+        writer: &mut W,
+        format: ExportFormat,
+    ) -> PayjpResult<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let (since, until) = export::created_bounds(range);
+        let params = ListChargeParams {
+            since,
+            until,
+            ..ListChargeParams::new().max_limit()
+        };
+        self.list_all(params).export(writer, format).await
+    }
+}
+
+/// Service for managing the charges of a specific customer.
+pub struct CustomerChargeService<'a> {
+    client: &'a PayjpClient,
+    customer_id: String,
+}
+
+impl<'a> CustomerChargeService<'a> {
+    /// Create a new charge service for a specific customer.
+    pub(crate) fn new(client: &'a PayjpClient, customer_id: String) -> Self {
+        Self {
+            client,
+            customer_id,
+        }
+    }
+
+    /// Charge the customer.
+    ///
+    /// The `customer` field of `params` is set to this customer's ID. Without a
+    /// `card`, the customer's default card is charged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, CreateChargeParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charge = client.customer("cus_xxxxx")
+    ///     .charges()
+    ///     .create(CreateChargeParams::new(1000, "jpy").description("Monthly fee"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create(&self, mut params: CreateChargeParams) -> PayjpResult<Charge> {
+        params.customer = Some(self.customer_id.clone());
+        self.client.create::<Charge>(params).await
+    }
+
+    /// List the customer's charges.
+    ///
+    /// The `customer` filter of `params` is set to this customer's ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListChargeParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charges = client.customer("cus_xxxxx").charges().list(
+    ///     ListChargeParams::new().limit(10)
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(&self, params: ListChargeParams) -> PayjpResult<ListResponse<Charge>> {
+        self.client
+            .list_resource(&params.customer(self.customer_id.as_str()))
+            .await
+    }
+
+    /// Stream all of the customer's charges, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListChargeParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let customer = client.customer("cus_xxxxx");
+    /// let mut charges = customer.charges().list_all(ListChargeParams::new().max_limit());
+    /// while let Some(charge) = charges.try_next().await? {
+    ///     println!("{}: {}", charge.id, charge.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListChargeParams) -> ListStream<'a, Charge> {
+        self.client
+            .stream_resource(params.customer(self.customer_id.as_str()))
+    }
+}
+
+/// Service for charging on behalf of a specific tenant (Platform API).
+#[cfg(feature = "platform")]
+pub struct TenantChargeService<'a> {
+    client: &'a PayjpClient,
+    tenant_id: String,
+}
+
+#[cfg(feature = "platform")]
+impl<'a> TenantChargeService<'a> {
+    /// Create a new charge service for a specific tenant.
+    pub(crate) fn new(client: &'a PayjpClient, tenant_id: String) -> Self {
+        Self { client, tenant_id }
+    }
+
+    /// Charge on behalf of the tenant, with the platform fee derived from the
+    /// tenant's `platform_fee_rate`.
+    ///
+    /// The `tenant` field of `params` is set to this tenant's ID.
+    pub async fn create(&self, mut params: CreateChargeParams) -> PayjpResult<Charge> {
+        params.tenant = Some(self.tenant_id.clone());
+        params.platform_fee = None;
+        self.client.charges().create(params).await
+    }
+
+    /// Charge on behalf of the tenant with a fixed platform fee (in addition to any
+    /// fee from the tenant's `platform_fee_rate`).
+    pub async fn create_with_fee(
+        &self,
+        mut params: CreateChargeParams,
+        platform_fee: i64,
+    ) -> PayjpResult<Charge> {
+        params.tenant = Some(self.tenant_id.clone());
+        params.platform_fee = Some(platform_fee);
+        self.client.charges().create(params).await
+    }
+
+    /// List the tenant's charges.
+    ///
+    /// The `tenant` filter of `params` is set to this tenant's ID.
+    pub async fn list(&self, mut params: ListChargeParams) -> PayjpResult<ListResponse<Charge>> {
+        params.tenant = Some(self.tenant_id.clone());
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all of the tenant's charges, fetching pages on demand.
+    pub fn list_all(&self, mut params: ListChargeParams) -> ListStream<'a, Charge> {
+        params.tenant = Some(self.tenant_id.clone());
+        self.client.stream_resource(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn charge(fee_rate: &str, platform: serde_json::Value) -> Charge {
+        let charge = serde_json::json!({ "amount": 1999, "fee_rate": fee_rate });
+        let charge = fixtures::with(fixtures::charge("ch_1"), charge);
+        serde_json::from_value(fixtures::with(charge, platform)).unwrap()
+    }
+
+    #[test]
+    fn test_card_may_be_id_or_object() {
+        let card = charge("3.00", serde_json::json!({"card": "car_1"}))
+            .card
+            .unwrap();
+        assert_eq!(card.id(), "car_1");
+        assert!(card.as_card().is_none());
+
+        let card = charge(
+            "3.00",
+            serde_json::json!({"card": {
+                "id": "car_2",
+                "object": "card",
+                "livemode": false,
+                "created": 1704067200,
+                "brand": "Visa",
+                "exp_month": 12,
+                "exp_year": 2030,
+                "last4": "4242"
+            }}),
+        )
+        .card
+        .unwrap();
+        assert_eq!(card.id(), "car_2");
+        assert_eq!(card.into_card().unwrap().last4, "4242");
+    }
+
+    #[test]
+    fn test_failure_maps_documented_codes() {
+        let failure = charge(
+            "3.00",
+            serde_json::json!({"failure_code": "expired_card", "failure_message": "Card expired"}),
+        )
+        .failure()
+        .unwrap();
+        assert_eq!(failure.code, FailureCode::ExpiredCard);
+        assert_eq!(failure.message.as_deref(), Some("Card expired"));
+        assert!(failure.code.requires_new_card());
+        assert!(!failure.code.is_retryable());
+
+        assert!(FailureCode::from("insufficient_funds").is_retryable());
+        let unknown = FailureCode::from("new_code".to_string());
+        assert_eq!(unknown, FailureCode::Other("new_code".to_string()));
+        assert_eq!(unknown.as_str(), "new_code");
+
+        assert!(charge("3.00", serde_json::json!({})).failure().is_none());
+    }
+
+    #[test]
+    fn test_fee_breakdown_truncates_fractional_yen() {
+        let fees = charge("3.25", serde_json::json!({})).fee_breakdown().unwrap();
+        assert_eq!(fees.fee, 64);
+        assert_eq!(fees.net, 1935);
+        assert_eq!(fees.total_platform_fee, None);
+
+        let fees = charge(
+            "3.00",
+            serde_json::json!({"platform_fee": 100, "platform_fee_rate": "10.15"}),
+        )
+        .fee_breakdown()
+        .unwrap();
+        assert_eq!(fees.fee, 59);
+        assert_eq!(fees.platform_fee_from_rate, Some(202));
+        assert_eq!(fees.total_platform_fee, Some(302));
+
+        assert!(charge("3.x", serde_json::json!({})).fee_breakdown().is_none());
+    }
+
+    #[test]
+    fn test_borrowed_params_encode_like_owned() {
+        let owned = CreateChargeParams::new(1000, "jpy")
+            .customer("cus_xxxxx")
+            .description("Order 1234")
+            .metadata("order_id", "1234")
+            .metadata("channel", "web");
+        let order_id = String::from("1234");
+        let borrowed = CreateChargeParamsRef::new(1000, "jpy")
+            .customer("cus_xxxxx")
+            .description("Order 1234")
+            .metadata("order_id", order_id.as_str())
+            .metadata("channel", "web");
+
+        let expected = serde_urlencoded::to_string(&owned).unwrap();
+        assert_eq!(serde_urlencoded::to_string(&borrowed).unwrap(), expected);
+        assert_eq!(
+            serde_urlencoded::to_string(CreateChargeParamsRef::from(&owned)).unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_customer_charges_are_scoped_to_customer() {
+        let body = serde_json::json!({
+            "id": "ch_1",
+            "object": "charge",
+            "livemode": false,
+            "created": 1704067200,
+            "amount": 1000,
+            "currency": "jpy",
+            "paid": true,
+            "captured": true,
+            "refunded": false,
+            "amount_refunded": 0,
+            "customer": "cus_1"
+        });
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("customer", "cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [body.clone()],
+                "has_more": false,
+                "url": "/v1/charges",
+                "count": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/charges"))
+            .and(body_string_contains("customer=cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let charges = client.customer("cus_1").charges();
+
+        let listed = charges.list(ListChargeParams::new()).await.unwrap();
+        assert_eq!(listed.data[0].id, "ch_1");
+        let created = charges
+            .create(CreateChargeParams::new(1000, "jpy"))
+            .await
+            .unwrap();
+        assert_eq!(created.customer.as_deref(), Some("cus_1"));
+    }
+
+    #[test]
+    fn test_clear_description_serializes_empty_value() {
+        let params = UpdateChargeParams::new().clear_description();
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "description=");
     }
 }
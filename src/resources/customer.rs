@@ -2,10 +2,20 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
+use crate::merge::CustomerMerge;
+use crate::pagination::ListStream;
+use crate::params::{impl_clear_setters, impl_metadata_setters, ListParams, Metadata};
+use crate::query::CustomerQuery;
+use crate::resources::ObjectKind;
 use crate::resources::card::{Card, CardService};
-use crate::response::ListResponse;
+use crate::resources::charge::CustomerChargeService;
+#[cfg(feature = "subscriptions")]
+use crate::resources::subscription::CustomerSubscriptionService;
+use crate::response::{Deleted, ListResponse};
+use chrono::Utc;
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents either a Card object or a card ID string.
 ///
@@ -15,11 +25,43 @@ use serde::{Deserialize, Serialize};
 #[serde(untagged)]
 pub enum CardOrId {
     /// Full Card object (when expanded).
-    Card(Card),
+    Card(Box<Card>),
     /// Card ID string.
     Id(String),
 }
 
+impl CardOrId {
+    /// Get the card ID, whether or not the card is expanded.
+    pub fn id(&self) -> &str {
+        match self {
+            CardOrId::Card(card) => &card.id,
+            CardOrId::Id(id) => id,
+        }
+    }
+
+    /// Get the card if it is expanded.
+    pub fn as_card(&self) -> Option<&Card> {
+        match self {
+            CardOrId::Card(card) => Some(card),
+            CardOrId::Id(_) => None,
+        }
+    }
+
+    /// Consume and return the card if it is expanded.
+    pub fn into_card(self) -> Option<Card> {
+        match self {
+            CardOrId::Card(card) => Some(*card),
+            CardOrId::Id(_) => None,
+        }
+    }
+}
+
+impl From<Card> for CardOrId {
+    fn from(card: Card) -> Self {
+        CardOrId::Card(Box::new(card))
+    }
+}
+
 /// A customer represents a buyer who can be charged multiple times.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Customer {
@@ -27,6 +69,7 @@ pub struct Customer {
     pub id: String,
 
     /// Object type (always "customer").
+    #[serde(deserialize_with = "Customer::deserialize_object")]
     pub object: String,
 
     /// Whether this customer was created in live mode.
@@ -53,6 +96,7 @@ pub struct Customer {
     pub metadata: Option<Metadata>,
 
     /// List of subscriptions for this customer (optional).
+    #[cfg(feature = "subscriptions")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subscriptions: Option<ListResponse<crate::resources::subscription::Subscription>>,
 
@@ -61,6 +105,10 @@ pub struct Customer {
     pub cards: Option<ListResponse<Card>>,
 }
 
+impl ObjectKind for Customer {
+    const OBJECT: &'static str = "customer";
+}
+
 /// Parameters for creating a customer.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct CreateCustomerParams {
@@ -77,7 +125,7 @@ pub struct CreateCustomerParams {
     pub card: Option<String>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -104,16 +152,10 @@ impl CreateCustomerParams {
         self.card = Some(card.into());
         self
     }
-
-    /// Add metadata to the customer.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_metadata_setters!(CreateCustomerParams, "customer");
+
 /// Parameters for updating a customer.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct UpdateCustomerParams {
@@ -130,7 +172,7 @@ pub struct UpdateCustomerParams {
     pub default_card: Option<String>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -157,27 +199,29 @@ impl UpdateCustomerParams {
         self.default_card = Some(card.into());
         self
     }
-
-    /// Add metadata to the customer.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
-/// Response from deleting a customer.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeletedCustomer {
-    /// Customer ID.
-    pub id: String,
+impl_clear_setters!(UpdateCustomerParams, "customer", {
+    clear_email => email,
+    clear_description => description,
+});
 
-    /// Whether the deletion was successful.
-    pub deleted: bool,
+impl_metadata_setters!(UpdateCustomerParams, "customer", update);
 
-    /// Whether this customer was in live mode.
-    pub livemode: bool,
+/// Response from deleting a customer.
+pub type DeletedCustomer = Deleted<Customer>;
+
+/// A customer and those of their cards that expire soon.
+///
+/// Yielded by [`CustomerService::expiring_cards`].
+#[derive(Debug, Clone)]
+pub struct ExpiringCards {
+    /// The customer.
+    pub customer: Customer,
+
+    /// The customer's cards that expire within the requested window, or have
+    /// already expired.
+    pub cards: Vec<Card>,
 }
 
 /// Service for managing customers.
@@ -208,7 +252,7 @@ impl<'a> CustomerService<'a> {
     /// # }
     /// ```
     pub async fn create(&self, params: CreateCustomerParams) -> PayjpResult<Customer> {
-        self.client.post("/customers", &params).await
+        self.client.create::<Customer>(params).await
     }
 
     /// Retrieve a customer by ID.
@@ -224,8 +268,33 @@ impl<'a> CustomerService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, customer_id: &str) -> PayjpResult<Customer> {
-        let path = format!("/customers/{}", customer_id);
-        self.client.get(&path).await
+        self.client.retrieve(customer_id).await
+    }
+
+    /// Retrieve many customers by ID, with at most `concurrency` requests in flight.
+    ///
+    /// Repeated IDs are retrieved once; see [`PayjpClient::retrieve_many`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let customers = client.customers().retrieve_many(["cus_1", "cus_2"], 4).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve_many<I>(
+        &self,
+        ids: I,
+        concurrency: usize,
+    ) -> HashMap<String, PayjpResult<Customer>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.client.retrieve_many(ids, concurrency).await
     }
 
     /// Update a customer.
@@ -248,8 +317,7 @@ impl<'a> CustomerService<'a> {
         customer_id: &str,
         params: UpdateCustomerParams,
     ) -> PayjpResult<Customer> {
-        let path = format!("/customers/{}", customer_id);
-        self.client.post(&path, &params).await
+        self.client.update_resource(customer_id, &params).await
     }
 
     /// Delete a customer.
@@ -265,8 +333,7 @@ impl<'a> CustomerService<'a> {
     /// # }
     /// ```
     pub async fn delete(&self, customer_id: &str) -> PayjpResult<DeletedCustomer> {
-        let path = format!("/customers/{}", customer_id);
-        self.client.delete(&path).await
+        self.client.delete_resource(customer_id).await
     }
 
     /// List all customers.
@@ -284,7 +351,140 @@ impl<'a> CustomerService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Customer>> {
-        self.client.get_with_params("/customers", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all customers, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut customers = client.customers().list_all(ListParams::new().max_limit());
+    /// while let Some(customer) = customers.try_next().await? {
+    ///     println!("{}", customer.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Customer> {
+        self.client.stream_resource(params)
+    }
+
+    /// Stream the customers matching `query`.
+    ///
+    /// See the [`query`](crate::query) module for which conditions are
+    /// filtered server-side.
+    pub fn query(&self, query: CustomerQuery) -> ListStream<'a, Customer> {
+        query.run(|params| self.list_all(params))
+    }
+
+    /// Stream customers whose email matches `email` (case-insensitive).
+    ///
+    /// PAY.JP has no server-side email filter, so this pages through all customers.
+    /// Stop polling the stream to end the scan early, or use
+    /// [`find_first_by_email`](Self::find_first_by_email).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let matches: Vec<_> = client
+    ///     .customers()
+    ///     .find_by_email("buyer@example.com")
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_by_email(&self, email: &str) -> ListStream<'a, Customer> {
+        let email = email.trim().to_lowercase();
+        self.list_all(ListParams::new().max_limit())
+            .filter_local(move |customer| {
+                customer
+                    .email
+                    .as_deref()
+                    .is_some_and(|e| e.trim().to_lowercase() == email)
+            })
+    }
+
+    /// Find the most recently created customer whose email matches `email`,
+    /// stopping the scan at the first match.
+    pub async fn find_first_by_email(&self, email: &str) -> PayjpResult<Option<Customer>> {
+        self.find_by_email(email).try_next().await
+    }
+
+    /// Stream customers with cards that expire within `months` months, counting
+    /// the current month, or have already expired.
+    ///
+    /// Each customer is yielded once with the matching cards; customers without
+    /// such cards are skipped. See [`Card::expires_within`] for how the window is
+    /// counted. Cards embedded in the customer list are used when complete, and
+    /// listed separately for customers with more cards than fit in the embedded
+    /// list.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// // Cards expiring this month or next
+    /// let mut expiring = client.customers().expiring_cards(2);
+    /// while let Some(entry) = expiring.try_next().await? {
+    ///     for card in &entry.cards {
+    ///         println!(
+    ///             "{}: {} ending {} expires {}/{}",
+    ///             entry.customer.id, card.brand, card.last4, card.exp_month, card.exp_year
+    ///         );
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expiring_cards(&self, months: u32) -> ListStream<'a, ExpiringCards> {
+        let client = self.client;
+        let now = Utc::now();
+        let expiring = move |card: &Card| {
+            i64::from(card.months_until_expiry_at(now)) < i64::from(months)
+        };
+        self.list_all(ListParams::new().max_limit())
+            .try_filter_map_local(move |customer: Customer| async move {
+                let cards: Vec<Card> = match &customer.cards {
+                    Some(list) if !list.has_more => {
+                        list.data.iter().filter(|card| expiring(card)).cloned().collect()
+                    }
+                    _ => {
+                        let wrapper = client.customer(customer.id.clone());
+                        wrapper
+                            .cards()
+                            .list_all(ListParams::new().max_limit())
+                            .filter_local(expiring)
+                            .try_collect()
+                            .await?
+                    }
+                };
+                Ok((!cards.is_empty()).then_some(ExpiringCards { customer, cards }))
+            })
+    }
+
+    /// Merge the duplicate customer `loser` into `winner`.
+    ///
+    /// Returns a [`CustomerMerge`] to provide card tokens or request a dry run
+    /// before calling [`run`](CustomerMerge::run). See the
+    /// [`merge`](crate::merge) module for what is merged.
+    pub fn merge(&self, winner: &str, loser: &str) -> CustomerMerge<'a> {
+        CustomerMerge::new(self.client, winner, loser)
     }
 }
 
@@ -327,6 +527,45 @@ impl<'a> CustomerWrapper<'a> {
         CardService::new(self.client, self.customer_id.clone())
     }
 
+    /// Access the charges service for this customer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListChargeParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let charges = client.customer("cus_xxxxx")
+    ///     .charges()
+    ///     .list(ListChargeParams::new())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn charges(&self) -> CustomerChargeService<'a> {
+        CustomerChargeService::new(self.client, self.customer_id.clone())
+    }
+
+    /// Access the subscriptions service for this customer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscription = client.customer("cus_xxxxx")
+    ///     .subscriptions()
+    ///     .create("pln_xxxxx")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "subscriptions")]
+    pub fn subscriptions(&self) -> CustomerSubscriptionService<'a> {
+        CustomerSubscriptionService::new(self.client, self.customer_id.clone())
+    }
+
     /// Retrieve the customer details.
     ///
     /// # Example
@@ -340,19 +579,222 @@ impl<'a> CustomerWrapper<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self) -> PayjpResult<Customer> {
-        let path = format!("/customers/{}", self.customer_id);
-        self.client.get(&path).await
+        self.client.retrieve(&self.customer_id).await
     }
 
     /// Update the customer.
     pub async fn update(&self, params: UpdateCustomerParams) -> PayjpResult<Customer> {
-        let path = format!("/customers/{}", self.customer_id);
-        self.client.post(&path, &params).await
+        self.client.update_resource(&self.customer_id, &params).await
     }
 
     /// Delete the customer.
     pub async fn delete(&self) -> PayjpResult<DeletedCustomer> {
-        let path = format!("/customers/{}", self.customer_id);
-        self.client.delete(&path).await
+        self.client.delete_resource(&self.customer_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_find_by_email_scans_pages() {
+        let customer = |id: &str, email: &str| {
+            serde_json::json!({
+                "id": id,
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000,
+                "email": email,
+                "cards": { "object": "list", "data": [], "has_more": false, "url": "", "count": 0 }
+            })
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers"))
+            .and(query_param("offset", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [customer("cus_2", "Buyer@Example.com")],
+                "has_more": false,
+                "url": "/v1/customers",
+                "count": 2
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [customer("cus_1", "other@example.com")],
+                "has_more": true,
+                "url": "/v1/customers",
+                "count": 2
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let found = client
+            .customers()
+            .find_first_by_email("buyer@example.com")
+            .await
+            .expect("Failed to search customers");
+        assert_eq!(found.map(|c| c.id).as_deref(), Some("cus_2"));
+    }
+
+    #[tokio::test]
+    async fn test_expiring_cards_groups_by_customer() {
+        use chrono::Datelike;
+
+        let now = Utc::now();
+        let card = |id: &str, year: i32| {
+            serde_json::json!({
+                "id": id,
+                "object": "card",
+                "livemode": false,
+                "created": 1700000000,
+                "brand": "Visa",
+                "exp_month": now.month(),
+                "exp_year": year,
+                "last4": "4242"
+            })
+        };
+        let customer = |id: &str, cards: Vec<serde_json::Value>, has_more: bool| {
+            serde_json::json!({
+                "id": id,
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000,
+                "cards": {
+                    "object": "list",
+                    "data": cards,
+                    "has_more": has_more,
+                    "url": "",
+                    "count": 0
+                }
+            })
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    customer(
+                        "cus_1",
+                        vec![card("car_soon", now.year()), card("car_later", now.year() + 1)],
+                        false,
+                    ),
+                    customer("cus_2", vec![card("car_later", now.year() + 1)], true),
+                    customer("cus_3", vec![card("car_later", now.year() + 1)], false),
+                ],
+                "has_more": false,
+                "url": "/v1/customers",
+                "count": 3
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_2/cards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [card("car_later", now.year() + 1), card("car_expired", now.year() - 1)],
+                "has_more": false,
+                "url": "/v1/customers/cus_2/cards",
+                "count": 2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let expiring: Vec<ExpiringCards> = client
+            .customers()
+            .expiring_cards(2)
+            .try_collect()
+            .await
+            .expect("Failed to list expiring cards");
+        let found: Vec<_> = expiring
+            .iter()
+            .map(|entry| {
+                let cards: Vec<_> = entry.cards.iter().map(|c| c.id.as_str()).collect();
+                (entry.customer.id.as_str(), cards)
+            })
+            .collect();
+        assert_eq!(found, [("cus_1", vec!["car_soon"]), ("cus_2", vec!["car_expired"])]);
+    }
+
+    #[test]
+    fn test_clear_setters_serialize_empty_values() {
+        let params = UpdateCustomerParams::new().clear_email().description("VIP");
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "email=&description=VIP");
+    }
+
+    #[test]
+    fn test_metadata_map_and_remove() {
+        let params = UpdateCustomerParams::new()
+            .metadata("plan", "basic")
+            .metadata_map(Metadata::from([("plan".to_string(), "pro".to_string())]))
+            .metadata_remove("legacy_id");
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "metadata%5Blegacy_id%5D=&metadata%5Bplan%5D=pro");
+    }
+
+    #[test]
+    fn test_bulk_metadata_setters() {
+        let mut params = CreateCustomerParams::new()
+            .metadata("plan", "basic")
+            .metadata_from([("plan", "pro"), ("source", "web")])
+            .metadata_map(Metadata::from([("team".to_string(), "ops".to_string())]));
+        params.extend(vec![("locale".to_string(), "ja")]);
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(
+            encoded,
+            "metadata%5Blocale%5D=ja&metadata%5Bplan%5D=pro\
+             &metadata%5Bsource%5D=web&metadata%5Bteam%5D=ops"
+        );
+    }
+
+    #[test]
+    fn test_metadata_replace_deletes_stale_keys() {
+        let current = Metadata::from([
+            ("plan".to_string(), "basic".to_string()),
+            ("legacy_id".to_string(), "42".to_string()),
+        ]);
+        let params = UpdateCustomerParams::new().metadata_replace(
+            Some(&current),
+            Metadata::from([("plan".to_string(), "pro".to_string())]),
+        );
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "metadata%5Blegacy_id%5D=&metadata%5Bplan%5D=pro");
+    }
+
+    #[test]
+    fn test_metadata_replace_discards_earlier_keys() {
+        let current = Metadata::from([("legacy_id".to_string(), "42".to_string())]);
+        let params = UpdateCustomerParams::new()
+            .metadata("source", "web")
+            .metadata_replace(Some(&current), Metadata::new())
+            .metadata("plan", "pro");
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(encoded, "metadata%5Blegacy_id%5D=&metadata%5Bplan%5D=pro");
     }
 }
@@ -2,7 +2,9 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::ListParams;
+use crate::pagination::ListStream;
+use crate::params::{ListParams, MAX_LIMIT};
+use crate::resources::{string_enum, ObjectKind};
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -14,6 +16,7 @@ pub struct Event {
     pub id: String,
 
     /// Object type (always "event").
+    #[serde(deserialize_with = "Event::deserialize_object")]
     pub object: String,
 
     /// Whether this event was created in live mode.
@@ -34,105 +37,102 @@ pub struct Event {
     pub pending_webhooks: Option<i64>,
 }
 
-/// Type of event that occurred.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum EventType {
-    /// Charge was created.
-    #[serde(rename = "charge.created")]
-    ChargeCreated,
-
-    /// Charge was updated.
-    #[serde(rename = "charge.updated")]
-    ChargeUpdated,
-
-    /// Charge succeeded.
-    #[serde(rename = "charge.succeeded")]
-    ChargeSucceeded,
-
-    /// Charge failed.
-    #[serde(rename = "charge.failed")]
-    ChargeFailed,
-
-    /// Charge was captured.
-    #[serde(rename = "charge.captured")]
-    ChargeCaptured,
-
-    /// Charge was refunded.
-    #[serde(rename = "charge.refunded")]
-    ChargeRefunded,
-
-    /// Customer was created.
-    #[serde(rename = "customer.created")]
-    CustomerCreated,
-
-    /// Customer was updated.
-    #[serde(rename = "customer.updated")]
-    CustomerUpdated,
-
-    /// Customer was deleted.
-    #[serde(rename = "customer.deleted")]
-    CustomerDeleted,
-
-    /// Card was created.
-    #[serde(rename = "customer.card.created")]
-    CustomerCardCreated,
-
-    /// Card was updated.
-    #[serde(rename = "customer.card.updated")]
-    CustomerCardUpdated,
-
-    /// Card was deleted.
-    #[serde(rename = "customer.card.deleted")]
-    CustomerCardDeleted,
-
-    /// Plan was created.
-    #[serde(rename = "plan.created")]
-    PlanCreated,
-
-    /// Plan was updated.
-    #[serde(rename = "plan.updated")]
-    PlanUpdated,
-
-    /// Plan was deleted.
-    #[serde(rename = "plan.deleted")]
-    PlanDeleted,
-
-    /// Subscription was created.
-    #[serde(rename = "subscription.created")]
-    SubscriptionCreated,
-
-    /// Subscription was updated.
-    #[serde(rename = "subscription.updated")]
-    SubscriptionUpdated,
-
-    /// Subscription was deleted.
-    #[serde(rename = "subscription.deleted")]
-    SubscriptionDeleted,
-
-    /// Subscription was paused.
-    #[serde(rename = "subscription.paused")]
-    SubscriptionPaused,
-
-    /// Subscription was resumed.
-    #[serde(rename = "subscription.resumed")]
-    SubscriptionResumed,
-
-    /// Subscription was canceled.
-    #[serde(rename = "subscription.canceled")]
-    SubscriptionCanceled,
-
-    /// Subscription renewal succeeded.
-    #[serde(rename = "subscription.renewed")]
-    SubscriptionRenewed,
-
-    /// Transfer was created.
-    #[serde(rename = "transfer.created")]
-    TransferCreated,
-
-    /// Other event types not explicitly handled.
-    #[serde(other)]
-    Other,
+impl ObjectKind for Event {
+    const OBJECT: &'static str = "event";
+}
+
+impl Event {
+    /// Get the ID of the resource affected by this event (e.g., `ch_xxxxx`).
+    ///
+    /// This reads `data.object.id` directly, without deserializing the full resource.
+    pub fn resource_id(&self) -> Option<&str> {
+        self.data.object.get("id").and_then(Value::as_str)
+    }
+
+    /// Get the object type of the resource affected by this event (e.g., `"charge"`).
+    pub fn resource_object(&self) -> Option<&str> {
+        self.data.object.get("object").and_then(Value::as_str)
+    }
+}
+
+string_enum! {
+    /// Type of event that occurred.
+    ///
+    /// Unknown event types are preserved as [`EventType::Other`] together with the
+    /// raw `type` string, so they can still be logged and routed.
+    pub enum EventType {
+        /// Other event types not explicitly handled (raw `type` string).
+        Other(String),
+
+        /// Charge was created.
+        ChargeCreated => "charge.created",
+
+        /// Charge was updated.
+        ChargeUpdated => "charge.updated",
+
+        /// Charge succeeded.
+        ChargeSucceeded => "charge.succeeded",
+
+        /// Charge failed.
+        ChargeFailed => "charge.failed",
+
+        /// Charge was captured.
+        ChargeCaptured => "charge.captured",
+
+        /// Charge was refunded.
+        ChargeRefunded => "charge.refunded",
+
+        /// Customer was created.
+        CustomerCreated => "customer.created",
+
+        /// Customer was updated.
+        CustomerUpdated => "customer.updated",
+
+        /// Customer was deleted.
+        CustomerDeleted => "customer.deleted",
+
+        /// Card was created.
+        CustomerCardCreated => "customer.card.created",
+
+        /// Card was updated.
+        CustomerCardUpdated => "customer.card.updated",
+
+        /// Card was deleted.
+        CustomerCardDeleted => "customer.card.deleted",
+
+        /// Plan was created.
+        PlanCreated => "plan.created",
+
+        /// Plan was updated.
+        PlanUpdated => "plan.updated",
+
+        /// Plan was deleted.
+        PlanDeleted => "plan.deleted",
+
+        /// Subscription was created.
+        SubscriptionCreated => "subscription.created",
+
+        /// Subscription was updated.
+        SubscriptionUpdated => "subscription.updated",
+
+        /// Subscription was deleted.
+        SubscriptionDeleted => "subscription.deleted",
+
+        /// Subscription was paused.
+        SubscriptionPaused => "subscription.paused",
+
+        /// Subscription was resumed.
+        SubscriptionResumed => "subscription.resumed",
+
+        /// Subscription was canceled.
+        SubscriptionCanceled => "subscription.canceled",
+
+        /// Subscription renewal succeeded.
+        SubscriptionRenewed => "subscription.renewed",
+
+        /// Transfer was created.
+        TransferCreated => "transfer.created",
+    }
 }
 
 /// Event data containing the affected resource.
@@ -146,6 +146,105 @@ pub struct EventData {
     pub object: Value,
 }
 
+/// Parameters for listing events.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ListEventParams {
+    /// Maximum number of items to return (default: 10, max: 100).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+
+    /// Offset for pagination (default: 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /// Return events created since this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+
+    /// Return events created until this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<i64>,
+
+    /// Filter by the ID of the affected resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+
+    /// Filter by the object type of the affected resource (e.g., "charge").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>,
+
+    /// Filter by event type.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+}
+
+impl From<ListParams> for ListEventParams {
+    fn from(params: ListParams) -> Self {
+        Self {
+            limit: params.limit,
+            offset: params.offset,
+            since: params.since,
+            until: params.until,
+            ..Default::default()
+        }
+    }
+}
+
+impl ListEventParams {
+    /// Create new list event parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit for the number of items to return.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the largest page size the API allows ([`MAX_LIMIT`]).
+    pub fn max_limit(mut self) -> Self {
+        self.limit = Some(MAX_LIMIT);
+        self
+    }
+
+    /// Set the offset for pagination.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Set the since timestamp filter.
+    pub fn since(mut self, since: i64) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Set the until timestamp filter.
+    pub fn until(mut self, until: i64) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Filter by the ID of the affected resource.
+    pub fn resource_id(mut self, resource_id: impl Into<String>) -> Self {
+        self.resource_id = Some(resource_id.into());
+        self
+    }
+
+    /// Filter by the object type of the affected resource.
+    pub fn object(mut self, object: impl Into<String>) -> Self {
+        self.object = Some(object.into());
+        self
+    }
+
+    /// Filter by event type.
+    pub fn event_type(mut self, event_type: EventType) -> Self {
+        self.event_type = Some(event_type);
+        self
+    }
+}
+
 /// Service for retrieving events.
 pub struct EventService<'a> {
     client: &'a PayjpClient,
@@ -170,25 +269,116 @@ impl<'a> EventService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, event_id: &str) -> PayjpResult<Event> {
-        let path = format!("/events/{}", event_id);
-        self.client.get(&path).await
+        self.client.retrieve(event_id).await
     }
 
     /// List all events.
     ///
+    /// Plain [`ListParams`] are accepted too, for listing without filters.
+    ///
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::{PayjpClient, ListParams};
+    /// # use payjp::{PayjpClient, EventType, ListEventParams};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = PayjpClient::new("sk_test_xxxxx")?;
     /// let events = client.events().list(
-    ///     ListParams::new().limit(10)
+    ///     ListEventParams::new()
+    ///         .limit(10)
+    ///         .event_type(EventType::ChargeFailed)
     /// ).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Event>> {
-        self.client.get_with_params("/events", &params).await
+    pub async fn list(
+        &self,
+        params: impl Into<ListEventParams>,
+    ) -> PayjpResult<ListResponse<Event>> {
+        self.client.list_resource(&params.into()).await
+    }
+
+    /// List all events, automatically fetching further pages as the stream is consumed.
+    pub fn list_all(&self, params: impl Into<ListEventParams>) -> ListStream<'a, Event> {
+        self.client.stream_resource(params.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_preserves_raw_string() {
+        let known: EventType = serde_json::from_str("\"charge.succeeded\"").unwrap();
+        assert_eq!(known, EventType::ChargeSucceeded);
+        assert_eq!(known.as_str(), "charge.succeeded");
+
+        let unknown: EventType = serde_json::from_str("\"tenant.updated\"").unwrap();
+        assert_eq!(unknown, EventType::Other("tenant.updated".to_string()));
+        assert_eq!(unknown.as_str(), "tenant.updated");
+        assert_eq!(
+            serde_json::to_string(&unknown).unwrap(),
+            "\"tenant.updated\""
+        );
+    }
+
+    fn event(object: serde_json::Value) -> Event {
+        serde_json::from_value(serde_json::json!({
+            "id": "evnt_1",
+            "object": "event",
+            "livemode": false,
+            "created": 1700000000,
+            "type": "charge.succeeded",
+            "data": { "object": object }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resource_id_and_object_read_data_object() {
+        let charge = event(serde_json::json!({ "id": "ch_1", "object": "charge" }));
+        assert_eq!(charge.resource_id(), Some("ch_1"));
+        assert_eq!(charge.resource_object(), Some("charge"));
+
+        let without_id = event(serde_json::json!({ "object": "balance" }));
+        assert_eq!(without_id.resource_id(), None);
+        assert_eq!(without_id.resource_object(), Some("balance"));
+
+        let not_an_object = event(serde_json::json!("ch_1"));
+        assert_eq!(not_an_object.resource_id(), None);
+        assert_eq!(not_an_object.resource_object(), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_accepts_plain_list_params() {
+        use crate::ClientOptions;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [],
+                "has_more": false,
+                "url": "/v1/events",
+                "count": 0
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let events = client
+            .events()
+            .list(ListParams::new().limit(2))
+            .await
+            .expect("Failed to list events");
+        assert!(events.data.is_empty());
     }
 }
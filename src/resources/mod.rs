@@ -3,40 +3,384 @@
 pub mod card;
 pub mod charge;
 pub mod customer;
+#[cfg(feature = "subscriptions")]
 pub mod plan;
+#[cfg(feature = "subscriptions")]
 pub mod subscription;
 pub mod token;
 pub mod account;
 pub mod event;
 pub mod transfer;
+#[cfg(feature = "statements")]
 pub mod statement;
+#[cfg(feature = "statements")]
 pub mod balance;
+#[cfg(feature = "statements")]
 pub mod term;
 pub mod three_d_secure;
 
+#[cfg(feature = "platform")]
 pub mod platform;
 
+use crate::pagination::{HasCreated, HasId};
+use crate::redact;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::fmt;
+
 // Re-export commonly used types
-pub use card::{Card, CardService, CardThreeDSecureStatus, CreateCardParams, UpdateCardParams};
+pub use card::{
+    Card, CardService, CardThreeDSecureStatus, CreateCardParams, ThreeDSecureOutcome,
+    UpdateCardParams,
+};
 pub use charge::{
-    CaptureParams, Charge, ChargeService, CreateChargeParams, ListChargeParams, ReauthParams,
-    RefundParams, UpdateChargeParams,
+    CaptureParams, Charge, ChargeFailure, ChargeService, ChargeThreeDSecureParams, CreateChargeParams,
+    CreateChargeParamsRef, CustomerChargeService, FailureCode, FeeBreakdown, ListChargeParams, ReauthParams, RefundParams,
+    UpdateChargeParams,
+};
+pub use customer::{
+    CardOrId, CreateCustomerParams, Customer, CustomerService, ExpiringCards, UpdateCustomerParams,
 };
-pub use customer::{CardOrId, CreateCustomerParams, Customer, CustomerService, UpdateCustomerParams};
+#[cfg(feature = "subscriptions")]
 pub use plan::{CreatePlanParams, Plan, PlanInterval, PlanService, UpdatePlanParams};
+#[cfg(feature = "subscriptions")]
 pub use subscription::{
-    CancelSubscriptionParams, CreateSubscriptionParams, PauseSubscriptionParams,
-    ResumeSubscriptionParams, Subscription, SubscriptionService, SubscriptionStatus,
+    CancelSubscriptionParams, CreateSubscriptionParams, CustomerSubscriptionService,
+    ListSubscriptionParams, PauseSubscriptionParams, ResumeSubscriptionParams, Subscription, SubscriptionService, SubscriptionStatus,
     UpdateSubscriptionParams,
 };
 pub use token::{CardDetails, CreateTokenParams, PublicTokenService, Token, TokenService};
 pub use account::{Account, AccountService};
-pub use event::{Event, EventData, EventService, EventType};
-pub use transfer::{Transfer, TransferService};
-pub use statement::{Statement, StatementService};
-pub use balance::{Balance, BalanceService};
+pub use event::{Event, EventData, EventService, EventType, ListEventParams};
+pub use transfer::{Transfer, TransferService, TransferStatus};
+#[cfg(feature = "statements")]
+pub use statement::{
+    ListStatementParams, Statement, StatementService, StatementType, StatementUrls,
+    StatementUrlsParams,
+};
+#[cfg(feature = "statements")]
+pub use balance::{Balance, BalanceService, BalanceState};
+#[cfg(feature = "statements")]
 pub use term::{Term, TermService};
 pub use three_d_secure::{
     CreateThreeDSecureRequestParams, ThreeDSecureRequest, ThreeDSecureRequestService,
     ThreeDSecureStatus,
 };
+
+/// Resources identified by a fixed `object` discriminator.
+pub(crate) trait ObjectKind {
+    /// Expected value of the `object` field (e.g., `"charge"`).
+    const OBJECT: &'static str;
+
+    /// Deserialize the `object` field, failing if it is not [`Self::OBJECT`].
+    fn deserialize_object<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        expect_object(deserializer, Self::OBJECT)
+    }
+}
+
+/// Deserialize an `object` field, failing with a descriptive error if it is not `expected`.
+///
+/// This catches responses that deserialize into the wrong type, such as a different
+/// resource returned by a misconfigured proxy.
+pub(crate) fn expect_object<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    expected: &str,
+) -> Result<String, D::Error> {
+    let object = String::deserialize(deserializer)?;
+    if object != expected {
+        return Err(D::Error::custom(format!(
+            "expected object \"{}\", got \"{}\"",
+            expected, object
+        )));
+    }
+    Ok(object)
+}
+
+/// Human-readable JSON rendering for resources, with card data masked.
+///
+/// Resource types also implement [`Display`](fmt::Display) using this rendering,
+/// so they can be printed directly in CLI tools and debug output.
+///
+/// # Example
+///
+/// ```no_run
+/// use payjp::resources::PrettyJson;
+/// use payjp::PayjpClient;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = PayjpClient::new("sk_test_xxxxx")?;
+/// let charge = client.charges().retrieve("ch_xxxxx").await?;
+///
+/// println!("{}", charge.to_json_pretty());
+/// // or simply
+/// println!("{}", charge);
+/// # Ok(())
+/// # }
+/// ```
+pub trait PrettyJson: Serialize {
+    /// Render as pretty-printed JSON, with card numbers, CVCs and fingerprints masked.
+    fn to_json_pretty(&self) -> String {
+        let rendered = serde_json::to_value(self).and_then(|mut value| {
+            redact::redact_json(&mut value);
+            serde_json::to_string_pretty(&value)
+        });
+        rendered.unwrap_or_else(|e| format!("<failed to render: {}>", e))
+    }
+}
+
+/// Implement [`PrettyJson`] and `Display` for resource types.
+macro_rules! impl_pretty_json {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl PrettyJson for $ty {}
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.to_json_pretty())
+                }
+            }
+        )*
+    };
+}
+
+impl_pretty_json!(
+    Account,
+    Card,
+    Charge,
+    Customer,
+    Event,
+    ThreeDSecureRequest,
+    Token,
+    Transfer,
+);
+
+#[cfg(feature = "subscriptions")]
+impl_pretty_json!(Plan, Subscription);
+
+#[cfg(feature = "statements")]
+impl_pretty_json!(Balance, Statement, StatementUrls, Term);
+
+#[cfg(feature = "platform")]
+impl_pretty_json!(platform::Tenant, platform::TenantTransfer);
+
+/// Fields shared by every PAY.JP resource object.
+///
+/// Lets logging, caching and storage layers work over any resource. The ID and
+/// creation timestamp come from the [`HasId`] and [`HasCreated`] supertraits.
+/// [`Term`] is not included, as terms have no `created` field.
+///
+/// # Example
+///
+/// ```
+/// use payjp::PayjpObject;
+///
+/// fn log_fetched<T: PayjpObject>(resource: &T) {
+///     println!(
+///         "{} {} (created {}, livemode: {})",
+///         resource.object(),
+///         resource.id(),
+///         resource.created(),
+///         resource.livemode()
+///     );
+/// }
+/// ```
+pub trait PayjpObject: HasCreated + HasId {
+    /// Get the object type (e.g., `"charge"`).
+    fn object(&self) -> &str;
+
+    /// Whether the resource belongs to live mode.
+    fn livemode(&self) -> bool;
+}
+
+macro_rules! impl_payjp_object {
+    ($($ty:ty),* $(,)?) => {
+        $(impl PayjpObject for $ty {
+            fn object(&self) -> &str {
+                &self.object
+            }
+
+            fn livemode(&self) -> bool {
+                self.livemode
+            }
+        })*
+    };
+}
+
+impl_payjp_object!(
+    Account,
+    Card,
+    Charge,
+    Customer,
+    Event,
+    ThreeDSecureRequest,
+    Token,
+    Transfer,
+);
+
+#[cfg(feature = "subscriptions")]
+impl_payjp_object!(Plan, Subscription);
+
+#[cfg(feature = "statements")]
+impl_payjp_object!(Balance, Statement);
+
+#[cfg(feature = "platform")]
+impl_payjp_object!(platform::Tenant, platform::TenantTransfer);
+
+/// Define an enum of the string values the API sends for a field, mapping each
+/// known variant to its raw string.
+///
+/// Unknown values are kept in the `Other(String)` variant, listed first, so values
+/// added to the API later still deserialize. Generates `as_str`, `Display` and the
+/// `String` conversions used for serde.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(#[$other_meta:meta])*
+            Other(String),
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $raw:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            $(#[$other_meta])*
+            Other(String),
+        }
+
+        impl $name {
+            /// Get the raw string the API uses for this value.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $raw,)*
+                    $name::Other(raw) => raw,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(raw: &str) -> Self {
+                match raw {
+                    $($raw => $name::$variant,)*
+                    other => $name::Other(other.to_string()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(raw: String) -> Self {
+                match $name::from(raw.as_str()) {
+                    $name::Other(_) => $name::Other(raw),
+                    known => known,
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Other(raw) => raw,
+                    known => known.as_str().to_string(),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+pub(crate) use string_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_discriminator_mismatch_is_rejected() {
+        let customer = serde_json::json!({
+            "id": "cus_1",
+            "object": "customer",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "paid": true,
+            "captured": true,
+            "refunded": false,
+            "amount_refunded": 0
+        });
+
+        let err = serde_json::from_value::<Charge>(customer).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected object \"charge\", got \"customer\""));
+    }
+
+    #[test]
+    fn test_pretty_json_masks_card_data() {
+        let charge: Charge = serde_json::from_value(serde_json::json!({
+            "id": "ch_1",
+            "object": "charge",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "paid": true,
+            "captured": true,
+            "refunded": false,
+            "amount_refunded": 0,
+            "card": {
+                "id": "car_1",
+                "object": "card",
+                "livemode": false,
+                "created": 1700000000,
+                "brand": "Visa",
+                "exp_month": 12,
+                "exp_year": 2030,
+                "fingerprint": "e1d8225886e3a7211127df751c86787f",
+                "last4": "4242"
+            }
+        }))
+        .unwrap();
+
+        let rendered = charge.to_string();
+        assert_eq!(rendered, charge.to_json_pretty());
+        assert!(rendered.contains("\"id\": \"ch_1\""));
+        assert!(rendered.contains("\"last4\": \"4242\""));
+        assert!(rendered.contains("\"fingerprint\": \"***\""));
+        assert!(!rendered.contains("e1d8225886e3a7211127df751c86787f"));
+    }
+
+    #[test]
+    fn test_payjp_object_is_generic_over_resources() {
+        fn describe<T: PayjpObject>(resource: &T) -> String {
+            format!(
+                "{}:{}:{}:{}",
+                resource.object(),
+                resource.id(),
+                resource.created(),
+                resource.livemode()
+            )
+        }
+
+        let customer: Customer = serde_json::from_value(serde_json::json!({
+            "id": "cus_1",
+            "object": "customer",
+            "livemode": true,
+            "created": 1700000000
+        }))
+        .unwrap();
+        assert_eq!(describe(&customer), "customer:cus_1:1700000000:true");
+    }
+}
@@ -2,8 +2,10 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
-use crate::response::ListResponse;
+use crate::pagination::ListStream;
+use crate::params::{impl_clear_setters, impl_metadata_setters, ListParams, Metadata};
+use crate::resources::ObjectKind;
+use crate::response::{Deleted, ListResponse};
 use serde::{Deserialize, Serialize};
 
 /// A plan defines the recurring billing details for subscriptions.
@@ -13,6 +15,7 @@ pub struct Plan {
     pub id: String,
 
     /// Object type (always "plan").
+    #[serde(deserialize_with = "Plan::deserialize_object")]
     pub object: String,
 
     /// Whether this plan was created in live mode.
@@ -47,6 +50,10 @@ pub struct Plan {
     pub metadata: Option<Metadata>,
 }
 
+impl ObjectKind for Plan {
+    const OBJECT: &'static str = "plan";
+}
+
 /// Billing interval for a plan.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -87,7 +94,7 @@ pub struct CreatePlanParams {
     pub billing_day: Option<i32>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -129,16 +136,10 @@ impl CreatePlanParams {
         self.billing_day = Some(day);
         self
     }
-
-    /// Add metadata to the plan.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_metadata_setters!(CreatePlanParams, "plan");
+
 /// Parameters for updating a plan.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct UpdatePlanParams {
@@ -155,7 +156,7 @@ pub struct UpdatePlanParams {
     pub billing_day: Option<i32>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -182,28 +183,16 @@ impl UpdatePlanParams {
         self.billing_day = Some(day);
         self
     }
-
-    /// Add metadata to the plan.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
-/// Response from deleting a plan.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeletedPlan {
-    /// Plan ID.
-    pub id: String,
+impl_clear_setters!(UpdatePlanParams, "plan", {
+    clear_name => name,
+});
 
-    /// Whether the deletion was successful.
-    pub deleted: bool,
+impl_metadata_setters!(UpdatePlanParams, "plan", update);
 
-    /// Whether this plan was in live mode.
-    pub livemode: bool,
-}
+/// Response from deleting a plan.
+pub type DeletedPlan = Deleted<Plan>;
 
 /// Service for managing plans.
 pub struct PlanService<'a> {
@@ -249,8 +238,7 @@ impl<'a> PlanService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, plan_id: &str) -> PayjpResult<Plan> {
-        let path = format!("/plans/{}", plan_id);
-        self.client.get(&path).await
+        self.client.retrieve(plan_id).await
     }
 
     /// Update a plan.
@@ -269,8 +257,7 @@ impl<'a> PlanService<'a> {
     /// # }
     /// ```
     pub async fn update(&self, plan_id: &str, params: UpdatePlanParams) -> PayjpResult<Plan> {
-        let path = format!("/plans/{}", plan_id);
-        self.client.post(&path, &params).await
+        self.client.update_resource(plan_id, &params).await
     }
 
     /// Delete a plan.
@@ -286,8 +273,7 @@ impl<'a> PlanService<'a> {
     /// # }
     /// ```
     pub async fn delete(&self, plan_id: &str) -> PayjpResult<DeletedPlan> {
-        let path = format!("/plans/{}", plan_id);
-        self.client.delete(&path).await
+        self.client.delete_resource(plan_id).await
     }
 
     /// List all plans.
@@ -305,6 +291,27 @@ impl<'a> PlanService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Plan>> {
-        self.client.get_with_params("/plans", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all plans, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut plans = client.plans().list_all(ListParams::new().max_limit());
+    /// while let Some(plan) = plans.try_next().await? {
+    ///     println!("{}: ¥{}", plan.id, plan.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Plan> {
+        self.client.stream_resource(params)
     }
 }
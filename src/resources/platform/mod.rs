@@ -3,5 +3,9 @@
 pub mod tenant;
 pub mod tenant_transfer;
 
-pub use tenant::{CreateTenantParams, Tenant, TenantService, UpdateTenantParams};
+pub use crate::resources::charge::TenantChargeService;
+pub use tenant::{
+    CreateTenantParams, Tenant, TenantService, TenantWrapper, TransferInterval, TransferSchedule,
+    UpdateTenantParams,
+};
 pub use tenant_transfer::{TenantTransfer, TenantTransferService};
@@ -1,10 +1,24 @@
 //! Tenant resource and service implementation (Platform API).
 
 use crate::client::PayjpClient;
-use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
-use crate::response::ListResponse;
+use crate::error::{PayjpError, PayjpResult};
+use crate::pagination::ListStream;
+use crate::params::{impl_clear_setters, impl_metadata_setters, ListParams, Metadata};
+use crate::resources::charge::TenantChargeService;
+use crate::resources::three_d_secure::{CreateThreeDSecureRequestParams, ThreeDSecureRequest};
+use crate::resources::{string_enum, ObjectKind};
+use crate::response::{Deleted, ListResponse};
 use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Maximum length of a caller-supplied tenant ID.
+pub const MAX_TENANT_ID_LENGTH: usize = 100;
+
+/// Smallest minimum transfer amount accepted by the API.
+pub const MIN_TRANSFER_AMOUNT: i64 = 1000;
+
+/// Largest platform fee rate (in percent) accepted by the API.
+pub const MAX_PLATFORM_FEE_RATE: f64 = 95.0;
 
 /// A tenant represents a sub-merchant in the platform.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +27,7 @@ pub struct Tenant {
     pub id: String,
 
     /// Object type (always "tenant").
+    #[serde(deserialize_with = "Tenant::deserialize_object")]
     pub object: String,
 
     /// Whether this tenant was created in live mode.
@@ -25,6 +40,18 @@ pub struct Tenant {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
+    /// Business name shown to customers (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_name: Option<String>,
+
+    /// URL of the tenant's website (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_url: Option<String>,
+
+    /// Contact phone number of the tenant (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_phone: Option<String>,
+
     /// Platform fee rate for this tenant (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub platform_fee_rate: Option<String>,
@@ -33,10 +60,18 @@ pub struct Tenant {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum_transfer_amount: Option<i64>,
 
+    /// Whether the platform fee rate includes the PAY.JP fee (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payjp_fee_included: Option<bool>,
+
     /// Bank information (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bank_account: Option<BankAccount>,
 
+    /// When the tenant's balance is transferred (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_schedule: Option<TransferSchedule>,
+
     /// Currencies enabled for this tenant (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currencies_supported: Option<Vec<String>>,
@@ -50,33 +85,136 @@ pub struct Tenant {
     pub metadata: Option<Metadata>,
 }
 
+impl ObjectKind for Tenant {
+    const OBJECT: &'static str = "tenant";
+}
+
 /// Bank account information for a tenant.
+///
+/// The tenant API takes bank details as top-level form fields, so the fields
+/// serialize with a `bank_` prefix (e.g. `bank_branch_code`) and are flattened
+/// into [`CreateTenantParams`] and [`UpdateTenantParams`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BankAccount {
     /// Bank code.
     pub bank_code: String,
 
     /// Branch code.
+    #[serde(rename(serialize = "bank_branch_code"), alias = "bank_branch_code")]
     pub branch_code: String,
 
     /// Account type ("普通" or "当座").
+    #[serde(rename(serialize = "bank_account_type"), alias = "bank_account_type")]
     pub account_type: String,
 
     /// Account number.
+    #[serde(rename(serialize = "bank_account_number"), alias = "bank_account_number")]
     pub account_number: String,
 
     /// Account holder name.
+    #[serde(
+        rename(serialize = "bank_account_holder_name"),
+        alias = "bank_account_holder_name"
+    )]
     pub account_holder_name: String,
 }
 
+string_enum! {
+    /// How often a tenant's balance is transferred.
+    #[non_exhaustive]
+    pub enum TransferInterval {
+        /// Other intervals not explicitly handled (raw `interval` string).
+        Other(String),
+
+        /// Once a month, on [`TransferSchedule::monthly_anchor`].
+        Monthly => "monthly",
+
+        /// Once a week, on [`TransferSchedule::weekly_anchor`].
+        Weekly => "weekly",
+    }
+}
+
+/// Transfer schedule of a tenant.
+///
+/// Like [`BankAccount`], the fields are sent as top-level form fields with a
+/// `transfer_` prefix (e.g. `transfer_interval`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferSchedule {
+    /// How often transfers are made.
+    #[serde(rename(serialize = "transfer_interval"), alias = "transfer_interval")]
+    pub interval: TransferInterval,
+
+    /// Day of the month (1 to 31) for monthly transfers (optional).
+    #[serde(
+        rename(serialize = "transfer_monthly_anchor"),
+        alias = "transfer_monthly_anchor",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub monthly_anchor: Option<u8>,
+
+    /// Day of the week (e.g. "monday") for weekly transfers (optional).
+    #[serde(
+        rename(serialize = "transfer_weekly_anchor"),
+        alias = "transfer_weekly_anchor",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub weekly_anchor: Option<String>,
+}
+
+impl TransferSchedule {
+    /// Transfer once a month on the given day (1 to 31).
+    pub fn monthly(day: u8) -> Self {
+        Self {
+            interval: TransferInterval::Monthly,
+            monthly_anchor: Some(day),
+            weekly_anchor: None,
+        }
+    }
+
+    /// Transfer once a week on the given day (e.g. "monday").
+    pub fn weekly(day: impl Into<String>) -> Self {
+        Self {
+            interval: TransferInterval::Weekly,
+            monthly_anchor: None,
+            weekly_anchor: Some(day.into()),
+        }
+    }
+
+    fn validate(&self) -> PayjpResult<()> {
+        match self.monthly_anchor {
+            Some(day) if !(1..=31).contains(&day) => Err(PayjpError::InvalidRequest(format!(
+                "transfer_monthly_anchor must be between 1 and 31, got {}",
+                day
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Parameters for creating a tenant.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct CreateTenantParams {
+    /// Tenant ID to use instead of a generated one (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
     /// Tenant name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
-    /// Platform fee rate (as a decimal string, e.g., "0.10" for 10%).
+    /// Business name shown to customers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_name: Option<String>,
+
+    /// URL of the tenant's website.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_url: Option<String>,
+
+    /// Contact phone number of the tenant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_phone: Option<String>,
+
+    /// Platform fee rate in percent (as a decimal string, e.g., "10.15").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub platform_fee_rate: Option<String>,
 
@@ -84,12 +222,20 @@ pub struct CreateTenantParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum_transfer_amount: Option<i64>,
 
-    /// Bank account information.
+    /// Whether the platform fee rate includes the PAY.JP fee (can only be set on creation).
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub payjp_fee_included: Option<bool>,
+
+    /// Bank account information (sent as flat `bank_*` fields).
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub bank_account: Option<BankAccount>,
 
+    /// Transfer schedule (sent as flat `transfer_*` fields).
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub transfer_schedule: Option<TransferSchedule>,
+
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -99,12 +245,40 @@ impl CreateTenantParams {
         Self::default()
     }
 
+    /// Set the tenant ID instead of letting PAY.JP generate one.
+    ///
+    /// Use a deterministic ID (e.g. derived from your merchant ID) so retried
+    /// creations cannot produce duplicate tenants. IDs may contain up to
+    /// [`MAX_TENANT_ID_LENGTH`] ASCII letters, digits, `-` and `_`.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     /// Set the tenant name.
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
         self
     }
 
+    /// Set the business name shown to customers.
+    pub fn business_name(mut self, name: impl Into<String>) -> Self {
+        self.business_name = Some(name.into());
+        self
+    }
+
+    /// Set the URL of the tenant's website (must be `http` or `https`).
+    pub fn business_url(mut self, url: impl Into<String>) -> Self {
+        self.business_url = Some(url.into());
+        self
+    }
+
+    /// Set the contact phone number.
+    pub fn business_phone(mut self, phone: impl Into<String>) -> Self {
+        self.business_phone = Some(phone.into());
+        self
+    }
+
     /// Set the platform fee rate.
     pub fn platform_fee_rate(mut self, rate: impl Into<String>) -> Self {
         self.platform_fee_rate = Some(rate.into());
@@ -117,21 +291,90 @@ impl CreateTenantParams {
         self
     }
 
+    /// Set whether the platform fee rate includes the PAY.JP fee.
+    ///
+    /// When `true`, the PAY.JP fee is deducted from the platform fee instead of
+    /// from the tenant's sales. This cannot be changed after creation.
+    pub fn payjp_fee_included(mut self, included: bool) -> Self {
+        self.payjp_fee_included = Some(included);
+        self
+    }
+
     /// Set the bank account.
     pub fn bank_account(mut self, account: BankAccount) -> Self {
         self.bank_account = Some(account);
         self
     }
 
-    /// Add metadata to the tenant.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
+    /// Set the transfer schedule.
+    pub fn transfer_schedule(mut self, schedule: TransferSchedule) -> Self {
+        self.transfer_schedule = Some(schedule);
         self
     }
+
+    /// Check the parameters against the API's constraints before sending.
+    ///
+    /// Called by [`TenantService::create`]; fails with `InvalidRequest`.
+    pub fn validate(&self) -> PayjpResult<()> {
+        if let Some(id) = &self.id {
+            let valid_chars = id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if id.is_empty() || id.len() > MAX_TENANT_ID_LENGTH || !valid_chars {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "Tenant ID {:?} must be 1 to {} ASCII letters, digits, '-' or '_'",
+                    id, MAX_TENANT_ID_LENGTH
+                )));
+            }
+        }
+        if let Some(url) = &self.business_url {
+            let valid = Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "business_url {:?} must be an http or https URL",
+                    url
+                )));
+            }
+        }
+        if let Some(phone) = &self.business_phone {
+            let valid_chars = phone
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '-' || c == '+');
+            if !phone.chars().any(|c| c.is_ascii_digit()) || !valid_chars {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "business_phone {:?} must contain only digits, '-' or '+'",
+                    phone
+                )));
+            }
+        }
+        if let Some(rate) = &self.platform_fee_rate {
+            let valid = rate
+                .parse::<f64>()
+                .is_ok_and(|rate| (0.0..=MAX_PLATFORM_FEE_RATE).contains(&rate));
+            if !valid {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "platform_fee_rate {:?} must be a decimal number from 0 to {}",
+                    rate, MAX_PLATFORM_FEE_RATE
+                )));
+            }
+        }
+        if let Some(amount) = self.minimum_transfer_amount {
+            if amount < MIN_TRANSFER_AMOUNT {
+                return Err(PayjpError::InvalidRequest(format!(
+                    "minimum_transfer_amount must be at least {}, got {}",
+                    MIN_TRANSFER_AMOUNT, amount
+                )));
+            }
+        }
+        if let Some(schedule) = &self.transfer_schedule {
+            schedule.validate()?;
+        }
+        Ok(())
+    }
 }
 
+impl_metadata_setters!(CreateTenantParams, "tenant");
+
 /// Parameters for updating a tenant.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct UpdateTenantParams {
@@ -147,12 +390,16 @@ pub struct UpdateTenantParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum_transfer_amount: Option<i64>,
 
-    /// Bank account information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Bank account information (sent as flat `bank_*` fields).
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub bank_account: Option<BankAccount>,
 
+    /// Transfer schedule (sent as flat `transfer_*` fields).
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub transfer_schedule: Option<TransferSchedule>,
+
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -186,27 +433,21 @@ impl UpdateTenantParams {
         self
     }
 
-    /// Add metadata to the tenant.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
+    /// Set the transfer schedule.
+    pub fn transfer_schedule(mut self, schedule: TransferSchedule) -> Self {
+        self.transfer_schedule = Some(schedule);
         self
     }
 }
 
-/// Response from deleting a tenant.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeletedTenant {
-    /// Tenant ID.
-    pub id: String,
+impl_clear_setters!(UpdateTenantParams, "tenant", {
+    clear_name => name,
+});
 
-    /// Whether the deletion was successful.
-    pub deleted: bool,
+impl_metadata_setters!(UpdateTenantParams, "tenant", update);
 
-    /// Whether this tenant was in live mode.
-    pub livemode: bool,
-}
+/// Response from deleting a tenant.
+pub type DeletedTenant = Deleted<Tenant>;
 
 /// Application URLs for tenant onboarding.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -236,10 +477,10 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::{PayjpClient, CreateTenantParams};
+    /// # use payjp::{PlatformClient, CreateTenantParams};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tenant = client.tenants().create(
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let tenant = platform.tenants().create(
     ///     CreateTenantParams::new()
     ///         .name("Sub-merchant")
     ///         .platform_fee_rate("0.10")
@@ -248,6 +489,7 @@ impl<'a> TenantService<'a> {
     /// # }
     /// ```
     pub async fn create(&self, params: CreateTenantParams) -> PayjpResult<Tenant> {
+        params.validate()?;
         self.client.post("/tenants", &params).await
     }
 
@@ -256,16 +498,15 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::PayjpClient;
+    /// # use payjp::PlatformClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tenant = client.tenants().retrieve("ten_xxxxx").await?;
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let tenant = platform.tenants().retrieve("ten_xxxxx").await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn retrieve(&self, tenant_id: &str) -> PayjpResult<Tenant> {
-        let path = format!("/tenants/{}", tenant_id);
-        self.client.get(&path).await
+        self.client.retrieve_resource(tenant_id).await
     }
 
     /// Update a tenant.
@@ -273,10 +514,10 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::{PayjpClient, UpdateTenantParams};
+    /// # use payjp::{PlatformClient, UpdateTenantParams};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tenant = client.tenants().update(
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let tenant = platform.tenants().update(
     ///     "ten_xxxxx",
     ///     UpdateTenantParams::new().name("Updated Name")
     /// ).await?;
@@ -284,8 +525,10 @@ impl<'a> TenantService<'a> {
     /// # }
     /// ```
     pub async fn update(&self, tenant_id: &str, params: UpdateTenantParams) -> PayjpResult<Tenant> {
-        let path = format!("/tenants/{}", tenant_id);
-        self.client.post(&path, &params).await
+        if let Some(schedule) = &params.transfer_schedule {
+            schedule.validate()?;
+        }
+        self.client.update_resource(tenant_id, &params).await
     }
 
     /// Delete a tenant.
@@ -293,16 +536,15 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::PayjpClient;
+    /// # use payjp::PlatformClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let deleted = client.tenants().delete("ten_xxxxx").await?;
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let deleted = platform.tenants().delete("ten_xxxxx").await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn delete(&self, tenant_id: &str) -> PayjpResult<DeletedTenant> {
-        let path = format!("/tenants/{}", tenant_id);
-        self.client.delete(&path).await
+        self.client.delete_resource(tenant_id).await
     }
 
     /// List all tenants.
@@ -310,17 +552,38 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::{PayjpClient, ListParams};
+    /// # use payjp::{PlatformClient, ListParams};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let tenants = client.tenants().list(
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let tenants = platform.tenants().list(
     ///     ListParams::new().limit(10)
     /// ).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Tenant>> {
-        self.client.get_with_params("/tenants", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all tenants, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PlatformClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let mut tenants = platform.tenants().list_all(ListParams::new().max_limit());
+    /// while let Some(tenant) = tenants.try_next().await? {
+    ///     println!("{}", tenant.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Tenant> {
+        self.client.stream_resource(params)
     }
 
     /// Create application URLs for tenant onboarding.
@@ -328,10 +591,10 @@ impl<'a> TenantService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::PayjpClient;
+    /// # use payjp::PlatformClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let urls = client.tenants().create_application_urls("ten_xxxxx").await?;
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let urls = platform.tenants().create_application_urls("ten_xxxxx").await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -340,3 +603,169 @@ impl<'a> TenantService<'a> {
         self.client.post(&path, &serde_json::json!({})).await
     }
 }
+
+/// Wrapper for acting on behalf of a specific tenant (Platform API).
+///
+/// Obtained from [`PlatformClient::tenant`](crate::PlatformClient::tenant); every
+/// request made through it carries the tenant's ID.
+pub struct TenantWrapper<'a> {
+    client: &'a PayjpClient,
+    tenant_id: String,
+}
+
+impl<'a> TenantWrapper<'a> {
+    /// Create a new tenant wrapper.
+    pub(crate) fn new(client: &'a PayjpClient, tenant_id: String) -> Self {
+        Self { client, tenant_id }
+    }
+
+    /// Get the tenant ID.
+    pub fn id(&self) -> &str {
+        &self.tenant_id
+    }
+
+    /// Retrieve the tenant.
+    pub async fn retrieve(&self) -> PayjpResult<Tenant> {
+        self.client.retrieve_resource(&self.tenant_id).await
+    }
+
+    /// Access the charges service for this tenant.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{CreateChargeParams, PlatformClient};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let charge = platform.tenant("ten_xxxxx")
+    ///     .charges()
+    ///     .create_with_fee(CreateChargeParams::new(1000, "jpy").card("tok_xxxxx"), 100)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn charges(&self) -> TenantChargeService<'a> {
+        TenantChargeService::new(self.client, self.tenant_id.clone())
+    }
+
+    /// Create a 3D Secure request for a card or charge of this tenant.
+    pub async fn create_three_d_secure_request(
+        &self,
+        mut params: CreateThreeDSecureRequestParams,
+    ) -> PayjpResult<ThreeDSecureRequest> {
+        params.tenant = Some(self.tenant_id.clone());
+        self.client.three_d_secure_requests().create(params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_params_payout_settings() {
+        let params = CreateTenantParams::new()
+            .id("ten_shop_1")
+            .platform_fee_rate("95")
+            .minimum_transfer_amount(1000)
+            .payjp_fee_included(true);
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert_eq!(
+            encoded,
+            "id=ten_shop_1&platform_fee_rate=95&minimum_transfer_amount=1000&payjp_fee_included=true"
+        );
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn test_transfer_schedule_flat_encoding() {
+        let params = CreateTenantParams::new().transfer_schedule(TransferSchedule::monthly(15));
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "transfer_interval=monthly&transfer_monthly_anchor=15"
+        );
+
+        let params =
+            UpdateTenantParams::new().transfer_schedule(TransferSchedule::weekly("friday"));
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "transfer_interval=weekly&transfer_weekly_anchor=friday"
+        );
+
+        let tenant: Tenant = serde_json::from_value(serde_json::json!({
+            "id": "ten_1",
+            "object": "tenant",
+            "livemode": false,
+            "created": 1704067200,
+            "transfer_schedule": { "interval": "monthly", "monthly_anchor": 31 }
+        }))
+        .unwrap();
+        let schedule = tenant.transfer_schedule.unwrap();
+        assert_eq!(schedule.interval, TransferInterval::Monthly);
+        assert_eq!(schedule.monthly_anchor, Some(31));
+    }
+
+    #[test]
+    fn test_create_params_business_information() {
+        let params = CreateTenantParams::new()
+            .name("shop")
+            .business_name("Pay Shop")
+            .business_url("https://shop.example.com")
+            .business_phone("03-1234-5678");
+
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "name=shop&business_name=Pay+Shop&business_url=https%3A%2F%2Fshop.example.com\
+            &business_phone=03-1234-5678"
+        );
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn test_bank_account_flat_encoding() {
+        let account = BankAccount {
+            bank_code: "0001".to_string(),
+            branch_code: "123".to_string(),
+            account_type: "普通".to_string(),
+            account_number: "1234567".to_string(),
+            account_holder_name: "ペイ タロウ".to_string(),
+        };
+        let expected = "bank_code=0001&bank_branch_code=123\
+            &bank_account_type=%E6%99%AE%E9%80%9A&bank_account_number=1234567\
+            &bank_account_holder_name=%E3%83%9A%E3%82%A4+%E3%82%BF%E3%83%AD%E3%82%A6";
+
+        let params = CreateTenantParams::new()
+            .name("shop")
+            .bank_account(account.clone());
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            format!("name=shop&{}", expected)
+        );
+
+        let params = UpdateTenantParams::new().bank_account(account);
+        assert_eq!(serde_urlencoded::to_string(&params).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_create_params_validation() {
+        let invalid = [
+            CreateTenantParams::new().id(""),
+            CreateTenantParams::new().id("ten shop"),
+            CreateTenantParams::new().id("x".repeat(MAX_TENANT_ID_LENGTH + 1)),
+            CreateTenantParams::new().platform_fee_rate("ten"),
+            CreateTenantParams::new().platform_fee_rate("95.01"),
+            CreateTenantParams::new().business_url("ftp://example.com"),
+            CreateTenantParams::new().business_phone("03 1234 5678"),
+            CreateTenantParams::new().minimum_transfer_amount(999),
+            CreateTenantParams::new().transfer_schedule(TransferSchedule::monthly(0)),
+        ];
+        for params in invalid {
+            assert!(
+                matches!(params.validate(), Err(PayjpError::InvalidRequest(_))),
+                "{:?} should be rejected",
+                params
+            );
+        }
+    }
+}
@@ -2,7 +2,10 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
+use crate::pagination::ListStream;
 use crate::params::ListParams;
+use crate::resources::transfer::TransferStatus;
+use crate::resources::ObjectKind;
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +16,7 @@ pub struct TenantTransfer {
     pub id: String,
 
     /// Object type (always "tenant_transfer").
+    #[serde(deserialize_with = "TenantTransfer::deserialize_object")]
     pub object: String,
 
     /// Whether this transfer was created in live mode.
@@ -31,7 +35,7 @@ pub struct TenantTransfer {
     pub currency: String,
 
     /// Transfer status.
-    pub status: String,
+    pub status: TransferStatus,
 
     /// Summary of charges included in this transfer.
     pub summary: TenantTransferSummary,
@@ -45,6 +49,10 @@ pub struct TenantTransfer {
     pub term: Option<String>,
 }
 
+impl ObjectKind for TenantTransfer {
+    const OBJECT: &'static str = "tenant_transfer";
+}
+
 /// Summary of charges in a tenant transfer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TenantTransferSummary {
@@ -83,16 +91,15 @@ impl<'a> TenantTransferService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::PayjpClient;
+    /// # use payjp::PlatformClient;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let transfer = client.tenant_transfers().retrieve("ttr_xxxxx").await?;
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let transfer = platform.tenant_transfers().retrieve("ttr_xxxxx").await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn retrieve(&self, transfer_id: &str) -> PayjpResult<TenantTransfer> {
-        let path = format!("/tenant_transfers/{}", transfer_id);
-        self.client.get(&path).await
+        self.client.retrieve_resource(transfer_id).await
     }
 
     /// List all tenant transfers.
@@ -100,16 +107,37 @@ impl<'a> TenantTransferService<'a> {
     /// # Example
     ///
     /// ```no_run
-    /// # use payjp::{PayjpClient, ListParams};
+    /// # use payjp::{PlatformClient, ListParams};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
-    /// let transfers = client.tenant_transfers().list(
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let transfers = platform.tenant_transfers().list(
     ///     ListParams::new().limit(10)
     /// ).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<TenantTransfer>> {
-        self.client.get_with_params("/tenant_transfers", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all tenant transfers, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PlatformClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let platform = PlatformClient::new("sk_test_xxxxx")?;
+    /// let mut transfers = platform.tenant_transfers().list_all(ListParams::new().max_limit());
+    /// while let Some(transfer) = transfers.try_next().await? {
+    ///     println!("{}: ¥{}", transfer.id, transfer.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, TenantTransfer> {
+        self.client.stream_resource(params)
     }
 }
@@ -2,7 +2,9 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::ListParams;
+use crate::pagination::ListStream;
+use crate::params::{ListParams, MAX_LIMIT};
+use crate::resources::{string_enum, ObjectKind};
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +15,7 @@ pub struct Statement {
     pub id: String,
 
     /// Object type (always "statement").
+    #[serde(deserialize_with = "Statement::deserialize_object")]
     pub object: String,
 
     /// Whether this statement was created in live mode.
@@ -38,18 +41,53 @@ pub struct Statement {
     pub balance_id: Option<String>,
 
     /// Statement type (optional).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub statement_type: Option<String>,
+    #[serde(
+        rename = "type",
+        alias = "statement_type",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub statement_type: Option<StatementType>,
 
     /// Updated timestamp (Unix timestamp, optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated: Option<i64>,
 }
 
+impl ObjectKind for Statement {
+    const OBJECT: &'static str = "statement";
+}
+
+string_enum! {
+    /// Kind of amounts a statement reports.
+    ///
+    /// Unknown statement types are preserved as [`StatementType::Other`] together
+    /// with the raw `type` string.
+    pub enum StatementType {
+        /// Other statement types not explicitly handled (raw `type` string).
+        Other(String),
+
+        /// Sales proceeds: charges, refunds and their fees.
+        Sales => "sales",
+
+        /// PAY.JP service fees.
+        ServiceFee => "service_fee",
+
+        /// Unclaimed proceeds forfeited after the claim period.
+        Forfeit => "forfeit",
+
+        /// Bank transfer fees.
+        TransferFee => "transfer_fee",
+
+        /// Other adjustments.
+        Misc => "misc",
+    }
+}
+
 /// Statement URLs response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatementUrls {
-    /// Object type (always "statement_urls").
+    /// Object type (always "statement_url").
+    #[serde(deserialize_with = "StatementUrls::deserialize_object")]
     pub object: String,
 
     /// Expiration timestamp for the URLs (Unix timestamp).
@@ -60,6 +98,98 @@ pub struct StatementUrls {
     pub url: Option<String>,
 }
 
+impl ObjectKind for StatementUrls {
+    const OBJECT: &'static str = "statement_url";
+}
+
+/// Parameters for requesting statement download URLs.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StatementUrlsParams {
+    /// Platform API: get the platform-view statement instead of the tenant's (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platformer: Option<bool>,
+}
+
+impl StatementUrlsParams {
+    /// Create new statement URL parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the platform-view statement (Platform API).
+    pub fn platformer(mut self, platformer: bool) -> Self {
+        self.platformer = Some(platformer);
+        self
+    }
+}
+
+/// Parameters for listing statements.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ListStatementParams {
+    /// Maximum number of items to return (default: 10, max: 100).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+
+    /// Offset for pagination (default: 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /// Return statements created since this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+
+    /// Return statements created until this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<i64>,
+
+    /// Filter by term ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+}
+
+impl From<ListParams> for ListStatementParams {
+    fn from(params: ListParams) -> Self {
+        Self {
+            limit: params.limit,
+            offset: params.offset,
+            since: params.since,
+            until: params.until,
+            ..Default::default()
+        }
+    }
+}
+
+impl ListStatementParams {
+    /// Create new list statement parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit for the number of items to return.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the largest page size the API allows ([`MAX_LIMIT`]).
+    pub fn max_limit(mut self) -> Self {
+        self.limit = Some(MAX_LIMIT);
+        self
+    }
+
+    /// Set the offset for pagination.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Filter by term ID.
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.term = Some(term.into());
+        self
+    }
+}
+
 /// Service for retrieving statements.
 pub struct StatementService<'a> {
     client: &'a PayjpClient,
@@ -84,8 +214,7 @@ impl<'a> StatementService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, statement_id: &str) -> PayjpResult<Statement> {
-        let path = format!("/statements/{}", statement_id);
-        self.client.get(&path).await
+        self.client.retrieve(statement_id).await
     }
 
     /// Get download URLs for a statement.
@@ -101,8 +230,31 @@ impl<'a> StatementService<'a> {
     /// # }
     /// ```
     pub async fn statement_urls(&self, statement_id: &str) -> PayjpResult<StatementUrls> {
+        self.statement_urls_with(statement_id, StatementUrlsParams::new()).await
+    }
+
+    /// Get download URLs for a statement with additional parameters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, StatementUrlsParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let urls = client.statements().statement_urls_with(
+    ///     "st_xxxxx",
+    ///     StatementUrlsParams::new()
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn statement_urls_with(
+        &self,
+        statement_id: &str,
+        params: StatementUrlsParams,
+    ) -> PayjpResult<StatementUrls> {
         let path = format!("/statements/{}/statement_urls", statement_id);
-        self.client.post(&path, &serde_json::json!({})).await
+        self.client.post(&path, &params).await
     }
 
     /// List all statements.
@@ -120,6 +272,81 @@ impl<'a> StatementService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Statement>> {
-        self.client.get_with_params("/statements", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// List all statements, automatically fetching further pages as the stream is consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListStatementParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut statements = client.statements().list_all(
+    ///     ListStatementParams::new().limit(100).term("tm_xxxxx")
+    /// );
+    /// while let Some(statement) = statements.try_next().await? {
+    ///     println!("{}: {:?}", statement.id, statement.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListStatementParams) -> ListStream<'a, Statement> {
+        self.client.stream_resource(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_type_preserves_raw_string() {
+        let statement: Statement = serde_json::from_value(serde_json::json!({
+            "id": "st_1",
+            "object": "statement",
+            "livemode": false,
+            "created": 1700000000,
+            "type": "service_fee"
+        }))
+        .unwrap();
+        assert_eq!(statement.statement_type, Some(StatementType::ServiceFee));
+
+        let unknown: StatementType = serde_json::from_str("\"platform_fee\"").unwrap();
+        assert_eq!(unknown, StatementType::Other("platform_fee".to_string()));
+        assert_eq!(unknown.to_string(), "platform_fee");
+        assert_eq!(
+            serde_json::to_string(&unknown).unwrap(),
+            "\"platform_fee\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_statement_urls_deserializes_response() {
+        use crate::{ClientOptions, PayjpClient};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/statements/st_1/statement_urls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "statement_url",
+                "url": "https://pay.jp/_/statements/st_1",
+                "expires": 1700003600
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let urls = client.statements().statement_urls("st_1").await.unwrap();
+        assert_eq!(urls.object, "statement_url");
+        assert_eq!(urls.expires, 1700003600);
     }
 }
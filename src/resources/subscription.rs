@@ -2,10 +2,16 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::params::{ListParams, Metadata};
+use crate::migration::{self, MigrationOptions, MigrationReport};
+use crate::pause::{AutoResumer, RESUME_AT_METADATA_KEY};
+use crate::pagination::ListStream;
+use crate::params::{impl_metadata_setters, ListParams, Metadata, MAX_LIMIT};
+use crate::query::SubscriptionQuery;
+use crate::resources::ObjectKind;
 use crate::resources::plan::Plan;
-use crate::response::ListResponse;
+use crate::response::{Deleted, ListResponse};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A subscription represents a recurring payment for a customer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +20,7 @@ pub struct Subscription {
     pub id: String,
 
     /// Object type (always "subscription").
+    #[serde(deserialize_with = "Subscription::deserialize_object")]
     pub object: String,
 
     /// Whether this subscription was created in live mode.
@@ -62,11 +69,36 @@ pub struct Subscription {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prorate: Option<bool>,
 
+    /// Platform API: Tenant ID (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
     /// Set of key-value pairs for storing additional information (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
 }
 
+impl ObjectKind for Subscription {
+    const OBJECT: &'static str = "subscription";
+}
+
+impl Subscription {
+    /// The date (Unix timestamp) this subscription is scheduled to resume, if it was
+    /// paused with [`SubscriptionService::pause_until`].
+    ///
+    /// A date recorded for an earlier pause is ignored.
+    pub fn scheduled_resume(&self) -> Option<i64> {
+        if self.status != SubscriptionStatus::Paused {
+            return None;
+        }
+        let resume_at = self.metadata.as_ref()?.get(RESUME_AT_METADATA_KEY)?.parse().ok()?;
+        match self.paused_at {
+            Some(paused_at) if resume_at <= paused_at => None,
+            _ => Some(resume_at),
+        }
+    }
+}
+
 /// Status of a subscription.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -84,6 +116,105 @@ pub enum SubscriptionStatus {
     Paused,
 }
 
+/// Parameters for listing subscriptions with server-side filters.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ListSubscriptionParams {
+    /// Maximum number of items to return (default: 10, max: 100).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+
+    /// Offset for pagination (default: 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+
+    /// Return subscriptions created since this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+
+    /// Return subscriptions created until this timestamp (Unix timestamp).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<i64>,
+
+    /// Filter by plan ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+
+    /// Filter by customer ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<String>,
+
+    /// Filter by subscription status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<SubscriptionStatus>,
+}
+
+impl From<ListParams> for ListSubscriptionParams {
+    fn from(params: ListParams) -> Self {
+        Self {
+            limit: params.limit,
+            offset: params.offset,
+            since: params.since,
+            until: params.until,
+            ..Default::default()
+        }
+    }
+}
+
+impl ListSubscriptionParams {
+    /// Create new list subscription parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit for the number of items to return.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the largest page size the API allows ([`MAX_LIMIT`]).
+    pub fn max_limit(mut self) -> Self {
+        self.limit = Some(MAX_LIMIT);
+        self
+    }
+
+    /// Set the offset for pagination.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Set the since timestamp filter.
+    pub fn since(mut self, since: i64) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Set the until timestamp filter.
+    pub fn until(mut self, until: i64) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Filter by plan ID.
+    pub fn plan(mut self, plan: impl Into<String>) -> Self {
+        self.plan = Some(plan.into());
+        self
+    }
+
+    /// Filter by customer ID.
+    pub fn customer(mut self, customer: impl Into<String>) -> Self {
+        self.customer = Some(customer.into());
+        self
+    }
+
+    /// Filter by subscription status.
+    pub fn status(mut self, status: SubscriptionStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
+
 /// Parameters for creating a subscription.
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateSubscriptionParams {
@@ -102,7 +233,7 @@ pub struct CreateSubscriptionParams {
     pub prorate: Option<bool>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -129,16 +260,10 @@ impl CreateSubscriptionParams {
         self.prorate = Some(prorate);
         self
     }
-
-    /// Add metadata to the subscription.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_metadata_setters!(CreateSubscriptionParams, "subscription");
+
 /// Parameters for updating a subscription.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct UpdateSubscriptionParams {
@@ -155,7 +280,7 @@ pub struct UpdateSubscriptionParams {
     pub prorate: Option<bool>,
 
     /// Set of key-value pairs for storing additional information.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten, serialize_with = "crate::params::serialize_metadata")]
     pub metadata: Option<Metadata>,
 }
 
@@ -182,16 +307,10 @@ impl UpdateSubscriptionParams {
         self.prorate = Some(prorate);
         self
     }
-
-    /// Add metadata to the subscription.
-    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata
-            .get_or_insert_with(Default::default)
-            .insert(key.into(), value.into());
-        self
-    }
 }
 
+impl_metadata_setters!(UpdateSubscriptionParams, "subscription", update);
+
 /// Parameters for pausing a subscription.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct PauseSubscriptionParams {}
@@ -206,6 +325,10 @@ impl PauseSubscriptionParams {
 /// Parameters for resuming a subscription.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct ResumeSubscriptionParams {
+    /// Trial end date as Unix timestamp to resume into a trial period (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_end: Option<i64>,
+
     /// Whether to charge for the period during which the subscription was paused.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prorate: Option<bool>,
@@ -217,6 +340,14 @@ impl ResumeSubscriptionParams {
         Self::default()
     }
 
+    /// Resume into a trial period ending at the given timestamp.
+    ///
+    /// No charge is made until the trial ends.
+    pub fn trial_end(mut self, timestamp: i64) -> Self {
+        self.trial_end = Some(timestamp);
+        self
+    }
+
     /// Set whether to prorate charges for the paused period.
     pub fn prorate(mut self, prorate: bool) -> Self {
         self.prorate = Some(prorate);
@@ -236,17 +367,7 @@ impl CancelSubscriptionParams {
 }
 
 /// Response from deleting a subscription.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeletedSubscription {
-    /// Subscription ID.
-    pub id: String,
-
-    /// Whether the deletion was successful.
-    pub deleted: bool,
-
-    /// Whether this subscription was in live mode.
-    pub livemode: bool,
-}
+pub type DeletedSubscription = Deleted<Subscription>;
 
 /// Service for managing subscriptions.
 pub struct SubscriptionService<'a> {
@@ -290,8 +411,33 @@ impl<'a> SubscriptionService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, subscription_id: &str) -> PayjpResult<Subscription> {
-        let path = format!("/subscriptions/{}", subscription_id);
-        self.client.get(&path).await
+        self.client.retrieve(subscription_id).await
+    }
+
+    /// Retrieve many subscriptions by ID, with at most `concurrency` requests in flight.
+    ///
+    /// Repeated IDs are retrieved once; see [`PayjpClient::retrieve_many`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscriptions = client.subscriptions().retrieve_many(["sub_1", "sub_2"], 4).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve_many<I>(
+        &self,
+        ids: I,
+        concurrency: usize,
+    ) -> HashMap<String, PayjpResult<Subscription>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.client.retrieve_many(ids, concurrency).await
     }
 
     /// Update a subscription.
@@ -314,8 +460,7 @@ impl<'a> SubscriptionService<'a> {
         subscription_id: &str,
         params: UpdateSubscriptionParams,
     ) -> PayjpResult<Subscription> {
-        let path = format!("/subscriptions/{}", subscription_id);
-        self.client.post(&path, &params).await
+        self.client.update_resource(subscription_id, &params).await
     }
 
     /// Pause a subscription.
@@ -338,8 +483,21 @@ impl<'a> SubscriptionService<'a> {
         subscription_id: &str,
         params: PauseSubscriptionParams,
     ) -> PayjpResult<Subscription> {
-        let path = format!("/subscriptions/{}/pause", subscription_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(subscription_id, "pause", &params).await
+    }
+
+    /// Pause a subscription and schedule it to resume at `resume_at` (Unix timestamp).
+    ///
+    /// The subscription is resumed by an [`AutoResumer`]; see the
+    /// [`pause`](crate::pause) module for an example.
+    pub async fn pause_until(
+        &self,
+        subscription_id: &str,
+        resume_at: i64,
+    ) -> PayjpResult<Subscription> {
+        AutoResumer::new(self.client)
+            .pause_until(subscription_id, resume_at)
+            .await
     }
 
     /// Resume a paused subscription.
@@ -362,8 +520,7 @@ impl<'a> SubscriptionService<'a> {
         subscription_id: &str,
         params: ResumeSubscriptionParams,
     ) -> PayjpResult<Subscription> {
-        let path = format!("/subscriptions/{}/resume", subscription_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(subscription_id, "resume", &params).await
     }
 
     /// Cancel a subscription.
@@ -386,8 +543,7 @@ impl<'a> SubscriptionService<'a> {
         subscription_id: &str,
         params: CancelSubscriptionParams,
     ) -> PayjpResult<Subscription> {
-        let path = format!("/subscriptions/{}/cancel", subscription_id);
-        self.client.post(&path, &params).await
+        self.client.resource_action(subscription_id, "cancel", &params).await
     }
 
     /// Delete a subscription.
@@ -403,8 +559,7 @@ impl<'a> SubscriptionService<'a> {
     /// # }
     /// ```
     pub async fn delete(&self, subscription_id: &str) -> PayjpResult<DeletedSubscription> {
-        let path = format!("/subscriptions/{}", subscription_id);
-        self.client.delete(&path).await
+        self.client.delete_resource(subscription_id).await
     }
 
     /// List all subscriptions.
@@ -422,6 +577,308 @@ impl<'a> SubscriptionService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Subscription>> {
-        self.client.get_with_params("/subscriptions", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all subscriptions matching the filters, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListSubscriptionParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut subscriptions = client
+    ///     .subscriptions()
+    ///     .list_all(ListSubscriptionParams::new().max_limit().plan("pln_xxxxx"));
+    /// while let Some(subscription) = subscriptions.try_next().await? {
+    ///     println!("{}: {:?}", subscription.id, subscription.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListSubscriptionParams) -> ListStream<'a, Subscription> {
+        self.client.stream_resource(params)
+    }
+
+    /// Stream the subscriptions matching `query`.
+    ///
+    /// See the [`query`](crate::query) module for which conditions are
+    /// filtered server-side.
+    pub fn query(&self, query: SubscriptionQuery) -> ListStream<'a, Subscription> {
+        query.run(|params| self.list_all(params))
+    }
+
+    /// Stream all subscriptions with the given status.
+    ///
+    /// The status is filtered on the server and checked again on each item, so the
+    /// stream only yields matching subscriptions.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, SubscriptionStatus};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let paused: Vec<_> = client
+    ///     .subscriptions()
+    ///     .stream_by_status(SubscriptionStatus::Paused)
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_by_status(&self, status: SubscriptionStatus) -> ListStream<'a, Subscription> {
+        let params = ListSubscriptionParams::new()
+            .max_limit()
+            .status(status.clone());
+        self.list_all(params)
+            .filter_local(move |subscription| subscription.status == status)
+    }
+
+    /// Move every subscription on `from_plan` that is not canceled to `to_plan`.
+    ///
+    /// Subscriptions are updated one at a time, with the proration, dry-run and
+    /// pacing settings of `options`. A failed update is recorded in the report and
+    /// does not stop the migration; failing to find `to_plan` or to list the
+    /// subscriptions does. See the [`migration`](crate::migration) module for an
+    /// example.
+    pub async fn migrate_plan(
+        &self,
+        from_plan: &str,
+        to_plan: &str,
+        options: MigrationOptions,
+    ) -> PayjpResult<MigrationReport> {
+        migration::migrate_plan(self.client, from_plan, to_plan, options).await
+    }
+}
+
+/// Service for managing the subscriptions of a specific customer.
+pub struct CustomerSubscriptionService<'a> {
+    client: &'a PayjpClient,
+    customer_id: String,
+}
+
+impl<'a> CustomerSubscriptionService<'a> {
+    /// Create a new subscription service for a specific customer.
+    pub(crate) fn new(client: &'a PayjpClient, customer_id: String) -> Self {
+        Self {
+            client,
+            customer_id,
+        }
+    }
+
+    /// Subscribe the customer to a plan.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscription = client.customer("cus_xxxxx")
+    ///     .subscriptions()
+    ///     .create("pln_xxxxx")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create(&self, plan: impl Into<String>) -> PayjpResult<Subscription> {
+        self.create_with(CreateSubscriptionParams::new(self.customer_id.as_str(), plan))
+            .await
+    }
+
+    /// Subscribe the customer using full parameters.
+    ///
+    /// The `customer` field of `params` is replaced with this customer's ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, CreateSubscriptionParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscription = client.customer("cus_xxxxx")
+    ///     .subscriptions()
+    ///     .create_with(CreateSubscriptionParams::new("cus_xxxxx", "pln_xxxxx").prorate(true))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_with(
+        &self,
+        mut params: CreateSubscriptionParams,
+    ) -> PayjpResult<Subscription> {
+        params.customer = self.customer_id.clone();
+        self.client.post("/subscriptions", &params).await
+    }
+
+    /// Retrieve one of the customer's subscriptions by ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscription = client.customer("cus_xxxxx")
+    ///     .subscriptions()
+    ///     .retrieve("sub_xxxxx")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve(&self, subscription_id: &str) -> PayjpResult<Subscription> {
+        let path = format!("/customers/{}/subscriptions/{}", self.customer_id, subscription_id);
+        self.client.get(&path).await
+    }
+
+    /// List the customer's subscriptions.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let subscriptions = client.customer("cus_xxxxx").subscriptions().list(
+    ///     ListParams::new().limit(10)
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Subscription>> {
+        let path = format!("/customers/{}/subscriptions", self.customer_id);
+        self.client.get_list(&path, &params).await
+    }
+
+    /// Stream all of the customer's subscriptions, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let customer = client.customer("cus_xxxxx");
+    /// let mut subscriptions = customer.subscriptions().list_all(ListParams::new().max_limit());
+    /// while let Some(subscription) = subscriptions.try_next().await? {
+    ///     println!("{}: {:?}", subscription.id, subscription.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Subscription> {
+        let path = format!("/customers/{}/subscriptions", self.customer_id);
+        ListStream::new(self.client, path, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use futures::TryStreamExt;
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn subscription(id: &str, status: &str) -> serde_json::Value {
+        let fields = serde_json::json!({
+            "current_period_start": 1704067200,
+            "current_period_end": 1706745600
+        });
+        fixtures::with(fixtures::subscription(id, status), fields)
+    }
+
+    #[tokio::test]
+    async fn test_stream_by_status_filters_server_and_client_side() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/subscriptions"))
+            .and(query_param("status", "paused"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [subscription("sub_1", "paused"), subscription("sub_2", "active")],
+                "has_more": false,
+                "url": "/v1/subscriptions",
+                "count": 2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let paused: Vec<Subscription> = client
+            .subscriptions()
+            .stream_by_status(SubscriptionStatus::Paused)
+            .try_collect()
+            .await
+            .expect("Failed to list subscriptions");
+        let ids: Vec<_> = paused.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["sub_1"]);
+    }
+
+    #[tokio::test]
+    async fn test_customer_subscriptions_are_scoped_to_customer() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1/subscriptions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [subscription("sub_1", "active")],
+                "has_more": false,
+                "url": "/v1/customers/cus_1/subscriptions",
+                "count": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/subscriptions"))
+            .and(body_string_contains("customer=cus_1"))
+            .and(body_string_contains("plan=pln_1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(subscription("sub_2", "active")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let customer = client.customer("cus_1");
+
+        let subscriptions: Vec<Subscription> = customer
+            .subscriptions()
+            .list_all(ListParams::new())
+            .try_collect()
+            .await
+            .expect("Failed to list subscriptions");
+        assert_eq!(subscriptions[0].id, "sub_1");
+
+        let created = customer.subscriptions().create("pln_1").await.unwrap();
+        assert_eq!(created.id, "sub_2");
+    }
+
+    #[test]
+    fn test_resume_params_trial_end() {
+        let params = ResumeSubscriptionParams::new()
+            .trial_end(1700000000)
+            .prorate(false);
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "trial_end=1700000000&prorate=false"
+        );
     }
 }
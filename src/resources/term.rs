@@ -2,7 +2,12 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
+use crate::pagination::ListStream;
 use crate::params::ListParams;
+use crate::resources::charge::{Charge, ListChargeParams};
+use crate::resources::statement::{ListStatementParams, Statement};
+use crate::resources::transfer::Transfer;
+use crate::resources::ObjectKind;
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +18,7 @@ pub struct Term {
     pub id: String,
 
     /// Object type (always "term").
+    #[serde(deserialize_with = "Term::deserialize_object")]
     pub object: String,
 
     /// Whether this term was created in live mode.
@@ -37,6 +43,10 @@ pub struct Term {
     pub dispute_count: Option<i64>,
 }
 
+impl ObjectKind for Term {
+    const OBJECT: &'static str = "term";
+}
+
 /// Service for retrieving terms.
 pub struct TermService<'a> {
     client: &'a PayjpClient,
@@ -61,8 +71,7 @@ impl<'a> TermService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, term_id: &str) -> PayjpResult<Term> {
-        let path = format!("/terms/{}", term_id);
-        self.client.get(&path).await
+        self.client.retrieve(term_id).await
     }
 
     /// List all terms.
@@ -80,6 +89,261 @@ impl<'a> TermService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Term>> {
-        self.client.get_with_params("/terms", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all terms, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut terms = client.terms().list_all(ListParams::new().max_limit());
+    /// while let Some(term) = terms.try_next().await? {
+    ///     println!("{}", term.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Term> {
+        self.client.stream_resource(params)
+    }
+}
+
+/// Wrapper for accessing a specific term and the resources aggregated in it.
+pub struct TermWrapper<'a> {
+    client: &'a PayjpClient,
+    term_id: String,
+}
+
+impl<'a> TermWrapper<'a> {
+    /// Create a new term wrapper.
+    pub(crate) fn new(client: &'a PayjpClient, term_id: String) -> Self {
+        Self { client, term_id }
+    }
+
+    /// Get the term ID.
+    pub fn id(&self) -> &str {
+        &self.term_id
+    }
+
+    /// Retrieve the term details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let term = client.term("tm_xxxxx").retrieve().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn retrieve(&self) -> PayjpResult<Term> {
+        TermService::new(self.client).retrieve(&self.term_id).await
+    }
+
+    /// List all statements issued for this term.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let statements: Vec<_> = client.term("tm_xxxxx").statements().try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn statements(&self) -> ListStream<'a, Statement> {
+        let params = ListStatementParams::new()
+            .max_limit()
+            .term(self.term_id.clone());
+        self.client.stream_resource(params)
+    }
+
+    /// List all charges aggregated in this term.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut charges = client.term("tm_xxxxx").charges();
+    /// while let Some(charge) = charges.try_next().await? {
+    ///     println!("{}: {}", charge.id, charge.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn charges(&self) -> ListStream<'a, Charge> {
+        let params = ListChargeParams::new()
+            .max_limit()
+            .term(self.term_id.clone());
+        self.client.stream_resource(params)
+    }
+
+    /// List all transfers for this term.
+    ///
+    /// The transfers endpoint has no term filter, so transfers are fetched page
+    /// by page and filtered locally.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let transfers: Vec<_> = client.term("tm_xxxxx").transfers().try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transfers(&self) -> ListStream<'a, Transfer> {
+        let term_id = self.term_id.clone();
+        ListStream::new(self.client, "/transfers".to_string(), ListParams::new().max_limit())
+            .filter_local(move |transfer: &Transfer| transfer.term.as_deref() == Some(&term_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use futures::TryStreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn transfer(id: &str, term: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "transfer",
+            "livemode": false,
+            "created": 1700000000,
+            "amount": 1000,
+            "currency": "jpy",
+            "status": "paid",
+            "summary": {
+                "charge_amount": 1000,
+                "charge_count": 1,
+                "charge_fee": 0,
+                "refund_amount": 0,
+                "refund_count": 0
+            },
+            "term": term
+        })
+    }
+
+    #[tokio::test]
+    async fn test_term_navigation() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/statements"))
+            .and(query_param("term", "tm_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [{
+                    "id": "st_1",
+                    "object": "statement",
+                    "livemode": false,
+                    "created": 1700000000,
+                    "term": "tm_1"
+                }],
+                "has_more": false,
+                "url": "/v1/statements",
+                "count": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/transfers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [transfer("tr_1", "tm_1"), transfer("tr_2", "tm_2")],
+                "has_more": false,
+                "url": "/v1/transfers",
+                "count": 2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let term = client.term("tm_1");
+
+        let statements: Vec<_> = term.statements().try_collect().await.unwrap();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(statements[0].id, "st_1");
+
+        let transfers: Vec<_> = term.transfers().try_collect().await.unwrap();
+        let ids: Vec<_> = transfers.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["tr_1"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_follows_pages() {
+        let server = MockServer::start().await;
+        let page = |ids: &[&str], has_more: bool| {
+            let data: Vec<_> = ids
+                .iter()
+                .map(|id| {
+                    serde_json::json!({
+                        "id": id,
+                        "object": "term",
+                        "livemode": false,
+                        "charge_count": 1,
+                        "refund_count": 0
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": data,
+                "has_more": has_more,
+                "url": "/v1/terms",
+                "count": 3
+            }))
+        };
+        Mock::given(method("GET"))
+            .and(path("/terms"))
+            .and(query_param("offset", "2"))
+            .respond_with(page(&["tm_3"], false))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/terms"))
+            .respond_with(page(&["tm_1", "tm_2"], true))
+            .with_priority(2)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let terms: Vec<_> = client
+            .terms()
+            .list_all(ListParams::new().limit(2))
+            .try_collect()
+            .await
+            .expect("Failed to list terms");
+        let ids: Vec<_> = terms.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["tm_1", "tm_2", "tm_3"]);
     }
 }
@@ -2,7 +2,9 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
+use crate::pagination::ListStream;
 use crate::params::ListParams;
+use crate::resources::ObjectKind;
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +15,7 @@ pub struct ThreeDSecureRequest {
     pub id: String,
 
     /// Object type (always "three_d_secure_request").
+    #[serde(deserialize_with = "ThreeDSecureRequest::deserialize_object")]
     pub object: String,
 
     /// Whether this request was created in live mode.
@@ -44,7 +47,8 @@ pub struct ThreeDSecureRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tenant: Option<String>,
 
-    /// State parameter for callback (optional).
+    /// Progress of the request: `created`, `in_progress`, `result_received` or
+    /// `finished` (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
 
@@ -53,6 +57,10 @@ pub struct ThreeDSecureRequest {
     pub result: Option<ThreeDSecureResult>,
 }
 
+impl ObjectKind for ThreeDSecureRequest {
+    const OBJECT: &'static str = "three_d_secure_request";
+}
+
 /// Status of a 3D Secure request.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -102,9 +110,9 @@ pub struct CreateThreeDSecureRequestParams {
     /// Resource ID (card ID like `car_xxxxx` or charge ID like `ch_xxxxx`).
     pub resource_id: String,
 
-    /// Tenant ID (Platform API, optional).
+    /// Tenant ID (Platform API, optional), set through `PlatformClient::tenant`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tenant: Option<String>,
+    pub(crate) tenant: Option<String>,
 }
 
 impl CreateThreeDSecureRequestParams {
@@ -124,12 +132,6 @@ impl CreateThreeDSecureRequestParams {
             tenant: None,
         }
     }
-
-    /// Set the tenant ID (Platform API).
-    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
-        self.tenant = Some(tenant.into());
-        self
-    }
 }
 
 /// Service for managing 3D Secure requests.
@@ -178,8 +180,7 @@ impl<'a> ThreeDSecureRequestService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, request_id: &str) -> PayjpResult<ThreeDSecureRequest> {
-        let path = format!("/three_d_secure_requests/{}", request_id);
-        self.client.get(&path).await
+        self.client.retrieve(request_id).await
     }
 
     /// List all 3D Secure requests.
@@ -198,7 +199,30 @@ impl<'a> ThreeDSecureRequestService<'a> {
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<ThreeDSecureRequest>> {
         self.client
-            .get_with_params("/three_d_secure_requests", &params)
+            .get_list("/three_d_secure_requests", &params)
             .await
     }
+
+    /// Stream all 3D Secure requests, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut tds_requests = client
+    ///     .three_d_secure_requests()
+    ///     .list_all(ListParams::new().max_limit());
+    /// while let Some(tds_request) = tds_requests.try_next().await? {
+    ///     println!("{}", tds_request.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, ThreeDSecureRequest> {
+        self.client.stream_resource(params)
+    }
 }
@@ -2,8 +2,12 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
-use crate::resources::card::Card;
+use crate::redact;
+use crate::resources::ObjectKind;
+use crate::resources::card::{Card, ThreeDSecureOutcome};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroize;
 
 /// A token represents a card that can be used to create a charge or customer.
 /// Tokens are one-time use and expire after a short period.
@@ -13,6 +17,7 @@ pub struct Token {
     pub id: String,
 
     /// Object type (always "token").
+    #[serde(deserialize_with = "Token::deserialize_object")]
     pub object: String,
 
     /// Whether this token was created in live mode.
@@ -28,9 +33,16 @@ pub struct Token {
     pub card: Card,
 }
 
+impl ObjectKind for Token {
+    const OBJECT: &'static str = "token";
+}
+
 /// Raw card details for creating a token (server-side only for testing).
 /// In production, tokens should be created client-side using PAY.JP.js.
-#[derive(Debug, Default, Clone, Serialize)]
+///
+/// The `Debug` output masks the card number (all but the last 4 digits) and the CVC,
+/// and both are zeroed in memory when the value is dropped.
+#[derive(Default, Clone, Serialize)]
 pub struct CardDetails {
     /// Card number (without spaces or hyphens).
     #[serde(rename = "card[number]")]
@@ -101,7 +113,15 @@ impl CardDetails {
             exp_month,
             exp_year,
             cvc: cvc.into(),
-            ..Default::default()
+            name: None,
+            address_line1: None,
+            address_line2: None,
+            address_city: None,
+            address_state: None,
+            address_zip: None,
+            country: None,
+            email: None,
+            phone: None,
         }
     }
 
@@ -118,12 +138,43 @@ impl CardDetails {
     }
 }
 
+impl fmt::Debug for CardDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardDetails")
+            .field("number", &redact::mask_card_number(&self.number))
+            .field("exp_month", &self.exp_month)
+            .field("exp_year", &self.exp_year)
+            .field("cvc", &"***")
+            .field("name", &self.name)
+            .field("address_line1", &self.address_line1)
+            .field("address_line2", &self.address_line2)
+            .field("address_city", &self.address_city)
+            .field("address_state", &self.address_state)
+            .field("address_zip", &self.address_zip)
+            .field("country", &self.country)
+            .field("email", &self.email)
+            .field("phone", &self.phone)
+            .finish()
+    }
+}
+
+impl Drop for CardDetails {
+    fn drop(&mut self) {
+        self.number.zeroize();
+        self.cvc.zeroize();
+    }
+}
+
 /// Parameters for creating a token.
 #[derive(Debug, Default, Clone, Serialize)]
 pub struct CreateTokenParams {
     /// Raw card details (server-side only for testing).
     #[serde(skip_serializing_if = "Option::is_none", flatten)]
     pub card: Option<CardDetails>,
+
+    /// Platform API: Tenant ID to scope the token to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
 }
 
 impl CreateTokenParams {
@@ -132,7 +183,18 @@ impl CreateTokenParams {
     /// **WARNING**: This should only be used for testing with test cards.
     /// In production, use PAY.JP.js to create tokens client-side.
     pub fn from_card(card: CardDetails) -> Self {
-        Self { card: Some(card) }
+        Self {
+            card: Some(card),
+            ..Self::default()
+        }
+    }
+
+    /// Set tenant (Platform API).
+    ///
+    /// Works with both [`TokenService`] and [`PublicTokenService`].
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
     }
 }
 
@@ -186,8 +248,7 @@ impl<'a> TokenService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, token_id: &str) -> PayjpResult<Token> {
-        let path = format!("/tokens/{}", token_id);
-        self.client.get(&path).await
+        self.client.retrieve(token_id).await
     }
 
     /// Finish 3D Secure authentication for a token.
@@ -203,8 +264,28 @@ impl<'a> TokenService<'a> {
     /// # }
     /// ```
     pub async fn tds_finish(&self, token_id: &str) -> PayjpResult<Token> {
-        let path = format!("/tokens/{}/tds_finish", token_id);
-        self.client.post(&path, &serde_json::json!({})).await
+        self.client.resource_action(token_id, "tds_finish", &serde_json::json!({})).await
+    }
+
+    /// Finish 3D Secure authentication for a token and classify the result.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::PayjpClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let outcome = client.tokens().tds_finish_outcome("tok_xxxxx").await?;
+    /// if !outcome.is_authenticated() {
+    ///     println!("ask the customer for another card");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tds_finish_outcome(&self, token_id: &str) -> PayjpResult<ThreeDSecureOutcome<Token>> {
+        let token = self.tds_finish(token_id).await?;
+        let status = token.card.three_d_secure_status.clone();
+        Ok(ThreeDSecureOutcome::new(token, status, None))
     }
 }
 
@@ -251,3 +332,28 @@ impl<'a> PublicTokenService<'a> {
         self.client.post("/tokens", &params).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_details_debug_is_masked() {
+        let card = CardDetails::new("4242424242424242", 12, 2030, "987").name("Taro Yamada");
+        let debug = format!("{:?}", CreateTokenParams::from_card(card));
+
+        assert!(debug.contains("************4242"));
+        assert!(!debug.contains("4242424242424242"));
+        assert!(!debug.contains("987"));
+        assert!(debug.contains("Taro Yamada"));
+    }
+
+    #[test]
+    fn test_create_params_tenant() {
+        let card = CardDetails::new("4242424242424242", 12, 2030, "123");
+        let params = CreateTokenParams::from_card(card).tenant("ten_shop_1");
+
+        let encoded = serde_urlencoded::to_string(&params).unwrap();
+        assert!(encoded.ends_with("&tenant=ten_shop_1"), "{}", encoded);
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::client::PayjpClient;
 use crate::error::PayjpResult;
+use crate::pagination::ListStream;
 use crate::params::ListParams;
+use crate::resources::{string_enum, ObjectKind};
 use crate::response::ListResponse;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +15,7 @@ pub struct Transfer {
     pub id: String,
 
     /// Object type (always "transfer").
+    #[serde(deserialize_with = "Transfer::deserialize_object")]
     pub object: String,
 
     /// Whether this transfer was created in live mode.
@@ -27,8 +30,8 @@ pub struct Transfer {
     /// Three-letter ISO currency code.
     pub currency: String,
 
-    /// Transfer status ("pending", "paid", "failed", "stop", or "carried_forward").
-    pub status: String,
+    /// Transfer status.
+    pub status: TransferStatus,
 
     /// Summary of charges included in this transfer.
     pub summary: TransferSummary,
@@ -50,6 +53,47 @@ pub struct Transfer {
     pub term: Option<String>,
 }
 
+impl ObjectKind for Transfer {
+    const OBJECT: &'static str = "transfer";
+}
+
+string_enum! {
+    /// Status of a transfer or tenant transfer.
+    ///
+    /// Unknown statuses are preserved as [`TransferStatus::Other`] together with
+    /// the raw `status` string.
+    #[non_exhaustive]
+    pub enum TransferStatus {
+        /// Other transfer statuses not explicitly handled (raw `status` string).
+        Other(String),
+
+        /// The transfer is scheduled and not yet paid.
+        Pending => "pending",
+
+        /// The transfer was paid to the bank account.
+        Paid => "paid",
+
+        /// The transfer failed, e.g. because of invalid bank details.
+        Failed => "failed",
+
+        /// The transfer was stopped by PAY.JP.
+        Stop => "stop",
+
+        /// The amount was below the minimum and carried forward to the next transfer.
+        CarriedForward => "carried_forward",
+    }
+}
+
+impl TransferStatus {
+    /// Whether the transfer can no longer change (paid, failed or carried forward).
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            TransferStatus::Paid | TransferStatus::Failed | TransferStatus::CarriedForward
+        )
+    }
+}
+
 /// Summary of charges in a transfer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferSummary {
@@ -112,8 +156,7 @@ impl<'a> TransferService<'a> {
     /// # }
     /// ```
     pub async fn retrieve(&self, transfer_id: &str) -> PayjpResult<Transfer> {
-        let path = format!("/transfers/{}", transfer_id);
-        self.client.get(&path).await
+        self.client.retrieve(transfer_id).await
     }
 
     /// List all transfers.
@@ -131,6 +174,47 @@ impl<'a> TransferService<'a> {
     /// # }
     /// ```
     pub async fn list(&self, params: ListParams) -> PayjpResult<ListResponse<Transfer>> {
-        self.client.get_with_params("/transfers", &params).await
+        self.client.list_resource(&params).await
+    }
+
+    /// Stream all transfers, fetching pages on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use payjp::{PayjpClient, ListParams};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = PayjpClient::new("sk_test_xxxxx")?;
+    /// let mut transfers = client.transfers().list_all(ListParams::new().max_limit());
+    /// while let Some(transfer) = transfers.try_next().await? {
+    ///     println!("{}: ¥{}", transfer.id, transfer.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_all(&self, params: ListParams) -> ListStream<'a, Transfer> {
+        self.client.stream_resource(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_status_preserves_raw_string() {
+        let status: TransferStatus = serde_json::from_str("\"carried_forward\"").unwrap();
+        assert_eq!(status, TransferStatus::CarriedForward);
+        assert!(status.is_final());
+        assert!(!TransferStatus::Pending.is_final());
+
+        let unknown: TransferStatus = serde_json::from_str("\"recombination\"").unwrap();
+        assert_eq!(unknown, TransferStatus::Other("recombination".to_string()));
+        assert_eq!(
+            serde_json::to_string(&unknown).unwrap(),
+            "\"recombination\""
+        );
     }
 }
@@ -0,0 +1,78 @@
+//! Response type for delete endpoints.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Response from deleting a resource of type `T`.
+///
+/// Every delete endpoint returns the same shape, so one type covers them all;
+/// `T` only records which kind of resource was deleted (e.g. `Deleted<Customer>`).
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Deleted<T> {
+    /// ID of the deleted resource.
+    pub id: String,
+
+    /// Whether the deletion was successful.
+    pub deleted: bool,
+
+    /// Whether the resource was in live mode.
+    pub livemode: bool,
+
+    #[serde(skip)]
+    resource: PhantomData<fn() -> T>,
+}
+
+impl<T> Deleted<T> {
+    /// Create a deletion response (e.g. for test doubles).
+    pub fn new(id: impl Into<String>, deleted: bool, livemode: bool) -> Self {
+        Self {
+            id: id.into(),
+            deleted,
+            livemode,
+            resource: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Deleted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deleted")
+            .field("id", &self.id)
+            .field("deleted", &self.deleted)
+            .field("livemode", &self.livemode)
+            .finish()
+    }
+}
+
+impl<T> Clone for Deleted<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.id.clone(), self.deleted, self.livemode)
+    }
+}
+
+impl<T> PartialEq for Deleted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.deleted == other.deleted && self.livemode == other.livemode
+    }
+}
+
+impl<T> Eq for Deleted<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Customer;
+
+    #[test]
+    fn test_deserialize_deleted() {
+        let deleted: Deleted<Customer> = serde_json::from_value(serde_json::json!({
+            "id": "cus_1",
+            "deleted": true,
+            "livemode": false
+        }))
+        .unwrap();
+        assert_eq!(deleted, Deleted::new("cus_1", true, false));
+    }
+}
@@ -1,11 +1,13 @@
 //! List response types for paginated API endpoints.
 
-use serde::{Deserialize, Serialize};
+use crate::resources::expect_object;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// A paginated list response from PAY.JP API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListResponse<T> {
     /// The object type (always "list").
+    #[serde(deserialize_with = "deserialize_list_object")]
     pub object: String,
 
     /// The list of items.
@@ -21,6 +23,11 @@ pub struct ListResponse<T> {
     pub count: i64,
 }
 
+/// Deserialize the `object` field of a list, failing if it is not "list".
+fn deserialize_list_object<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    expect_object(deserializer, "list")
+}
+
 impl<T> Default for ListResponse<T> {
     fn default() -> Self {
         Self {
@@ -1,5 +1,7 @@
 //! Response types for PAY.JP API.
 
+pub mod deleted;
 pub mod list;
 
+pub use deleted::Deleted;
 pub use list::ListResponse;
@@ -0,0 +1,180 @@
+//! Recurring revenue reporting from active subscriptions.
+//!
+//! [`RevenueReport`] pages through active subscriptions and totals their
+//! monthly recurring revenue (MRR) and annual recurring revenue (ARR), overall,
+//! per plan and per platform tenant. Yearly plans are normalized to one twelfth
+//! of their amount per month.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{PayjpClient, RevenueReport};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let report = RevenueReport::fetch(&client).await?;
+//!
+//! println!(
+//!     "{} subscriptions, MRR ¥{:.0}, ARR ¥{}",
+//!     report.totals.subscriptions,
+//!     report.totals.mrr(),
+//!     report.totals.arr()
+//! );
+//! for (plan, totals) in &report.by_plan {
+//!     println!("{}: MRR ¥{:.0}", plan, totals.mrr());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::PayjpResult;
+use crate::resources::{ListSubscriptionParams, PlanInterval, Subscription, SubscriptionStatus};
+use futures::TryStreamExt;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Recurring revenue totalled for a set of subscriptions.
+///
+/// Amounts of monthly and yearly plans are kept apart so ARR is exact; MRR
+/// divides the yearly amounts by 12.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RevenueTotals {
+    /// Number of subscriptions counted.
+    pub subscriptions: usize,
+
+    /// Sum of the amounts of subscriptions on monthly plans.
+    pub monthly_amount: i64,
+
+    /// Sum of the amounts of subscriptions on yearly plans.
+    pub yearly_amount: i64,
+}
+
+impl RevenueTotals {
+    /// Monthly recurring revenue, with yearly plans spread evenly over 12 months.
+    pub fn mrr(&self) -> f64 {
+        self.monthly_amount as f64 + self.yearly_amount as f64 / 12.0
+    }
+
+    /// Annual recurring revenue, counting monthly plans 12 times.
+    pub fn arr(&self) -> i64 {
+        self.monthly_amount * 12 + self.yearly_amount
+    }
+
+    fn add(&mut self, subscription: &Subscription) {
+        self.subscriptions += 1;
+        match subscription.plan.interval {
+            PlanInterval::Month => self.monthly_amount += subscription.plan.amount,
+            PlanInterval::Year => self.yearly_amount += subscription.plan.amount,
+        }
+    }
+}
+
+/// Recurring revenue of active subscriptions, overall and broken down.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RevenueReport {
+    /// Totals across all counted subscriptions.
+    pub totals: RevenueTotals,
+
+    /// Totals per plan ID.
+    pub by_plan: BTreeMap<String, RevenueTotals>,
+
+    /// Totals per tenant ID (Platform API).
+    ///
+    /// Subscriptions without a tenant are only counted in `totals` and `by_plan`.
+    pub by_tenant: BTreeMap<String, RevenueTotals>,
+}
+
+impl RevenueReport {
+    /// Build the report by listing all active subscriptions.
+    ///
+    /// Subscriptions in trial, paused or canceled are not recurring revenue yet
+    /// (or any more) and are not counted.
+    pub async fn fetch(client: &PayjpClient) -> PayjpResult<Self> {
+        let params = ListSubscriptionParams::new()
+            .max_limit()
+            .status(SubscriptionStatus::Active);
+
+        let mut report = Self::default();
+        let mut stream = client.subscriptions().list_all(params);
+        while let Some(subscription) = stream.try_next().await? {
+            report.add(&subscription);
+        }
+        Ok(report)
+    }
+
+    /// Count one subscription, whatever its status.
+    ///
+    /// Use this to build a report from subscriptions listed or filtered by other
+    /// means than [`fetch`](Self::fetch).
+    pub fn add(&mut self, subscription: &Subscription) {
+        self.totals.add(subscription);
+        self.by_plan
+            .entry(subscription.plan.id.clone())
+            .or_default()
+            .add(subscription);
+        if let Some(tenant) = &subscription.tenant {
+            self.by_tenant.entry(tenant.clone()).or_default().add(subscription);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn subscription(
+        id: &str,
+        plan_id: &str,
+        amount: i64,
+        interval: &str,
+        tenant: Option<&str>,
+    ) -> serde_json::Value {
+        let plan = serde_json::json!({ "amount": amount, "interval": interval });
+        let plan = fixtures::with(fixtures::plan(plan_id), plan);
+        let fields = serde_json::json!({ "plan": plan, "tenant": tenant });
+        fixtures::with(fixtures::subscription(id, "active"), fields)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_totals_active_subscriptions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/subscriptions"))
+            .and(query_param("status", "active"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [
+                    subscription("sub_1", "pln_monthly", 1000, "month", Some("ten_a")),
+                    subscription("sub_2", "pln_monthly", 1000, "month", Some("ten_b")),
+                    subscription("sub_3", "pln_yearly", 12000, "year", Some("ten_a")),
+                    subscription("sub_4", "pln_yearly", 12000, "year", None),
+                ],
+                "has_more": false,
+                "url": "/v1/subscriptions",
+                "count": 4
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let report = RevenueReport::fetch(&client).await.unwrap();
+        assert_eq!(report.totals.subscriptions, 4);
+        assert_eq!(report.totals.mrr(), 4000.0);
+        assert_eq!(report.totals.arr(), 48000);
+
+        assert_eq!(report.by_plan["pln_monthly"].mrr(), 2000.0);
+        assert_eq!(report.by_plan["pln_yearly"].arr(), 24000);
+
+        assert_eq!(report.by_tenant.len(), 2);
+        assert_eq!(report.by_tenant["ten_a"].mrr(), 2000.0);
+        assert_eq!(report.by_tenant["ten_b"].subscriptions, 1);
+    }
+}
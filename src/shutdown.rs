@@ -0,0 +1,301 @@
+//! Graceful shutdown of a client and its in-flight requests.
+//!
+//! [`PayjpClient::shutdown`] stops the client (and all of its clones) from
+//! sending new requests, waits for the requests already running to finish, and
+//! reports any that had to be abandoned when the timeout ran out. Abandoned
+//! requests may or may not have been processed by PAY.JP, so payment operations
+//! among them should be reconciled, for example by looking up the charge.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::PayjpClient;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! // On SIGTERM during a rolling deploy
+//! let report = client.shutdown(Duration::from_secs(10)).await;
+//! for operation in &report.abandoned {
+//!     eprintln!(
+//!         "abandoned {} {} (request {}) after {:?}",
+//!         operation.method, operation.path, operation.request_id, operation.elapsed
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// An API request that was still running when shutdown timed out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AbandonedOperation {
+    /// HTTP method (e.g., "POST").
+    pub method: String,
+
+    /// Request path relative to the base URL (e.g., "/charges").
+    pub path: String,
+
+    /// Client-generated request ID sent in the `X-Request-Id` header.
+    pub request_id: String,
+
+    /// How long the request had been running when it was abandoned.
+    pub elapsed: Duration,
+}
+
+/// Outcome of [`PayjpClient::shutdown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ShutdownReport {
+    /// Requests that did not finish before the timeout, oldest first.
+    pub abandoned: Vec<AbandonedOperation>,
+}
+
+impl ShutdownReport {
+    /// Whether every in-flight request finished before the timeout.
+    pub fn is_clean(&self) -> bool {
+        self.abandoned.is_empty()
+    }
+}
+
+/// Shutdown progress, shared by all clones of a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Phase {
+    Running,
+    /// No new requests are accepted and retries are not attempted.
+    Draining,
+    /// Remaining in-flight requests are cancelled.
+    Aborted,
+}
+
+#[derive(Debug)]
+struct Operation {
+    method: String,
+    path: String,
+    request_id: String,
+    started: Instant,
+}
+
+/// Tracks in-flight requests so they can be drained on shutdown.
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    phase: watch::Sender<Phase>,
+    in_flight: Mutex<HashMap<u64, Operation>>,
+    /// Number of in-flight requests, watched while draining.
+    count: watch::Sender<usize>,
+    next_id: AtomicU64,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            phase: watch::Sender::new(Phase::Running),
+            in_flight: Mutex::new(HashMap::new()),
+            count: watch::Sender::new(0),
+            next_id: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Register a request, or fail if the client is shutting down.
+    pub(crate) fn begin(
+        &self,
+        method: &str,
+        path: &str,
+        request_id: &str,
+    ) -> PayjpResult<Guard<'_>> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if *self.phase.borrow() != Phase::Running {
+            return Err(PayjpError::Shutdown);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        in_flight.insert(
+            id,
+            Operation {
+                method: method.to_string(),
+                path: path.to_string(),
+                request_id: request_id.to_string(),
+                started: Instant::now(),
+            },
+        );
+        self.count.send_replace(in_flight.len());
+        Ok(Guard {
+            lifecycle: self,
+            id,
+        })
+    }
+
+    /// Resolve once shutdown has reached `phase`.
+    async fn reached(&self, phase: Phase) {
+        let mut rx = self.phase.subscribe();
+        // The sender lives as long as `self`, so this cannot fail
+        let _ = rx.wait_for(|current| *current >= phase).await;
+    }
+
+    /// Resolve once shutdown has started.
+    pub(crate) async fn draining(&self) {
+        self.reached(Phase::Draining).await
+    }
+
+    async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.phase.send_if_modified(|phase| {
+            let start = *phase == Phase::Running;
+            if start {
+                *phase = Phase::Draining;
+            }
+            start
+        });
+
+        let mut count = self.count.subscribe();
+        if tokio::time::timeout(timeout, count.wait_for(|n| *n == 0)).await.is_ok() {
+            return ShutdownReport::default();
+        }
+
+        let mut abandoned: Vec<_> = {
+            let in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            self.phase.send_replace(Phase::Aborted);
+            in_flight
+                .values()
+                .map(|operation| {
+                    (
+                        operation.started,
+                        AbandonedOperation {
+                            method: operation.method.clone(),
+                            path: operation.path.clone(),
+                            request_id: operation.request_id.clone(),
+                            elapsed: operation.started.elapsed(),
+                        },
+                    )
+                })
+                .collect()
+        };
+        abandoned.sort_by_key(|(started, _)| *started);
+        ShutdownReport {
+            abandoned: abandoned.into_iter().map(|(_, operation)| operation).collect(),
+        }
+    }
+}
+
+/// Registration of one in-flight request, removed when dropped.
+pub(crate) struct Guard<'a> {
+    lifecycle: &'a Lifecycle,
+    id: u64,
+}
+
+impl Guard<'_> {
+    /// Run the request, cancelling it with [`PayjpError::Shutdown`] if shutdown
+    /// times out first.
+    pub(crate) async fn run<T>(
+        self,
+        request: impl Future<Output = PayjpResult<T>>,
+    ) -> PayjpResult<T> {
+        tokio::select! {
+            result = request => result,
+            _ = self.lifecycle.reached(Phase::Aborted) => Err(PayjpError::Shutdown),
+        }
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.lifecycle.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.remove(&self.id);
+        self.lifecycle.count.send_replace(in_flight.len());
+    }
+}
+
+impl PayjpClient {
+    /// Stop sending requests and wait up to `timeout` for in-flight ones to finish.
+    ///
+    /// Applies to this client and all of its clones. Once called, new requests
+    /// fail with [`PayjpError::Shutdown`], and requests waiting to retry after a
+    /// rate limit return [`PayjpError::RateLimit`] instead of retrying. Requests still running
+    /// when the timeout expires are cancelled with [`PayjpError::Shutdown`] and
+    /// listed in the report. See the [`shutdown`](crate::shutdown) module for an
+    /// example.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.lifecycle().shutdown(timeout).await
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called on this client or a clone.
+    pub fn is_shut_down(&self) -> bool {
+        *self.lifecycle().phase.borrow() != Phase::Running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::charge;
+    use crate::{ClientOptions, PayjpClient, PayjpError};
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_shutdown_drains_then_rejects_new_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges/ch_1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(charge("ch_1"))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let charges = client.charges();
+        let (charge, report) = tokio::join!(charges.retrieve("ch_1"), async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            client.shutdown(Duration::from_secs(5)).await
+        });
+        assert_eq!(charge.unwrap().id, "ch_1");
+        assert!(report.is_clean());
+
+        assert!(client.is_shut_down());
+        let result = client.clone().charges().retrieve("ch_1").await;
+        assert!(matches!(result, Err(PayjpError::Shutdown)));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_timeout_abandons_slow_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/charges/ch_slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(charge("ch_slow"))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let charges = client.charges();
+        let (result, report) = tokio::join!(charges.retrieve("ch_slow"), async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            client.shutdown(Duration::from_millis(100)).await
+        });
+        assert!(matches!(result, Err(PayjpError::Shutdown)));
+        assert_eq!(report.abandoned.len(), 1);
+        assert_eq!(report.abandoned[0].method, "GET");
+        assert_eq!(report.abandoned[0].path, "/charges/ch_slow");
+    }
+}
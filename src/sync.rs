@@ -0,0 +1,577 @@
+//! Differential sync of charges, customers and subscriptions into a local store.
+//!
+//! [`ResourceSync`] keeps a local read model up to date through a
+//! [`ResourceStore`] implemented over your database. The first run backfills
+//! every resource with `since` list queries. Later runs read the events feed
+//! from the saved [`SyncCheckpoint`], retrieve each changed resource once and
+//! apply deletions.
+//!
+//! The checkpoint is only saved after the store has accepted every change
+//! before it, so an interrupted run is repeated rather than lost. Store writes
+//! should therefore be upserts.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//! use payjp::sync::{ResourceKind, ResourceStore, ResourceSync, SyncCheckpoint, SyncedResource};
+//! use payjp::{PayjpClient, PayjpResult};
+//! use std::time::Duration;
+//!
+//! struct Database;
+//!
+//! #[async_trait]
+//! impl ResourceStore for Database {
+//!     async fn upsert(&self, resource: SyncedResource) -> PayjpResult<()> {
+//!         println!("upsert {:?} {}", resource.kind(), resource.id());
+//!         Ok(())
+//!     }
+//!
+//!     async fn delete(&self, kind: ResourceKind, id: &str) -> PayjpResult<()> {
+//!         println!("delete {:?} {}", kind, id);
+//!         Ok(())
+//!     }
+//!
+//!     async fn load_checkpoint(&self) -> PayjpResult<Option<SyncCheckpoint>> {
+//!         Ok(None)
+//!     }
+//!
+//!     async fn save_checkpoint(&self, checkpoint: &SyncCheckpoint) -> PayjpResult<()> {
+//!         println!("synced up to {}", checkpoint.events_since);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! ResourceSync::new(&client, Database)
+//!     .concurrency(8)
+//!     .run(Duration::from_secs(60))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::endpoint::{Listable, Retrievable};
+use crate::error::{PayjpError, PayjpResult};
+use crate::params::ListParams;
+use crate::resources::{Charge, Customer, Event, EventType, ListEventParams};
+#[cfg(feature = "subscriptions")]
+use crate::resources::Subscription;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Default number of concurrent retrieves when applying events.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Kind of a synced resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ResourceKind {
+    /// A charge.
+    Charge,
+
+    /// A customer, including its cards.
+    Customer,
+
+    /// A subscription.
+    #[cfg(feature = "subscriptions")]
+    Subscription,
+}
+
+impl ResourceKind {
+    /// Kind and ID of the synced resource changed by `event`.
+    ///
+    /// Card events change the customer owning the card.
+    fn of_event(event: &Event) -> Option<(Self, String)> {
+        let id = event.resource_id()?;
+        let kind = match event.resource_object()? {
+            "charge" => Self::Charge,
+            "customer" => Self::Customer,
+            "card" => {
+                let customer = event.data.object.get("customer")?.as_str()?;
+                return Some((Self::Customer, customer.to_string()));
+            }
+            #[cfg(feature = "subscriptions")]
+            "subscription" => Self::Subscription,
+            _ => return None,
+        };
+        Some((kind, id.to_string()))
+    }
+}
+
+/// A resource to insert into the store, or to replace the stored copy of.
+#[derive(Debug, Clone)]
+pub enum SyncedResource {
+    /// A charge.
+    Charge(Box<Charge>),
+
+    /// A customer.
+    Customer(Box<Customer>),
+
+    /// A subscription.
+    #[cfg(feature = "subscriptions")]
+    Subscription(Box<Subscription>),
+}
+
+impl SyncedResource {
+    /// Kind of the resource.
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            SyncedResource::Charge(_) => ResourceKind::Charge,
+            SyncedResource::Customer(_) => ResourceKind::Customer,
+            #[cfg(feature = "subscriptions")]
+            SyncedResource::Subscription(_) => ResourceKind::Subscription,
+        }
+    }
+
+    /// ID of the resource.
+    pub fn id(&self) -> &str {
+        match self {
+            SyncedResource::Charge(charge) => &charge.id,
+            SyncedResource::Customer(customer) => &customer.id,
+            #[cfg(feature = "subscriptions")]
+            SyncedResource::Subscription(subscription) => &subscription.id,
+        }
+    }
+}
+
+impl From<Charge> for SyncedResource {
+    fn from(charge: Charge) -> Self {
+        SyncedResource::Charge(Box::new(charge))
+    }
+}
+
+impl From<Customer> for SyncedResource {
+    fn from(customer: Customer) -> Self {
+        SyncedResource::Customer(Box::new(customer))
+    }
+}
+
+#[cfg(feature = "subscriptions")]
+impl From<Subscription> for SyncedResource {
+    fn from(subscription: Subscription) -> Self {
+        SyncedResource::Subscription(Box::new(subscription))
+    }
+}
+
+/// Position in the events feed up to which the store is in sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    /// Creation time of the newest applied event (Unix timestamp).
+    pub events_since: i64,
+
+    /// IDs of the applied events created at `events_since`, skipped when the
+    /// feed is read again from that time.
+    pub seen_events: Vec<String>,
+}
+
+/// Local storage kept in sync by [`ResourceSync`].
+#[async_trait]
+pub trait ResourceStore: Send + Sync {
+    /// Insert `resource`, or replace the stored resource of the same kind and ID.
+    async fn upsert(&self, resource: SyncedResource) -> PayjpResult<()>;
+
+    /// Remove the resource of `kind` with `id`, if it is stored.
+    async fn delete(&self, kind: ResourceKind, id: &str) -> PayjpResult<()>;
+
+    /// Load the checkpoint saved by the last run, or `None` before the first run.
+    async fn load_checkpoint(&self) -> PayjpResult<Option<SyncCheckpoint>>;
+
+    /// Persist `checkpoint`. Called once every change before it has been stored.
+    async fn save_checkpoint(&self, checkpoint: &SyncCheckpoint) -> PayjpResult<()>;
+}
+
+/// Changes applied by one [`ResourceSync::sync_once`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    /// Whether the run was a full backfill rather than an events update.
+    pub backfilled: bool,
+
+    /// Number of new events read.
+    pub events: usize,
+
+    /// Number of resources upserted.
+    pub upserted: usize,
+
+    /// Number of resources deleted.
+    pub deleted: usize,
+}
+
+/// Mirrors charges, customers and subscriptions into a [`ResourceStore`].
+pub struct ResourceSync<'a, S> {
+    client: &'a PayjpClient,
+    store: S,
+    concurrency: usize,
+    backfill_since: Option<i64>,
+}
+
+impl<'a, S: ResourceStore> ResourceSync<'a, S> {
+    /// Create a sync into `store`.
+    pub fn new(client: &'a PayjpClient, store: S) -> Self {
+        Self {
+            client,
+            store,
+            concurrency: DEFAULT_CONCURRENCY,
+            backfill_since: None,
+        }
+    }
+
+    /// Set how many resources are retrieved concurrently when applying events
+    /// (default: 4).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Only backfill resources created since `timestamp` (Unix timestamp).
+    ///
+    /// Older resources still enter the store once an event changes them.
+    pub fn backfill_since(mut self, timestamp: i64) -> Self {
+        self.backfill_since = Some(timestamp);
+        self
+    }
+
+    /// Get the store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Backfill the store if it has no checkpoint yet, or apply the events
+    /// created since the checkpoint.
+    pub async fn sync_once(&self) -> PayjpResult<SyncReport> {
+        match self.store.load_checkpoint().await? {
+            Some(checkpoint) => self.apply_events(checkpoint).await,
+            None => self.backfill().await,
+        }
+    }
+
+    /// Sync every `interval` until an error occurs.
+    pub async fn run(&self, interval: Duration) -> PayjpResult<()> {
+        loop {
+            self.sync_once().await?;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Upsert every resource, then checkpoint at the time the backfill started
+    /// so changes made during it are picked up from the events feed.
+    async fn backfill(&self) -> PayjpResult<SyncReport> {
+        let started = Utc::now().timestamp();
+        let mut params = ListParams::new().max_limit();
+        if let Some(since) = self.backfill_since {
+            params = params.since(since);
+        }
+
+        let mut report = SyncReport {
+            backfilled: true,
+            ..SyncReport::default()
+        };
+        report.upserted += self.backfill_all::<Charge>(params.clone()).await?;
+        report.upserted += self.backfill_all::<Customer>(params.clone()).await?;
+        #[cfg(feature = "subscriptions")]
+        {
+            report.upserted += self.backfill_all::<Subscription>(params).await?;
+        }
+
+        let checkpoint = SyncCheckpoint {
+            events_since: started,
+            seen_events: Vec::new(),
+        };
+        self.store.save_checkpoint(&checkpoint).await?;
+        Ok(report)
+    }
+
+    async fn backfill_all<T>(&self, params: ListParams) -> PayjpResult<usize>
+    where
+        T: Listable,
+        SyncedResource: From<T>,
+    {
+        let mut stream = self.client.list_all::<T>(params);
+        let mut upserted = 0;
+        while let Some(resource) = stream.try_next().await? {
+            self.store.upsert(resource.into()).await?;
+            upserted += 1;
+        }
+        Ok(upserted)
+    }
+
+    /// Apply the events created since `checkpoint` and advance it.
+    async fn apply_events(&self, checkpoint: SyncCheckpoint) -> PayjpResult<SyncReport> {
+        let params = ListEventParams::new()
+            .max_limit()
+            .since(checkpoint.events_since);
+        let mut events: Vec<Event> = self.client.events().list_all(params).try_collect().await?;
+        events.retain(|event| !checkpoint.seen_events.contains(&event.id));
+        // The feed lists newest first; apply oldest first, keeping the feed's
+        // order among events created in the same second.
+        events.reverse();
+        events.sort_by_key(|event| event.created);
+        let Some(last) = events.last().map(|event| event.created) else {
+            return Ok(SyncReport::default());
+        };
+
+        // The last change to a resource wins: a deletion is applied as is, any
+        // other change retrieves the resource's current state.
+        let mut changes = BTreeMap::new();
+        for event in &events {
+            if let Some(key) = ResourceKind::of_event(event) {
+                let deleted = matches!(
+                    event.event_type,
+                    EventType::CustomerDeleted | EventType::SubscriptionDeleted
+                );
+                changes.insert(key, deleted);
+            }
+        }
+
+        let mut report = SyncReport {
+            events: events.len(),
+            ..SyncReport::default()
+        };
+        let mut changed: BTreeMap<ResourceKind, Vec<String>> = BTreeMap::new();
+        for ((kind, id), deleted) in changes {
+            if deleted {
+                self.store.delete(kind, &id).await?;
+                report.deleted += 1;
+            } else {
+                changed.entry(kind).or_default().push(id);
+            }
+        }
+        for (kind, ids) in changed {
+            match kind {
+                ResourceKind::Charge => self.refresh::<Charge>(kind, &ids, &mut report).await?,
+                ResourceKind::Customer => self.refresh::<Customer>(kind, &ids, &mut report).await?,
+                #[cfg(feature = "subscriptions")]
+                ResourceKind::Subscription => {
+                    self.refresh::<Subscription>(kind, &ids, &mut report).await?
+                }
+            }
+        }
+
+        let mut seen_events = if last == checkpoint.events_since {
+            checkpoint.seen_events
+        } else {
+            Vec::new()
+        };
+        seen_events.extend(
+            events
+                .into_iter()
+                .filter(|event| event.created == last)
+                .map(|event| event.id),
+        );
+        let checkpoint = SyncCheckpoint {
+            events_since: last,
+            seen_events,
+        };
+        self.store.save_checkpoint(&checkpoint).await?;
+        Ok(report)
+    }
+
+    /// Retrieve the current state of changed resources and store it, deleting
+    /// resources that no longer exist.
+    async fn refresh<T>(
+        &self,
+        kind: ResourceKind,
+        ids: &[String],
+        report: &mut SyncReport,
+    ) -> PayjpResult<()>
+    where
+        T: Retrievable,
+        SyncedResource: From<T>,
+    {
+        for (id, result) in self.client.retrieve_many::<T, _>(ids, self.concurrency).await {
+            match result {
+                Ok(resource) => {
+                    self.store.upsert(resource.into()).await?;
+                    report.upserted += 1;
+                }
+                Err(PayjpError::Api(err)) if err.status == 404 => {
+                    self.store.delete(kind, &id).await?;
+                    report.deleted += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::charge;
+    use crate::ClientOptions;
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Default)]
+    struct MemoryStore {
+        resources: Mutex<BTreeMap<(ResourceKind, String), SyncedResource>>,
+        checkpoint: Mutex<Option<SyncCheckpoint>>,
+    }
+
+    #[async_trait]
+    impl ResourceStore for MemoryStore {
+        async fn upsert(&self, resource: SyncedResource) -> PayjpResult<()> {
+            let key = (resource.kind(), resource.id().to_string());
+            self.resources.lock().unwrap().insert(key, resource);
+            Ok(())
+        }
+
+        async fn delete(&self, kind: ResourceKind, id: &str) -> PayjpResult<()> {
+            self.resources.lock().unwrap().remove(&(kind, id.to_string()));
+            Ok(())
+        }
+
+        async fn load_checkpoint(&self) -> PayjpResult<Option<SyncCheckpoint>> {
+            Ok(self.checkpoint.lock().unwrap().clone())
+        }
+
+        async fn save_checkpoint(&self, checkpoint: &SyncCheckpoint) -> PayjpResult<()> {
+            *self.checkpoint.lock().unwrap() = Some(checkpoint.clone());
+            Ok(())
+        }
+    }
+
+    impl MemoryStore {
+        fn ids(&self) -> Vec<String> {
+            let resources = self.resources.lock().unwrap();
+            resources.keys().map(|(_, id)| id.clone()).collect()
+        }
+    }
+
+    fn list(data: Vec<serde_json::Value>) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": false,
+            "url": "/v1/list",
+            "count": data.len()
+        }))
+    }
+
+    fn customer(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "customer",
+            "livemode": false,
+            "created": 1700000000
+        })
+    }
+
+    fn event(
+        id: &str,
+        created: i64,
+        event_type: &str,
+        object: serde_json::Value,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "event",
+            "livemode": false,
+            "created": created,
+            "type": event_type,
+            "data": { "object": object }
+        })
+    }
+
+    async fn mount_list(server: &MockServer, list_path: &str, data: Vec<serde_json::Value>) {
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .respond_with(list(data))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_first_run_backfills_and_checkpoints() {
+        let server = MockServer::start().await;
+        mount_list(&server, "/charges", vec![charge("ch_1")]).await;
+        mount_list(&server, "/customers", vec![customer("cus_1")]).await;
+        mount_list(&server, "/subscriptions", vec![]).await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let before = Utc::now().timestamp();
+        let sync = ResourceSync::new(&client, MemoryStore::default());
+        let report = sync.sync_once().await.unwrap();
+        assert!(report.backfilled);
+        assert_eq!(report.upserted, 2);
+        assert_eq!(sync.store().ids(), ["ch_1", "cus_1"]);
+
+        let checkpoint = sync.store().checkpoint.lock().unwrap().clone().unwrap();
+        assert!(checkpoint.events_since >= before);
+        assert!(checkpoint.seen_events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_events_refresh_changed_resources_and_apply_deletions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("since", "1700000100"))
+            .respond_with(list(vec![
+                event("evnt_3", 1700000300, "customer.deleted", customer("cus_2")),
+                event(
+                    "evnt_2",
+                    1700000300,
+                    "customer.card.created",
+                    serde_json::json!({ "id": "car_1", "object": "card", "customer": "cus_1" }),
+                ),
+                event("evnt_4", 1700000200, "charge.updated", charge("ch_1")),
+                event("evnt_5", 1700000200, "charge.refunded", charge("ch_1")),
+                event("evnt_1", 1700000100, "charge.updated", charge("ch_old")),
+            ]))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges/ch_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(charge("ch_1")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(customer("cus_1")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let store = MemoryStore::default();
+        let deleted: Customer = serde_json::from_value(customer("cus_2")).unwrap();
+        store.upsert(deleted.into()).await.unwrap();
+        *store.checkpoint.lock().unwrap() = Some(SyncCheckpoint {
+            events_since: 1700000100,
+            seen_events: vec!["evnt_1".to_string()],
+        });
+
+        let sync = ResourceSync::new(&client, store);
+        let report = sync.sync_once().await.unwrap();
+        assert_eq!(
+            report,
+            SyncReport {
+                backfilled: false,
+                events: 4,
+                upserted: 2,
+                deleted: 1,
+            }
+        );
+        assert_eq!(sync.store().ids(), ["ch_1", "cus_1"]);
+
+        let checkpoint = sync.store().checkpoint.lock().unwrap().clone().unwrap();
+        assert_eq!(checkpoint.events_since, 1700000300);
+        assert_eq!(checkpoint.seen_events, ["evnt_2", "evnt_3"]);
+    }
+}
@@ -0,0 +1,237 @@
+//! 3D Secure redirect helpers.
+//!
+//! A redirect-based 3D Secure integration sends the customer to the request's
+//! authentication URL, identified by the account's public key and the name of a
+//! redirect destination registered in the dashboard (`publickey` and `back`).
+//! PAY.JP returns the customer to that destination without any value of ours, so
+//! the `state` generated here never leaves the application: keep it in the
+//! customer's session when redirecting and read it back in the callback handler.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::tds::PendingThreeDSecure;
+//! use payjp::{ChargeThreeDSecureParams, CreateThreeDSecureRequestParams, PayjpClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let pending = PendingThreeDSecure::new();
+//!
+//! // Start authentication, store the state in the session and redirect the customer
+//! let request = client
+//!     .three_d_secure_requests()
+//!     .create(CreateThreeDSecureRequestParams::new("car_xxxxx"))
+//!     .await?;
+//! let params = ChargeThreeDSecureParams::new("pk_test_xxxxx").back("shop");
+//! let redirect = pending.begin(&request, &params)?;
+//! println!("store {} and redirect to {}", redirect.state, redirect.url);
+//!
+//! // In the callback handler, with `state` taken from the session
+//! # let state = "";
+//! let request = pending.complete(&client, state).await?;
+//! println!("3DS status: {:?}", request.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::resources::{ChargeThreeDSecureParams, ThreeDSecureRequest};
+use base64::{engine::general_purpose, Engine as _};
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use url::Url;
+
+/// Values of [`ThreeDSecureRequest::state`] once the authentication result has
+/// been received.
+const COMPLETED_STATES: [&str; 2] = ["result_received", "finished"];
+
+/// Generate a cryptographically random, URL-safe `state` value (256 bits).
+pub fn generate_state() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill(&mut bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Compare a received `state` against the expected value in constant time.
+pub fn verify_state(expected: &str, received: &str) -> bool {
+    let (expected, received) = (expected.as_bytes(), received.as_bytes());
+    if expected.len() != received.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(received)
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+/// Build the URL to redirect the customer to for authentication.
+///
+/// `params` are added to the request's authentication URL, as for
+/// [`ChargeService::three_d_secure_url`](crate::ChargeService::three_d_secure_url).
+pub fn redirect_url(
+    request: &ThreeDSecureRequest,
+    params: &ChargeThreeDSecureParams,
+) -> PayjpResult<Url> {
+    let authentication_url = request.authentication_url.as_deref().ok_or_else(|| {
+        PayjpError::InvalidRequest(format!(
+            "3D Secure request {} has no authentication URL",
+            request.id
+        ))
+    })?;
+
+    let mut url = Url::parse(authentication_url)?;
+    let query = serde_urlencoded::to_string(params)
+        .map_err(|e| PayjpError::InvalidRequest(format!("Failed to encode query: {}", e)))?;
+    url.set_query(Some(&query));
+    Ok(url)
+}
+
+/// A redirect started by [`PendingThreeDSecure::begin`].
+#[derive(Debug, Clone)]
+pub struct ThreeDSecureRedirect {
+    /// State to keep in the customer's session until the callback.
+    pub state: String,
+
+    /// URL to redirect the customer to.
+    pub url: Url,
+}
+
+/// In-memory registry of 3D Secure requests awaiting their callback, keyed by `state`.
+///
+/// Each `state` can be completed once. For multi-process deployments, store the
+/// `state` and request ID in your session instead and use [`verify_state`].
+#[derive(Debug, Default)]
+pub struct PendingThreeDSecure {
+    pending: Mutex<HashMap<String, String>>,
+}
+
+impl PendingThreeDSecure {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a request under a fresh `state` and build its redirect URL.
+    pub fn begin(
+        &self,
+        request: &ThreeDSecureRequest,
+        params: &ChargeThreeDSecureParams,
+    ) -> PayjpResult<ThreeDSecureRedirect> {
+        let state = generate_state();
+        let url = redirect_url(request, params)?;
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(state.clone(), request.id.clone());
+        Ok(ThreeDSecureRedirect { state, url })
+    }
+
+    /// Remove and return the request ID registered under `state`.
+    pub fn take(&self, state: &str) -> Option<String> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(state)
+    }
+
+    /// Verify a callback's `state` and retrieve the pending 3D Secure request.
+    ///
+    /// Fails with `InvalidRequest` if the `state` is unknown or was already used,
+    /// or if PAY.JP has not received the authentication result yet.
+    pub async fn complete(
+        &self,
+        client: &PayjpClient,
+        state: &str,
+    ) -> PayjpResult<ThreeDSecureRequest> {
+        let request_id = self.take(state).ok_or_else(|| {
+            PayjpError::InvalidRequest("Unknown or already used 3D Secure state".to_string())
+        })?;
+        let request = client.three_d_secure_requests().retrieve(&request_id).await?;
+        match request.state.as_deref() {
+            Some(state) if COMPLETED_STATES.contains(&state) => Ok(request),
+            state => Err(PayjpError::InvalidRequest(format!(
+                "3D Secure request {} has not received a result (state: {})",
+                request.id,
+                state.unwrap_or("unknown")
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn request(state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "tdsr_1",
+            "object": "three_d_secure_request",
+            "livemode": false,
+            "created": 1700000000,
+            "authentication_url": "https://api.pay.jp/v1/tds/tdsr_1/start",
+            "state": state
+        })
+    }
+
+    #[test]
+    fn test_begin_and_take_round_trip() {
+        let request: ThreeDSecureRequest = serde_json::from_value(request("created")).unwrap();
+
+        let pending = PendingThreeDSecure::new();
+        let params = ChargeThreeDSecureParams::new("pk_test_xxxxx").back("shop");
+        let redirect = pending
+            .begin(&request, &params)
+            .expect("Failed to build redirect URL");
+
+        assert_eq!(
+            redirect.url.as_str(),
+            "https://api.pay.jp/v1/tds/tdsr_1/start?publickey=pk_test_xxxxx&back=shop"
+        );
+        assert_eq!(redirect.state.len(), 43);
+        assert!(verify_state(&redirect.state, &redirect.state));
+        assert!(!verify_state(&redirect.state, &generate_state()));
+        assert_eq!(pending.take(&redirect.state).as_deref(), Some("tdsr_1"));
+        assert_eq!(pending.take(&redirect.state), None);
+    }
+
+    #[tokio::test]
+    async fn test_complete_requires_received_result() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/three_d_secure_requests/tdsr_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(request("in_progress")))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/three_d_secure_requests/tdsr_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(request("result_received")))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+        let request: ThreeDSecureRequest = serde_json::from_value(request("created")).unwrap();
+        let pending = PendingThreeDSecure::new();
+        let params = ChargeThreeDSecureParams::new("pk_test_xxxxx");
+
+        let redirect = pending.begin(&request, &params).unwrap();
+        let result = pending.complete(&client, &redirect.state).await;
+        assert!(matches!(result, Err(PayjpError::InvalidRequest(_))));
+
+        let redirect = pending.begin(&request, &params).unwrap();
+        let completed = pending
+            .complete(&client, &redirect.state)
+            .await
+            .expect("Failed to complete 3D Secure");
+        assert_eq!(completed.state.as_deref(), Some("result_received"));
+        assert!(pending.complete(&client, &redirect.state).await.is_err());
+    }
+}
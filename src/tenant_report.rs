@@ -0,0 +1,278 @@
+//! Consolidated balance reporting across platform tenants.
+//!
+//! [`TenantBalanceReport`] lists every tenant together with its open balances and
+//! pending tenant transfers, and totals them per tenant and across the platform.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{PlatformClient, TenantBalanceReport};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let platform = PlatformClient::new("sk_test_xxxxx")?;
+//! let report = TenantBalanceReport::fetch(&platform).await?;
+//!
+//! println!(
+//!     "{} tenants, ¥{} open, ¥{} pending payout",
+//!     report.tenants.len(),
+//!     report.totals.balance,
+//!     report.totals.pending_transfers
+//! );
+//! for tenant in report.below_minimum() {
+//!     println!("{} is below its minimum transfer amount", tenant.tenant.id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::PayjpResult;
+use crate::params::ListParams;
+use crate::platform::PlatformClient;
+use crate::resources::platform::tenant::MIN_TRANSFER_AMOUNT;
+use crate::resources::platform::{Tenant, TenantTransfer};
+use crate::resources::{Balance, TransferStatus};
+use futures::TryStreamExt;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Amounts totalled for one tenant or for the whole platform.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BalanceTotals {
+    /// Sum of the `total` of open (not yet closed) balances.
+    pub balance: i64,
+
+    /// Sum of tenant transfers that have not been paid yet.
+    pub pending_transfers: i64,
+}
+
+impl BalanceTotals {
+    fn add(&mut self, other: BalanceTotals) {
+        self.balance += other.balance;
+        self.pending_transfers += other.pending_transfers;
+    }
+}
+
+/// One tenant's entry in a [`TenantBalanceReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantBalance {
+    /// The tenant.
+    pub tenant: Tenant,
+
+    /// The tenant's open balances.
+    pub balances: Vec<Balance>,
+
+    /// The tenant's pending tenant transfers.
+    pub pending_transfers: Vec<TenantTransfer>,
+
+    /// Totals for this tenant.
+    pub totals: BalanceTotals,
+}
+
+impl TenantBalance {
+    /// The tenant's minimum transfer amount, or [`MIN_TRANSFER_AMOUNT`] if unset.
+    pub fn minimum_transfer_amount(&self) -> i64 {
+        self.tenant
+            .minimum_transfer_amount
+            .unwrap_or(MIN_TRANSFER_AMOUNT)
+    }
+
+    /// Whether the open balance is below the minimum transfer amount, so it will be
+    /// carried forward instead of paid out.
+    pub fn is_below_minimum(&self) -> bool {
+        self.totals.balance < self.minimum_transfer_amount()
+    }
+}
+
+/// Balances and pending payouts for every tenant of a platform.
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantBalanceReport {
+    /// Per-tenant entries, in the order tenants are listed by the API.
+    pub tenants: Vec<TenantBalance>,
+
+    /// Totals across all tenants.
+    pub totals: BalanceTotals,
+}
+
+impl TenantBalanceReport {
+    /// Build the report by listing all tenants, balances and tenant transfers.
+    ///
+    /// Each collection is paged through once, so the number of requests depends
+    /// on the size of the account's history rather than the number of tenants.
+    /// Balances and transfers of tenants that no longer exist are not included.
+    pub async fn fetch(platform: &PlatformClient) -> PayjpResult<Self> {
+        let params = ListParams::new().max_limit();
+
+        let mut balances: HashMap<String, Vec<Balance>> = HashMap::new();
+        let mut stream = platform.client().list_all::<Balance>(params.clone());
+        while let Some(balance) = stream.try_next().await? {
+            if balance.closed_at.is_some() {
+                continue;
+            }
+            if let Some(tenant) = balance.tenant.clone() {
+                balances.entry(tenant).or_default().push(balance);
+            }
+        }
+
+        let mut transfers: HashMap<String, Vec<TenantTransfer>> = HashMap::new();
+        let mut stream = platform.tenant_transfers().list_all(params.clone());
+        while let Some(transfer) = stream.try_next().await? {
+            if transfer.status == TransferStatus::Pending {
+                transfers
+                    .entry(transfer.tenant.clone())
+                    .or_default()
+                    .push(transfer);
+            }
+        }
+
+        let mut report = Self {
+            tenants: Vec::new(),
+            totals: BalanceTotals::default(),
+        };
+        let mut stream = platform.tenants().list_all(params);
+        while let Some(tenant) = stream.try_next().await? {
+            let balances = balances.remove(&tenant.id).unwrap_or_default();
+            let pending_transfers = transfers.remove(&tenant.id).unwrap_or_default();
+            let totals = BalanceTotals {
+                balance: balances.iter().map(|balance| balance.total).sum(),
+                pending_transfers: pending_transfers.iter().map(|t| t.amount).sum(),
+            };
+            report.totals.add(totals);
+            report.tenants.push(TenantBalance {
+                tenant,
+                balances,
+                pending_transfers,
+                totals,
+            });
+        }
+        Ok(report)
+    }
+
+    /// Tenants whose open balance is below their minimum transfer amount.
+    pub fn below_minimum(&self) -> impl Iterator<Item = &TenantBalance> {
+        self.tenants.iter().filter(|tenant| tenant.is_below_minimum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn list(url: &str, data: Vec<serde_json::Value>) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": false,
+            "url": url,
+            "count": data.len()
+        }))
+    }
+
+    fn tenant(id: &str, minimum_transfer_amount: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "tenant",
+            "livemode": false,
+            "created": 1704067200,
+            "minimum_transfer_amount": minimum_transfer_amount
+        })
+    }
+
+    fn balance(id: &str, tenant: &str, total: i64, closed_at: Option<i64>) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "balance",
+            "livemode": false,
+            "created": 1704067200,
+            "total": total,
+            "available": total,
+            "pending": 0,
+            "tenant": tenant,
+            "closed_at": closed_at
+        })
+    }
+
+    fn transfer(id: &str, tenant: &str, amount: i64, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "tenant_transfer",
+            "livemode": false,
+            "created": 1704067200,
+            "tenant": tenant,
+            "amount": amount,
+            "currency": "jpy",
+            "status": status,
+            "summary": {
+                "charge_amount": amount,
+                "charge_count": 1,
+                "charge_fee": 0,
+                "platform_fee": 0,
+                "refund_amount": 0,
+                "refund_count": 0
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_report_totals_per_tenant_and_platform() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tenants"))
+            .respond_with(list(
+                "/v1/tenants",
+                vec![tenant("ten_a", 1000), tenant("ten_b", 5000)],
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/balances"))
+            .respond_with(list(
+                "/v1/balances",
+                vec![
+                    balance("ba_1", "ten_a", 3000, None),
+                    balance("ba_2", "ten_a", 9000, Some(1704067200)),
+                    balance("ba_3", "ten_b", 2000, None),
+                    balance("ba_4", "ten_deleted", 7000, None),
+                ],
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tenant_transfers"))
+            .respond_with(list(
+                "/v1/tenant_transfers",
+                vec![
+                    transfer("ttr_1", "ten_a", 8000, "pending"),
+                    transfer("ttr_2", "ten_a", 4000, "paid"),
+                ],
+            ))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let platform = PlatformClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let report = TenantBalanceReport::fetch(&platform).await.unwrap();
+        assert_eq!(report.tenants.len(), 2);
+        assert_eq!(
+            report.tenants[0].totals,
+            BalanceTotals {
+                balance: 3000,
+                pending_transfers: 8000
+            }
+        );
+        assert_eq!(
+            report.totals,
+            BalanceTotals {
+                balance: 5000,
+                pending_transfers: 8000
+            }
+        );
+
+        let below: Vec<_> = report.below_minimum().map(|t| t.tenant.id.as_str()).collect();
+        assert_eq!(below, ["ten_b"]);
+    }
+}
@@ -0,0 +1,294 @@
+//! Subscription renewal monitoring built on top of events.
+//!
+//! [`RenewalWatcher`] polls the events API for `subscription.renewed` and
+//! `charge.failed` events, resolves the subscription, customer and charge involved,
+//! and hands them to user callbacks as a [`RenewalContext`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::{PayjpClient, RenewalWatcher};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//!
+//! RenewalWatcher::new(&client)
+//!     .on_renewed(|ctx| async move {
+//!         println!("{} renewed for {}", ctx.subscription.id, ctx.customer.id);
+//!     })
+//!     .on_payment_failed(|ctx| async move {
+//!         println!("renewal of {} failed", ctx.subscription.id);
+//!     })
+//!     .run(Duration::from_secs(60))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::PayjpResult;
+use crate::resources::{
+    Charge, Customer, Event, EventType, ListChargeParams, ListEventParams, Subscription,
+};
+use futures::future::BoxFuture;
+use futures::{FutureExt, TryStreamExt};
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+
+/// Resources involved in a subscription renewal, passed to [`RenewalWatcher`] callbacks.
+#[derive(Debug, Clone)]
+pub struct RenewalContext {
+    /// The event that triggered the callback.
+    pub event: Event,
+
+    /// The renewed subscription.
+    pub subscription: Subscription,
+
+    /// The customer owning the subscription.
+    pub customer: Customer,
+
+    /// The renewal charge.
+    ///
+    /// For `subscription.renewed` this is the subscription's most recent charge, and
+    /// may be `None` when the renewal did not create a charge (e.g., a free trial).
+    pub charge: Option<Charge>,
+}
+
+type Handler<'a> = Box<dyn FnMut(RenewalContext) -> BoxFuture<'a, ()> + Send + 'a>;
+
+/// Watches events for subscription renewals and failed renewal charges.
+///
+/// Events are processed oldest first. The watcher only advances past an event once
+/// its callback has completed, so an error while resolving resources causes the
+/// event to be retried on the next poll.
+pub struct RenewalWatcher<'a> {
+    client: &'a PayjpClient,
+    since: i64,
+    seen: HashSet<String>,
+    on_renewed: Option<Handler<'a>>,
+    on_payment_failed: Option<Handler<'a>>,
+}
+
+impl<'a> RenewalWatcher<'a> {
+    /// Create a watcher that reports events created from now on.
+    pub fn new(client: &'a PayjpClient) -> Self {
+        Self {
+            client,
+            since: chrono::Utc::now().timestamp(),
+            seen: HashSet::new(),
+            on_renewed: None,
+            on_payment_failed: None,
+        }
+    }
+
+    /// Report events created at or after this timestamp (Unix timestamp).
+    pub fn since(mut self, since: i64) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Set the callback invoked for each `subscription.renewed` event.
+    pub fn on_renewed<F, Fut>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(RenewalContext) -> Fut + Send + 'a,
+        Fut: Future<Output = ()> + Send + 'a,
+    {
+        self.on_renewed = Some(Box::new(move |ctx| f(ctx).boxed()));
+        self
+    }
+
+    /// Set the callback invoked for each `charge.failed` event on a subscription charge.
+    pub fn on_payment_failed<F, Fut>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(RenewalContext) -> Fut + Send + 'a,
+        Fut: Future<Output = ()> + Send + 'a,
+    {
+        self.on_payment_failed = Some(Box::new(move |ctx| f(ctx).boxed()));
+        self
+    }
+
+    /// Fetch new events once and invoke the callbacks.
+    ///
+    /// Returns the number of callbacks invoked.
+    pub async fn poll_once(&mut self) -> PayjpResult<usize> {
+        let mut events: Vec<Event> = self
+            .client
+            .events()
+            .list_all(ListEventParams::new().max_limit().since(self.since))
+            .filter_local(|event| {
+                matches!(
+                    event.event_type,
+                    EventType::SubscriptionRenewed | EventType::ChargeFailed
+                )
+            })
+            .try_collect()
+            .await?;
+        events.retain(|event| !self.seen.contains(&event.id));
+        events.sort_by_key(|event| event.created);
+
+        let mut invoked = 0;
+        for event in events {
+            if self.dispatch(&event).await? {
+                invoked += 1;
+            }
+            self.advance(&event);
+        }
+        Ok(invoked)
+    }
+
+    /// Poll for new events every `interval` until an error occurs.
+    pub async fn run(mut self, interval: Duration) -> PayjpResult<()> {
+        loop {
+            self.poll_once().await?;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Move the cursor past `event`, remembering IDs that share the cursor timestamp.
+    fn advance(&mut self, event: &Event) {
+        if event.created > self.since {
+            self.since = event.created;
+            self.seen.clear();
+        }
+        self.seen.insert(event.id.clone());
+    }
+
+    /// Resolve the context for `event` and invoke the matching callback.
+    async fn dispatch(&mut self, event: &Event) -> PayjpResult<bool> {
+        match event.event_type {
+            EventType::SubscriptionRenewed if self.on_renewed.is_some() => {
+                let subscription: Subscription = serde_json::from_value(event.data.object.clone())?;
+                let customer = self
+                    .client
+                    .customers()
+                    .retrieve(&subscription.customer)
+                    .await?;
+                let charge = self
+                    .client
+                    .charges()
+                    .list(
+                        ListChargeParams::new()
+                            .limit(1)
+                            .subscription(&subscription.id),
+                    )
+                    .await?
+                    .data
+                    .into_iter()
+                    .next();
+                let ctx = RenewalContext {
+                    event: event.clone(),
+                    subscription,
+                    customer,
+                    charge,
+                };
+                if let Some(handler) = self.on_renewed.as_mut() {
+                    handler(ctx).await;
+                }
+                Ok(true)
+            }
+            EventType::ChargeFailed if self.on_payment_failed.is_some() => {
+                let charge: Charge = serde_json::from_value(event.data.object.clone())?;
+                let Some(subscription_id) = charge.subscription.as_deref() else {
+                    return Ok(false);
+                };
+                let subscription = self
+                    .client
+                    .subscriptions()
+                    .retrieve(subscription_id)
+                    .await?;
+                let customer = self
+                    .client
+                    .customers()
+                    .retrieve(&subscription.customer)
+                    .await?;
+                let ctx = RenewalContext {
+                    event: event.clone(),
+                    subscription,
+                    customer,
+                    charge: Some(charge),
+                };
+                if let Some(handler) = self.on_payment_failed.as_mut() {
+                    handler(ctx).await;
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ClientOptions;
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn list(data: Vec<serde_json::Value>) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": data,
+            "has_more": false,
+            "url": "/v1/list",
+            "count": data.len()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_poll_once_resolves_renewal_context() {
+        let server = MockServer::start().await;
+        let subscription = fixtures::subscription("sub_1", "active");
+        let event = serde_json::json!({
+            "id": "evnt_1",
+            "object": "event",
+            "livemode": false,
+            "created": 1700000100,
+            "type": "subscription.renewed",
+            "data": { "object": subscription }
+        });
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("since", "1700000000"))
+            .respond_with(list(vec![event]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/customers/cus_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "cus_1",
+                "object": "customer",
+                "livemode": false,
+                "created": 1700000000
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/charges"))
+            .and(query_param("subscription", "sub_1"))
+            .respond_with(list(vec![]))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client =
+            PayjpClient::with_options("sk_test_xxxxx", options).expect("Failed to create client");
+
+        let renewed = Arc::new(Mutex::new(Vec::new()));
+        let sink = renewed.clone();
+        let mut watcher = RenewalWatcher::new(&client)
+            .since(1700000000)
+            .on_renewed(move |ctx| {
+                sink.lock().unwrap().push(ctx.customer.id);
+                async {}
+            });
+
+        assert_eq!(watcher.poll_once().await.expect("Failed to poll"), 1);
+        assert_eq!(*renewed.lock().unwrap(), ["cus_1"]);
+        assert_eq!(watcher.since, 1700000100);
+    }
+}
@@ -0,0 +1,295 @@
+//! Webhook delivery tracking.
+//!
+//! Record the ID of every webhook event you receive in a [`DedupStore`] to drop
+//! duplicate deliveries, and periodically run a [`DeliveryChecker`] against the
+//! events API to find events that were never delivered. A [`WebhookValidator`]
+//! rejects stale and replayed deliveries with a typed error.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use payjp::webhook::{DedupStore, DeliveryChecker, MemoryDedupStore, WebhookValidator};
+//! use payjp::{PayjpClient, PayjpError};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = PayjpClient::new("sk_test_xxxxx")?;
+//! let store = MemoryDedupStore::new();
+//!
+//! // In the webhook handler
+//! # let event_id = "evnt_xxxxx";
+//! if store.insert(event_id).await {
+//!     // First delivery: process the event
+//! }
+//!
+//! // Or reject stale and replayed deliveries
+//! let validator = WebhookValidator::new()
+//!     .max_age(Duration::from_secs(600))
+//!     .replay_window(Duration::from_secs(3600));
+//! # let event: payjp::Event = unimplemented!();
+//! match validator.validate(&event) {
+//!     Ok(()) => { /* process the event */ }
+//!     Err(PayjpError::StaleWebhook { .. } | PayjpError::ReplayedWebhook { .. }) => {
+//!         // Acknowledge without processing
+//!     }
+//!     Err(err) => return Err(err.into()),
+//! }
+//!
+//! // In a periodic job
+//! let report = DeliveryChecker::new(&client, &store)
+//!     .check(1_700_000_000, 1_700_086_400)
+//!     .await?;
+//! for event in report.missed {
+//!     println!("missed webhook: {} ({})", event.id, event.event_type);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::PayjpClient;
+use crate::error::{PayjpError, PayjpResult};
+use crate::resources::{Event, ListEventParams};
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::TryStreamExt;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Storage for the IDs of webhook events that have been received.
+///
+/// Implement this on top of a shared database or cache so that deduplication and
+/// delivery checks work across processes.
+#[async_trait]
+pub trait DedupStore: Send + Sync {
+    /// Record an event ID as received.
+    ///
+    /// Returns `true` if the ID had not been recorded before.
+    async fn insert(&self, event_id: &str) -> bool;
+
+    /// Check whether an event ID has been recorded.
+    async fn contains(&self, event_id: &str) -> bool;
+}
+
+/// In-memory [`DedupStore`] for single-process deployments and tests.
+#[derive(Debug, Default)]
+pub struct MemoryDedupStore {
+    seen: Mutex<HashSet<String>>,
+}
+
+impl MemoryDedupStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DedupStore for MemoryDedupStore {
+    async fn insert(&self, event_id: &str) -> bool {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).insert(event_id.to_string())
+    }
+
+    async fn contains(&self, event_id: &str) -> bool {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).contains(event_id)
+    }
+}
+
+/// Result of a [`DeliveryChecker::check`] run.
+#[derive(Debug, Clone)]
+pub struct DeliveryReport {
+    /// Number of events returned by the events API for the window.
+    pub checked: usize,
+
+    /// Events that are missing from the dedup store, oldest first.
+    pub missed: Vec<Event>,
+}
+
+/// Compares received webhook IDs against the events API to detect missed deliveries.
+pub struct DeliveryChecker<'a, S: ?Sized> {
+    client: &'a PayjpClient,
+    store: &'a S,
+}
+
+impl<'a, S: DedupStore + ?Sized> DeliveryChecker<'a, S> {
+    /// Create a checker backed by the given dedup store.
+    pub fn new(client: &'a PayjpClient, store: &'a S) -> Self {
+        Self { client, store }
+    }
+
+    /// Report events created between `since` and `until` (Unix timestamps) that
+    /// were never recorded in the dedup store.
+    ///
+    /// Leave a margin before `until` for webhooks that are still being retried.
+    pub async fn check(&self, since: i64, until: i64) -> PayjpResult<DeliveryReport> {
+        let events: Vec<Event> = self
+            .client
+            .events()
+            .list_all(ListEventParams::new().max_limit().since(since).until(until))
+            .try_collect()
+            .await?;
+
+        let checked = events.len();
+        let mut missed = Vec::new();
+        for event in events {
+            if !self.store.contains(&event.id).await {
+                missed.push(event);
+            }
+        }
+        missed.sort_by_key(|event| event.created);
+
+        Ok(DeliveryReport { checked, missed })
+    }
+}
+
+/// Rejects stale and replayed webhook deliveries before they are processed.
+///
+/// Event IDs are remembered in memory for the replay window, so replays are only
+/// detected within one process; use a [`DedupStore`] to deduplicate across processes.
+#[derive(Debug, Default)]
+pub struct WebhookValidator {
+    max_age: Option<Duration>,
+    replay_window: Option<Duration>,
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl WebhookValidator {
+    /// Create a validator that accepts every event (until limits are set).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject events whose `created` timestamp is older than `max_age`.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Reject events received again within `window` of their `created` timestamp.
+    ///
+    /// Events older than the window are rejected as stale, since a replay of them
+    /// could no longer be detected.
+    pub fn replay_window(mut self, window: Duration) -> Self {
+        self.replay_window = Some(window);
+        self
+    }
+
+    /// Check an event and record it as received.
+    ///
+    /// Returns [`PayjpError::StaleWebhook`] or [`PayjpError::ReplayedWebhook`] for
+    /// deliveries that should be acknowledged without being processed.
+    pub fn validate(&self, event: &Event) -> PayjpResult<()> {
+        self.validate_at(event, Utc::now().timestamp())
+    }
+
+    /// Forget a received event, so that a redelivery after failed processing is
+    /// accepted.
+    pub fn forget(&self, event_id: &str) {
+        self.seen.lock().unwrap_or_else(|e| e.into_inner()).remove(event_id);
+    }
+
+    fn validate_at(&self, event: &Event, now: i64) -> PayjpResult<()> {
+        let age = Duration::from_secs(now.saturating_sub(event.created).max(0) as u64);
+        let max_age = match (self.max_age, self.replay_window) {
+            (Some(max_age), Some(window)) => Some(max_age.min(window)),
+            (max_age, window) => max_age.or(window),
+        };
+        if let Some(max_age) = max_age.filter(|max_age| age > *max_age) {
+            return Err(PayjpError::StaleWebhook {
+                event_id: event.id.clone(),
+                age,
+                max_age,
+            });
+        }
+
+        let Some(window) = self.replay_window else {
+            return Ok(());
+        };
+        let oldest = now.saturating_sub(window.as_secs() as i64);
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, created| *created >= oldest);
+        if seen.insert(event.id.clone(), event.created).is_some() {
+            return Err(PayjpError::ReplayedWebhook {
+                event_id: event.id.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientOptions;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn event(id: &str, created: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "event",
+            "livemode": false,
+            "created": created,
+            "type": "charge.succeeded",
+            "data": { "object": { "id": "ch_1", "object": "charge" } }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_undelivered_events() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .and(query_param("since", "100"))
+            .and(query_param("until", "200"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "data": [event("evnt_3", 130), event("evnt_2", 120), event("evnt_1", 110)],
+                "has_more": false,
+                "url": "/v1/events",
+                "count": 3
+            })))
+            .mount(&server)
+            .await;
+
+        let options = ClientOptions::new().base_url(&server.uri());
+        let client = PayjpClient::with_options("sk_test_xxxxx", options)
+            .expect("Failed to create client");
+
+        let store = MemoryDedupStore::new();
+        assert!(store.insert("evnt_2").await);
+        assert!(!store.insert("evnt_2").await);
+
+        let report = DeliveryChecker::new(&client, &store)
+            .check(100, 200)
+            .await
+            .expect("Failed to check deliveries");
+
+        assert_eq!(report.checked, 3);
+        let missed: Vec<_> = report.missed.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(missed, ["evnt_1", "evnt_3"]);
+    }
+
+    #[test]
+    fn test_validator_rejects_stale_and_replayed_events() {
+        let validator = WebhookValidator::new()
+            .max_age(Duration::from_secs(300))
+            .replay_window(Duration::from_secs(600));
+        let fresh: Event = serde_json::from_value(event("evnt_1", 1_000)).unwrap();
+        let stale: Event = serde_json::from_value(event("evnt_2", 600)).unwrap();
+
+        assert!(validator.validate_at(&fresh, 1_100).is_ok());
+        assert!(matches!(
+            validator.validate_at(&fresh, 1_200),
+            Err(PayjpError::ReplayedWebhook { event_id }) if event_id == "evnt_1"
+        ));
+        assert!(matches!(
+            validator.validate_at(&stale, 1_100),
+            Err(PayjpError::StaleWebhook { age, max_age, .. })
+                if age == Duration::from_secs(500) && max_age == Duration::from_secs(300)
+        ));
+
+        validator.forget("evnt_1");
+        assert!(validator.validate_at(&fresh, 1_250).is_ok());
+    }
+}